@@ -0,0 +1,179 @@
+use crate::logging::LoggedOutput;
+use crate::tools;
+use crate::types::{CachedResponse, ForkStore};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Responses by request, and the requests whose response changed since the
+/// last `flush`.
+struct Cache {
+    responses: HashMap<String, CachedResponse>,
+    changed: Vec<String>,
+}
+
+/// Loaded by `load`; until then `gh_api` makes plain requests.
+static CACHE: Mutex<Option<Cache>> = Mutex::new(None);
+
+/// Load the saved API responses from `store`, so `gh_api` can make
+/// conditional requests.
+pub fn load(store: &dyn ForkStore) {
+    let responses = store.api_responses().unwrap_or_else(|e| {
+        tracing::warn!("Failed to load the API cache: {e}");
+        Vec::new()
+    });
+    if let Ok(mut cache) = CACHE.lock() {
+        *cache = Some(Cache {
+            responses: responses
+                .into_iter()
+                .map(|r| (r.request.clone(), r))
+                .collect(),
+            changed: Vec::new(),
+        });
+    }
+}
+
+/// Write the responses fetched (or revalidated) since the last call to
+/// `store`.
+pub fn flush(store: Option<&dyn ForkStore>) {
+    let Some(store) = store else {
+        return;
+    };
+    let changed: Vec<CachedResponse> = CACHE
+        .lock()
+        .ok()
+        .and_then(|mut cache| {
+            let cache = cache.as_mut()?;
+            let mut requests = std::mem::take(&mut cache.changed);
+            requests.sort();
+            requests.dedup();
+            Some(
+                requests
+                    .iter()
+                    .filter_map(|request| cache.responses.get(request).cloned())
+                    .collect(),
+            )
+        })
+        .unwrap_or_default();
+    if changed.is_empty() {
+        return;
+    }
+    if let Err(e) = store.save_api_responses(&changed) {
+        tracing::warn!("Failed to save the API cache: {e}");
+    }
+}
+
+/// `gh api <args>` (a GET, without `--paginate`): its stdout on success, or
+/// its stderr. Once `load`ed, the request carries the validators of the last
+/// response to it, and a 304 Not Modified, which doesn't count against the
+/// rate limit, returns the cached output.
+pub fn gh_api(args: &[&str]) -> Result<String, String> {
+    let request = args.join(" ");
+    let cached = CACHE.lock().ok().and_then(|cache| {
+        let cache = cache.as_ref()?;
+        Some(cache.responses.get(&request).cloned())
+    });
+    let Some(cached) = cached else {
+        return plain_request(args);
+    };
+
+    let mut command = tools::gh();
+    command.args(["api", "--include"]);
+    if let Some(etag) = cached.as_ref().and_then(|c| c.etag.as_deref()) {
+        command.args(["-H", &format!("If-None-Match: {etag}")]);
+    }
+    if let Some(since) = cached.as_ref().and_then(|c| c.last_modified.as_deref()) {
+        command.args(["-H", &format!("If-Modified-Since: {since}")]);
+    }
+    command.args(args);
+    let output = command.logged_output().map_err(|e| e.to_string())?;
+    let (status, headers, body) = split_response(&String::from_utf8_lossy(&output.stdout));
+
+    // gh exits non-zero for a 304, as for any status above 299
+    let response = match (status, cached) {
+        (Some(304), Some(cached)) => CachedResponse {
+            fetched_at: Utc::now(),
+            ..cached
+        },
+        _ if output.status.success() => CachedResponse {
+            request: request.clone(),
+            etag: headers.get("etag").cloned(),
+            last_modified: headers.get("last-modified").cloned(),
+            output: body,
+            fetched_at: Utc::now(),
+        },
+        _ => return Err(String::from_utf8_lossy(&output.stderr).into_owned()),
+    };
+    let body = response.output.clone();
+    if response.etag.is_some() || response.last_modified.is_some() {
+        if let Ok(mut cache) = CACHE.lock() {
+            if let Some(cache) = cache.as_mut() {
+                cache.responses.insert(request.clone(), response);
+                cache.changed.push(request);
+            }
+        }
+    }
+    Ok(body)
+}
+
+fn plain_request(args: &[&str]) -> Result<String, String> {
+    let output = tools::gh()
+        .arg("api")
+        .args(args)
+        .logged_output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Split `gh api --include` output into the status code, the headers (by
+/// lowercased name) and the body after the blank line.
+fn split_response(output: &str) -> (Option<u16>, HashMap<String, String>, String) {
+    let mut lines = output.split_inclusive('\n');
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok());
+    let mut headers = HashMap::new();
+    let mut consumed = output.find('\n').map_or(output.len(), |i| i + 1);
+    for line in lines {
+        consumed += line.len();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+    (
+        status,
+        headers,
+        output[consumed.min(output.len())..].to_string(),
+    )
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_response() {
+        let output = "HTTP/2.0 200 OK\r\nEtag: W/\"abc\"\r\nX-Ratelimit-Remaining: 4999\r\n\r\n3\n";
+        let (status, headers, body) = split_response(output);
+        assert_eq!(status, Some(200));
+        assert_eq!(headers["etag"], "W/\"abc\"");
+        assert_eq!(body, "3\n");
+
+        let (status, headers, body) = split_response("HTTP/2.0 304 Not Modified\r\n\r\n");
+        assert_eq!(status, Some(304));
+        assert!(headers.is_empty());
+        assert_eq!(body, "");
+    }
+}
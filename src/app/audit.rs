@@ -0,0 +1,40 @@
+use super::App;
+use crate::logging;
+use crate::types::Mode;
+
+/// Commands the audit overlay loads, newest first.
+const AUDIT_SHOWN: usize = 500;
+
+impl App {
+    /// Open the audit overlay (`T`) on the latest commands the tool ran.
+    pub fn open_audit(&mut self) {
+        let Some(store) = &self.store else {
+            self.show_message("The audit log needs the cache");
+            return;
+        };
+        logging::flush_audit(Some(store.as_ref()));
+        match store.recent_commands(AUDIT_SHOWN) {
+            Ok(entries) => {
+                self.audit = entries;
+                self.audit_selected = 0;
+                self.mode = Mode::AuditOverlay;
+            }
+            Err(e) => self.show_message(&format!("Failed to read the audit log: {e}")),
+        }
+    }
+
+    pub fn audit_next(&mut self) {
+        if self.audit_selected + 1 < self.audit.len() {
+            self.audit_selected += 1;
+        }
+    }
+
+    pub fn audit_previous(&mut self) {
+        self.audit_selected = self.audit_selected.saturating_sub(1);
+    }
+
+    pub fn close_audit(&mut self) {
+        self.audit.clear();
+        self.mode = Mode::Selecting;
+    }
+}
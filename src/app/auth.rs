@@ -0,0 +1,52 @@
+use super::App;
+use crate::github::is_authenticated;
+use crate::types::{ErrorAction, ErrorDetails, SyncStatus};
+
+impl App {
+    /// Pause notice for a batch that hit "not logged in": one popup offering
+    /// an inline `gh auth login`.
+    pub fn show_auth_popup(&mut self) {
+        self.auth_paused = true;
+        self.show_error_popup(ErrorDetails {
+            title: "GitHub Login Required".to_string(),
+            message: "gh is not logged in, so the sync queue is paused.\n\n\
+                      Log in to resume the remaining forks,\n\
+                      or dismiss to skip them."
+                .to_string(),
+            action: Some(ErrorAction {
+                label: "gh auth login".to_string(),
+                gh_args: vec!["auth".to_string(), "login".to_string()],
+                interactive: true,
+                retry: None,
+            }),
+        });
+    }
+
+    /// After a re-auth attempt: resume the queue if `gh` is logged in now,
+    /// otherwise show the popup again.
+    pub fn finish_reauth(&mut self) {
+        if !is_authenticated() {
+            self.show_auth_popup();
+            return;
+        }
+        self.auth_paused = false;
+        if let Some(queue) = &self.queue {
+            queue.resume();
+        }
+        self.show_message("Logged in, resuming sync");
+    }
+
+    /// Give up on a paused batch: skip every fork that hasn't started. They
+    /// stay in the persisted queue, so the next launch offers to resume them.
+    pub fn skip_after_auth(&mut self) {
+        self.auth_paused = false;
+        let Some(queue) = &self.queue else {
+            return;
+        };
+        for (idx, _) in queue.drain() {
+            if let Some(status) = self.statuses.get_mut(idx) {
+                *status = SyncStatus::Skipped("gh not logged in".to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use super::App;
+use crate::types::{ConfirmList, Fork, ModalAction, Mode};
+
+impl App {
+    /// Forks the Sync/Resume confirm modal is about: the selected ones, or
+    /// those left over from the interrupted batch.
+    pub fn confirm_targets(&self) -> Vec<usize> {
+        match self.modal_action {
+            ModalAction::Sync => (0..self.forks.len())
+                .filter(|&i| self.selected[i])
+                .collect(),
+            ModalAction::Resume => (0..self.forks.len())
+                .filter(|&i| self.resume.contains(&self.forks[i].full_name()))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn scroll_confirm_list(&mut self, down: bool) {
+        let max = self
+            .confirm_targets()
+            .len()
+            .saturating_sub(ConfirmList::ROWS);
+        let list = &mut self.confirm_list;
+        list.scroll = if down {
+            (list.scroll + 1).min(max)
+        } else {
+            list.scroll.saturating_sub(1)
+        };
+    }
+
+    pub fn set_dirty(&mut self, id: String, dirty: bool) {
+        if self.confirm_list.checked {
+            self.confirm_list.dirty.insert(id, dirty);
+        }
+    }
+
+    /// The clones to check for uncommitted changes when a Sync/Resume
+    /// confirm modal has just opened. The list is reset once it's closed.
+    pub fn wants_dirty_check(&mut self) -> Option<Vec<Fork>> {
+        let confirming = self.mode == Mode::ConfirmModal
+            && matches!(self.modal_action, ModalAction::Sync | ModalAction::Resume);
+        if !confirming {
+            if self.confirm_list.checked {
+                self.confirm_list = ConfirmList::default();
+            }
+            return None;
+        }
+        if self.confirm_list.checked {
+            return None;
+        }
+        self.confirm_list.checked = true;
+        Some(
+            self.confirm_targets()
+                .into_iter()
+                .map(|i| &self.forks[i])
+                .filter(|f| f.is_cloned)
+                .cloned()
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,102 @@
+use super::App;
+use crate::types::{
+    CacheStatus, Fork, GitStatus, GitStatusCheck, UpstreamCommits, UpstreamCommitsCheck,
+};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How long the cursor has to rest on a fork before its upstream commits
+/// and local status are fetched, so scrolling through the list doesn't fire off requests.
+const UPSTREAM_COMMITS_DELAY: Duration = Duration::from_millis(300);
+
+impl App {
+    /// Show or hide the details pane, whatever the terminal width.
+    pub fn toggle_details(&mut self) {
+        let show = !self.details.shown;
+        self.details.forced = Some(show);
+        if !show {
+            self.details.focused = false;
+        }
+    }
+
+    /// Move focus into the details pane (j/k scroll it) if it's shown.
+    pub fn focus_details(&mut self) {
+        if self.details.shown {
+            self.details.focused = true;
+            self.details.scroll = 0;
+        }
+    }
+
+    pub fn unfocus_details(&mut self) {
+        self.details.focused = false;
+    }
+
+    pub fn scroll_details(&mut self, down: bool) {
+        self.details.scroll = if down {
+            self.details.scroll.saturating_add(1)
+        } else {
+            self.details.scroll.saturating_sub(1)
+        };
+    }
+
+    /// The fork under the cursor, if the details pane is showing it and its
+    /// upstream commits haven't been fetched yet. Marks them as loading.
+    pub fn wants_upstream_commits(&mut self) -> Option<Fork> {
+        if !self.details.shown
+            || self.cache_status == CacheStatus::Offline
+            || self.last_activity.elapsed() < UPSTREAM_COMMITS_DELAY
+        {
+            return None;
+        }
+        let fork = self.current_fork().filter(|f| f.is_github_fork())?;
+        let id = fork.full_name();
+        if self.upstream_commits.contains_key(&id) {
+            return None;
+        }
+        let fork = fork.clone();
+        self.upstream_commits
+            .insert(id, UpstreamCommitsCheck::Loading);
+        Some(fork)
+    }
+
+    pub fn set_upstream_commits(&mut self, id: String, result: Result<UpstreamCommits, String>) {
+        let check = match result {
+            Ok(commits) => UpstreamCommitsCheck::Loaded(commits),
+            Err(e) => UpstreamCommitsCheck::Failed(e),
+        };
+        self.upstream_commits.insert(id, check);
+    }
+
+    /// The clone under the cursor, if the details pane is showing it and its
+    /// git status hasn't been read since the cursor moved to it. Marks it as
+    /// loading.
+    pub fn wants_git_status(&mut self) -> Option<(String, PathBuf)> {
+        if !self.details.shown || self.last_activity.elapsed() < UPSTREAM_COMMITS_DELAY {
+            return None;
+        }
+        let fork = self.current_fork().filter(|f| f.is_cloned)?;
+        let id = fork.full_name();
+        if self
+            .git_status
+            .as_ref()
+            .is_some_and(|(shown, _)| *shown == id)
+        {
+            return None;
+        }
+        let path = fork.local_path.clone();
+        self.git_status = Some((id.clone(), GitStatusCheck::Loading));
+        Some((id, path))
+    }
+
+    /// Record a git status, unless the cursor has moved on since.
+    pub fn set_git_status(&mut self, id: &str, result: Result<GitStatus, String>) {
+        if let Some((shown, check)) = &mut self.git_status {
+            if shown == id {
+                *check = match result {
+                    Ok(status) => GitStatusCheck::Loaded(status),
+                    Err(e) => GitStatusCheck::Failed(e),
+                };
+            }
+        }
+    }
+}
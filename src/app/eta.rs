@@ -0,0 +1,116 @@
+use super::App;
+use crate::sync::SLOW_MODE_DELAY;
+use crate::types::{SyncStatus, Toast, SYNC_STEPS};
+use crate::ui::format_duration;
+use std::time::{Duration, Instant};
+
+impl App {
+    /// Time forks of the running batch: from their first in-flight status
+    /// to their final one.
+    pub(super) fn record_timing(&mut self, idx: usize, status: &SyncStatus) {
+        if status.is_finished() {
+            self.commands.remove(&idx);
+            if let Some(started) = self.fork_started.remove(&idx) {
+                self.fork_durations
+                    .insert(self.forks[idx].full_name(), started.elapsed());
+            }
+        } else if *status != SyncStatus::Pending {
+            self.fork_started.entry(idx).or_insert_with(Instant::now);
+        }
+    }
+
+    /// Forget the timings of the last batch and start timing a new one.
+    pub(super) fn reset_timings(&mut self) {
+        self.batch_started = Some(Instant::now());
+        self.fork_started.clear();
+        self.fork_durations.clear();
+        self.commands.clear();
+    }
+
+    /// Note the command an in-flight fork is running.
+    pub fn set_command(&mut self, id: &str, line: String) {
+        let Some(idx) = self.fork_index(id) else {
+            return;
+        };
+        if self.statuses.get(idx).is_some_and(|s| !s.is_finished()) {
+            self.commands.insert(idx, line);
+        }
+    }
+
+    /// How long a fork of the batch has been running, or took once finished.
+    /// Finished forks keep their duration until the next batch starts.
+    pub fn fork_elapsed(&self, idx: usize) -> Option<Duration> {
+        self.fork_started
+            .get(&idx)
+            .map(Instant::elapsed)
+            .or_else(|| {
+                self.fork_durations
+                    .get(&self.forks.get(idx)?.full_name())
+                    .copied()
+            })
+    }
+
+    /// Wall time of the running batch.
+    pub fn batch_elapsed(&self) -> Option<Duration> {
+        self.batch_started.map(|started| started.elapsed())
+    }
+
+    /// Toast the wall time of the batch that just finished.
+    pub(super) fn announce_batch_time(&mut self) {
+        if let Some(started) = self.batch_started.take() {
+            self.add_toast(Toast::info(format!(
+                "Batch done in {}",
+                format_duration(started.elapsed())
+            )));
+        }
+    }
+
+    /// Estimated time left in the batch: the average fork duration so far,
+    /// times the rounds of forks the workers still have to get through.
+    /// `None` until a fork has finished.
+    pub fn eta(&self) -> Option<Duration> {
+        let finished = u32::try_from(self.fork_durations.len()).ok()?;
+        if finished == 0 {
+            return None;
+        }
+        let average = self.fork_durations.values().sum::<Duration>() / finished;
+        let remaining = self
+            .statuses
+            .iter()
+            .zip(&self.selected)
+            .filter(|(status, &selected)| selected && !status.is_finished())
+            .count();
+        let (workers, per_fork) = if self.is_slow_mode() {
+            (1, average + SLOW_MODE_DELAY)
+        } else {
+            (self.jobs.max(1), average)
+        };
+        let rounds = u32::try_from(remaining.div_ceil(workers)).unwrap_or(u32::MAX);
+        Some(per_fork * rounds)
+    }
+
+    /// Completion of the running batch (0.0 to 1.0), counting forks in
+    /// flight by the sync steps they have finished.
+    pub fn batch_progress(&self) -> f64 {
+        let total = self.selected_count();
+        if total == 0 {
+            return 0.0;
+        }
+        let done: f64 = self
+            .statuses
+            .iter()
+            .zip(&self.selected)
+            .filter(|(_, &selected)| selected)
+            .map(|(status, _)| {
+                if status.is_finished() {
+                    1.0
+                } else {
+                    status
+                        .sync_step()
+                        .map_or(0.0, |step| f64::from(step - 1) / f64::from(SYNC_STEPS))
+                }
+            })
+            .sum();
+        (done / total as f64).min(1.0)
+    }
+}
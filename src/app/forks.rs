@@ -0,0 +1,303 @@
+use super::App;
+use crate::local::apply_path_overrides;
+use crate::types::{CacheStatus, Fork, ForkDelta, Mode, SyncStatus, Toast};
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+impl App {
+    /// Check whether the fork at `idx` is on the ignore list.
+    pub fn is_ignored(&self, idx: usize) -> bool {
+        self.forks
+            .get(idx)
+            .is_some_and(|f| self.ignored.contains(&f.full_name()))
+    }
+
+    /// Where the fork `owner/name` is in the list now (background results
+    /// name their fork, since indices shift when the list changes).
+    pub fn fork_index(&self, id: &str) -> Option<usize> {
+        self.forks.iter().position(|f| f.full_name() == id)
+    }
+
+    /// Number of ignored forks present in the current list.
+    pub fn ignored_count(&self) -> usize {
+        (0..self.forks.len())
+            .filter(|&i| self.is_ignored(i))
+            .count()
+    }
+
+    /// Add a fork created with `n` to the list and cache. It goes to the top
+    /// like the newest fork would, or to the bottom during a sync batch so
+    /// in-flight indices stay valid.
+    pub fn add_new_fork(&mut self, fork: Fork, clone_error: Option<String>) {
+        let id = fork.full_name();
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_forks(std::slice::from_ref(&fork)) {
+                self.show_message(&format!("Failed to cache {id}: {e}"));
+            }
+        }
+        self.track_action("new fork");
+
+        if let Some(idx) = self.forks.iter().position(|f| f.full_name() == id) {
+            self.forks[idx].is_cloned |= fork.is_cloned;
+        } else if self.mode == Mode::Syncing {
+            let mut fork = fork;
+            apply_path_overrides(std::slice::from_mut(&mut fork), &self.path_overrides);
+            self.forks.push(fork);
+            self.selected.push(false);
+            self.statuses.push(SyncStatus::Pending);
+            self.update_search();
+        } else {
+            let mut forks = vec![fork];
+            forks.extend(self.forks.iter().cloned());
+            self.merge_refreshed_forks(forks);
+        }
+
+        self.add_toast(match clone_error {
+            None => Toast::success(format!("Forked and cloned {id}")),
+            Some(e) => Toast::warning(format!("Forked {id}, clone failed: {}", e.trim())),
+        });
+    }
+
+    /// Record a fork's new visibility and persist it.
+    pub fn set_visibility(&mut self, id: &str, visibility: String) {
+        let Some(idx) = self.fork_index(id) else {
+            return;
+        };
+        let fork = &mut self.forks[idx];
+        fork.visibility = Some(visibility);
+        let message = format!(
+            "{} is now {}",
+            fork.full_name(),
+            fork.visibility.as_deref().unwrap_or_default()
+        );
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_forks(std::slice::from_ref(&self.forks[idx])) {
+                self.show_message(&format!("Failed to save visibility: {e}"));
+                return;
+            }
+        }
+        self.add_toast(Toast::success(message));
+    }
+
+    /// Toggle the ignore flag for the current fork and persist it.
+    pub fn toggle_ignore_current(&mut self) {
+        let Some(idx) = self.current_fork_index() else {
+            return;
+        };
+        let id = self.forks[idx].full_name();
+        let ignore = !self.ignored.contains(&id);
+
+        if let Some(store) = &self.store {
+            if let Err(e) = store.set_ignored(&id, ignore) {
+                self.show_message(&format!("Failed to save ignore list: {e}"));
+                return;
+            }
+        }
+
+        let cursor = self.state.selected();
+        if ignore {
+            self.ignored.insert(id.clone());
+            self.selected[idx] = false;
+            self.show_message(&format!("Ignored {id}"));
+        } else {
+            self.ignored.remove(&id);
+            self.show_message(&format!("Un-ignored {id}"));
+        }
+        self.update_search();
+        self.restore_cursor(cursor);
+    }
+
+    /// Toggle whether ignored forks are shown in the list.
+    pub fn toggle_show_hidden(&mut self) {
+        let current = self.current_fork_index();
+        self.show_hidden = !self.show_hidden;
+        self.update_search();
+        // Keep the cursor on the same fork if it is still visible
+        if let Some(pos) = current.and_then(|c| self.search_results.iter().position(|&i| i == c)) {
+            self.state.select(Some(pos));
+        }
+        let msg = if self.show_hidden {
+            "Showing ignored forks"
+        } else {
+            "Hiding ignored forks"
+        };
+        self.show_message(msg);
+    }
+
+    /// Re-select a visible row by position, clamped to the list length.
+    pub(super) fn restore_cursor(&mut self, cursor: Option<usize>) {
+        if self.search_results.is_empty() {
+            self.state.select(None);
+        } else if let Some(pos) = cursor {
+            self.state
+                .select(Some(pos.min(self.search_results.len() - 1)));
+        }
+    }
+
+    /// Replace the fork list with a refreshed one, matching forks by
+    /// `owner/name` so selections, statuses and the cursor survive even when
+    /// forks were added or removed upstream. Returns (added, removed) counts.
+    pub fn merge_refreshed_forks(&mut self, mut new_forks: Vec<Fork>) -> (usize, usize) {
+        let mut previous: HashMap<String, (bool, SyncStatus)> = self
+            .forks
+            .iter()
+            .zip(self.selected.iter().zip(&self.statuses))
+            .map(|(f, (&sel, status))| (f.full_name(), (sel, status.clone())))
+            .collect();
+        let current = self.current_fork().map(Fork::full_name);
+
+        let mut added = 0;
+        let mut selected = Vec::with_capacity(new_forks.len());
+        let mut statuses = Vec::with_capacity(new_forks.len());
+        for fork in &new_forks {
+            if let Some((sel, status)) = previous.remove(&fork.full_name()) {
+                selected.push(sel);
+                statuses.push(status);
+            } else {
+                added += 1;
+                selected.push(false);
+                statuses.push(SyncStatus::Pending);
+            }
+        }
+        let removed = previous.len();
+
+        apply_path_overrides(&mut new_forks, &self.path_overrides);
+        self.forks = new_forks;
+        self.selected = selected;
+        self.statuses = statuses;
+        self.update_search();
+
+        // Keep the cursor on the same fork if it still exists
+        if let Some(pos) = current.and_then(|id| {
+            self.search_results
+                .iter()
+                .position(|&i| self.forks[i].full_name() == id)
+        }) {
+            self.state.select(Some(pos));
+        }
+
+        (added, removed)
+    }
+
+    /// Whether the session has been idle long enough, with data old enough,
+    /// to refresh from GitHub on its own (`idle_refresh_minutes`).
+    pub fn should_auto_refresh(&self) -> bool {
+        let minutes = self.config.idle_refresh_minutes;
+        if minutes == 0
+            || self.mode != Mode::Selecting
+            || self.cache_status == (CacheStatus::Stale { refreshing: true })
+            || self.last_activity.elapsed() < Duration::from_secs(minutes * 60)
+        {
+            return false;
+        }
+        self.refreshed_at.is_none_or(|at| {
+            (Utc::now() - at).num_minutes() >= i64::try_from(minutes).unwrap_or(i64::MAX)
+        })
+    }
+
+    /// Merge an incremental refresh into the list (or the refresh waiting for
+    /// the sync batch): updated forks keep their place, new ones go on top
+    /// and archived ones are dropped.
+    pub fn apply_delta(&mut self, delta: ForkDelta) {
+        let archived: HashSet<&String> = delta.archived.iter().collect();
+        let mut updated: HashMap<String, Fork> = delta
+            .updated
+            .into_iter()
+            .map(|f| (f.full_name(), f))
+            .collect();
+        let base = self
+            .pending_refresh
+            .take()
+            .unwrap_or_else(|| self.forks.clone());
+        let kept: Vec<Fork> = base
+            .into_iter()
+            .filter(|f| !archived.contains(&f.full_name()))
+            .map(|f| updated.remove(&f.full_name()).unwrap_or(f))
+            .collect();
+
+        let mut forks: Vec<Fork> = updated.into_values().collect();
+        forks.sort_by_key(|f| std::cmp::Reverse(f.created_at));
+        forks.extend(kept);
+        self.apply_refresh(forks);
+    }
+
+    /// Apply a background refresh, deferring it while a sync batch is
+    /// running (its queue refers to forks by index).
+    pub fn apply_refresh(&mut self, mut new_forks: Vec<Fork>) {
+        // Local upstream-remote repos never come from GitHub; keep them
+        let fetched: HashSet<String> = new_forks.iter().map(Fork::full_name).collect();
+        new_forks.extend(
+            self.forks
+                .iter()
+                .filter(|f| !f.is_github_fork() && !fetched.contains(&f.full_name()))
+                .cloned(),
+        );
+
+        self.cache_status = CacheStatus::Fresh;
+        self.refreshed_at = Some(Utc::now());
+        if self.mode == Mode::Syncing {
+            self.pending_refresh = Some(new_forks);
+            self.show_message("Refresh received, will apply after sync");
+            return;
+        }
+
+        let (added, removed) = self.merge_refreshed_forks(new_forks);
+        let summary = match (added, removed) {
+            (0, 0) => "Forks refreshed: no changes".to_string(),
+            (a, 0) => format!("Forks refreshed: {a} added"),
+            (0, r) => format!("Forks refreshed: {r} removed"),
+            (a, r) => format!("Forks refreshed: {a} added, {r} removed"),
+        };
+        self.add_toast(Toast::info(summary));
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::ToolHomes;
+    use std::path::PathBuf;
+
+    fn forks(names: &[&str]) -> Vec<Fork> {
+        names.iter().map(|name| Fork::test("me", name)).collect()
+    }
+
+    #[test]
+    fn test_merge_refreshed_forks_keeps_state_by_name() {
+        let mut app = App::new(
+            forks(&["alpha", "beta", "gamma"]),
+            false,
+            ToolHomes::new(vec![PathBuf::from("/tmp/test")]),
+            CacheStatus::Fresh,
+            None,
+        );
+        app.selected = vec![false, true, false];
+        app.statuses[1] = SyncStatus::Synced(Some(2));
+        app.statuses[2] = SyncStatus::Failed("boom".to_string());
+        app.state.select(Some(1));
+
+        // gamma moved to the front, alpha is gone, delta is new
+        let (added, removed) = app.merge_refreshed_forks(forks(&["gamma", "delta", "beta"]));
+        assert_eq!((added, removed), (1, 1));
+        let names: Vec<String> = app.forks.iter().map(|f| f.name.clone()).collect();
+        assert_eq!(names, ["gamma", "delta", "beta"]);
+        assert_eq!(app.selected, [false, false, true]);
+        assert_eq!(
+            app.statuses,
+            [
+                SyncStatus::Failed("boom".to_string()),
+                SyncStatus::Pending,
+                SyncStatus::Synced(Some(2)),
+            ]
+        );
+        assert_eq!(
+            app.current_fork().map(Fork::full_name).as_deref(),
+            Some("me/beta")
+        );
+    }
+}
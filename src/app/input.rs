@@ -0,0 +1,41 @@
+use super::App;
+use crate::local::parse_repo_slug;
+use crate::types::{InputPrompt, InputPurpose, Mode};
+
+impl App {
+    /// Show a text prompt in the help bar, pre-filled with `initial`.
+    pub fn open_prompt(&mut self, purpose: InputPurpose, label: &str, initial: String) {
+        self.input = Some(InputPrompt {
+            purpose,
+            label: label.to_string(),
+            buffer: initial,
+        });
+        self.mode = Mode::Input;
+    }
+
+    pub fn cancel_input(&mut self) {
+        self.input = None;
+        self.mode = Mode::Selecting;
+    }
+
+    /// Close the prompt and act on what was typed. Returns the upstream
+    /// `(owner, name)` to fork for the `n` prompt, which needs a background task.
+    pub fn submit_input(&mut self) -> Option<(String, String)> {
+        let prompt = self.input.take()?;
+        self.mode = Mode::Selecting;
+        match prompt.purpose {
+            InputPurpose::Reminder(id) => self.apply_reminder_input(&id, &prompt.buffer),
+            InputPurpose::LocalPath(id) => self.apply_local_path_input(&id, &prompt.buffer),
+            InputPurpose::NewFork => {
+                let upstream = parse_repo_slug(&prompt.buffer);
+                if upstream.is_none() {
+                    self.show_message("Expected owner/repo or a GitHub URL");
+                }
+                return upstream;
+            }
+            // The cursor already moved while typing
+            InputPurpose::GoTo => {}
+        }
+        None
+    }
+}
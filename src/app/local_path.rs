@@ -0,0 +1,60 @@
+use super::App;
+use crate::paths::expand_home;
+use crate::types::InputPurpose;
+use std::path::{Path, PathBuf};
+
+impl App {
+    /// Prompt for where the current fork is cloned, pre-filled with the path
+    /// in use. For clones that predate the `<tool home>/<owner>/<name>` layout.
+    pub fn prompt_local_path(&mut self) {
+        let Some(fork) = self.current_fork() else {
+            return;
+        };
+        let id = fork.full_name();
+        let initial = fork.local_path.display().to_string();
+        self.open_prompt(
+            InputPurpose::LocalPath(id),
+            "Clone path (empty resets to tool home)",
+            initial,
+        );
+    }
+
+    pub(super) fn apply_local_path_input(&mut self, id: &str, input: &str) {
+        let input = input.trim();
+        let path = (!input.is_empty()).then(|| expand_home(Path::new(input)));
+        if path.as_ref().is_some_and(|p| p.is_relative()) {
+            self.show_message("Expected an absolute path (or ~/...)");
+            return;
+        }
+        self.set_path_override(id, path);
+    }
+
+    fn set_path_override(&mut self, id: &str, path: Option<PathBuf>) {
+        let Some(idx) = self.forks.iter().position(|f| f.full_name() == id) else {
+            return;
+        };
+        if let Some(store) = &self.store {
+            if let Err(e) = store.set_path_override(id, path.as_deref()) {
+                self.show_message(&format!("Failed to save clone path: {e}"));
+                return;
+            }
+        }
+
+        let fork = &mut self.forks[idx];
+        if let Some(path) = path {
+            self.path_overrides.insert(id.to_string(), path.clone());
+            fork.relocate(path);
+        } else {
+            self.path_overrides.remove(id);
+            let (path, _) = self.tool_homes.locate(&fork.owner, &fork.name);
+            fork.relocate(path);
+        }
+
+        let message = match (self.path_overrides.contains_key(id), fork.is_cloned) {
+            (true, true) => format!("{id} is cloned at {}", fork.local_path.display()),
+            (true, false) => format!("No clone at {} yet", fork.local_path.display()),
+            (false, _) => format!("{id} uses tool home again"),
+        };
+        self.show_message(&message);
+    }
+}
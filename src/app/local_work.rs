@@ -0,0 +1,50 @@
+use super::App;
+use crate::types::{Fork, Mode};
+
+impl App {
+    /// Select every visible fork, or deselect them all if they already are.
+    /// With `select_all_skips_dirty`, clones holding local work are left out.
+    pub fn select_all(&mut self) {
+        let (eligible, busy): (Vec<usize>, Vec<usize>) = self
+            .visible_forks()
+            .iter()
+            .partition(|&&i| !self.config.select_all_skips_dirty || !self.has_local_work(i));
+        let all_selected = eligible.iter().all(|&i| self.selected[i]);
+        for &i in &eligible {
+            self.selected[i] = !all_selected;
+        }
+        if !all_selected && !busy.is_empty() {
+            self.show_message(&format!(
+                "Left out {} clones with uncommitted changes or unpushed commits",
+                busy.len()
+            ));
+        }
+    }
+
+    /// Whether the fork's clone has uncommitted changes or unpushed commits,
+    /// as of the last scan.
+    pub fn has_local_work(&self, idx: usize) -> bool {
+        self.local_work.busy.contains(&self.forks[idx].full_name())
+    }
+
+    pub fn set_local_work(&mut self, id: String, busy: bool) {
+        if busy {
+            self.local_work.busy.insert(id);
+        } else {
+            self.local_work.busy.remove(&id);
+        }
+    }
+
+    /// The clones to scan for local work: all of them, at startup and after
+    /// each batch, when `select_all_skips_dirty` is on.
+    pub fn wants_local_work_scan(&mut self) -> Option<Vec<Fork>> {
+        if !self.config.select_all_skips_dirty
+            || self.local_work.scanned
+            || self.mode == Mode::Syncing
+        {
+            return None;
+        }
+        self.local_work.scanned = true;
+        Some(self.forks.iter().filter(|f| f.is_cloned).cloned().collect())
+    }
+}
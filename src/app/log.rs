@@ -0,0 +1,57 @@
+use super::App;
+use crate::types::{ErrorDetails, Mode, SyncStatus, Toast};
+
+/// Lines kept per fork; older output is dropped.
+const MAX_LOG_LINES: usize = 500;
+
+impl App {
+    /// Append output to the fork's log (for this session only).
+    pub fn append_log(&mut self, id: &str, lines: Vec<String>) {
+        let log = self.logs.entry(id.to_string()).or_default();
+        log.extend(lines);
+        if log.len() > MAX_LOG_LINES {
+            log.drain(..log.len() - MAX_LOG_LINES);
+        }
+    }
+
+    /// The log of the fork under the cursor.
+    pub fn current_log(&self) -> &[String] {
+        self.current_fork()
+            .and_then(|f| self.logs.get(&f.full_name()))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn open_log(&mut self) {
+        if self.current_fork().is_some() {
+            self.mode = Mode::LogOverlay;
+        }
+    }
+
+    /// Show the whole error of a failed fork in the error popup (the list
+    /// only has room for its start).
+    pub fn show_full_error(&mut self) {
+        let Some(idx) = self.current_fork_index() else {
+            return;
+        };
+        let SyncStatus::Failed(err) = &self.statuses[idx] else {
+            self.show_message("No error to show for this fork");
+            return;
+        };
+        self.show_error_popup(ErrorDetails {
+            title: format!("{} failed", self.forks[idx].full_name()),
+            message: err.clone(),
+            action: None,
+        });
+    }
+
+    /// Log and announce a finished force-sync.
+    pub fn report_force_sync(&mut self, name: &str, discarded: usize) {
+        let message = match discarded {
+            0 => format!("Force-synced {name}"),
+            1 => format!("Force-synced {name}, discarding 1 local commit"),
+            n => format!("Force-synced {name}, discarding {n} local commits"),
+        };
+        self.append_log(name, vec![message.clone()]);
+        self.add_toast(Toast::success(message));
+    }
+}
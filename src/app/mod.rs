@@ -0,0 +1,480 @@
+mod audit;
+mod auth;
+mod confirm;
+mod details;
+mod eta;
+mod forks;
+mod input;
+mod local_path;
+mod local_work;
+mod log;
+mod paging;
+mod plan;
+mod preview;
+mod profiles;
+mod reconcile;
+mod reminders;
+mod resume;
+mod risks;
+mod search;
+mod sort;
+mod stashes;
+mod stats;
+mod toasts;
+mod undo;
+mod usage;
+
+use crate::config::Config;
+use crate::paths::ToolHomes;
+use crate::sync::{SyncOptions, SyncQueue};
+use crate::types::{
+    AuditEntry, BackupRecord, CacheStatus, ConfirmList, DetailsPane, ErrorDetails, FailureRecord,
+    Fork, ForkStats, ForkStore, GitStatusCheck, InputPrompt, LocalFinding, LocalWork, ModalAction,
+    Mode, PlanView, Preview, ProfileSwitch, Reminder, RiskCheck, SnapshotDiff, SortColumn,
+    StashRecord, SyncStatus, Toast, UpstreamCommitsCheck, UsageSession, UsageStats, FAILURES_SHOWN,
+};
+use chrono::{DateTime, Utc};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use ratatui::layout::Rect;
+use ratatui::widgets::TableState;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+#[allow(clippy::struct_excessive_bools)] // Independent UI flags
+pub struct App {
+    pub forks: Vec<Fork>,
+    pub statuses: Vec<SyncStatus>,
+    pub state: TableState,
+    pub selected: Vec<bool>,
+    pub mode: Mode,
+    pub dry_run: bool,
+    // User configuration (config.toml merged with CLI flags)
+    pub config: Config,
+    // Cache database location (for background refreshes)
+    pub db_file: PathBuf,
+    pub tool_homes: ToolHomes,
+    // Root the clone modal clones into (index into `tool_homes`)
+    pub clone_root: usize,
+    // Worker count and the queue of the running sync batch
+    pub jobs: usize,
+    pub queue: Option<Arc<SyncQueue>>,
+    // Queue paused until `gh` is logged in again
+    pub auth_paused: bool,
+    // Ctrl-C: the batch is winding down, and the app quits once it's done
+    pub stopping: bool,
+    pub spinner_tick: usize,
+    pub last_tick: Instant,
+    // Last keypress, and when the fork list was last fetched (idle refresh)
+    pub last_activity: Instant,
+    pub refreshed_at: Option<DateTime<Utc>>,
+    pub modal_button: usize,
+    pub modal_action: ModalAction,
+    // Search state
+    pub search_query: String,
+    pub search_results: Vec<usize>,
+    pub fuzzy_matcher: SkimMatcherV2,
+    // Stats cache
+    pub stats_cache: Option<ForkStats>,
+    // Changes between the last two refreshes (loaded when the overlay opens)
+    pub changes: Option<SnapshotDiff>,
+    // Status message (legacy, keeping for compatibility)
+    pub status_message: Option<(String, Instant)>,
+    // Cache status
+    pub cache_status: CacheStatus,
+    // The last full fetch stopped at the fallback's fork limit
+    pub forks_truncated: bool,
+    // Toast notifications
+    pub toasts: VecDeque<Toast>,
+    // Error popup details
+    pub error_details: Option<ErrorDetails>,
+    // First message line shown in the error popup (j/k scroll)
+    pub error_scroll: u16,
+    // Previous mode (to return to after error popup)
+    pub previous_mode: Option<Mode>,
+    // Persistent storage (None if the cache couldn't be opened)
+    pub store: Option<Box<dyn ForkStore>>,
+    // Ignored forks (by `owner/name`) and whether to show them anyway
+    pub ignored: HashSet<String>,
+    // Clone locations set with `p`, by `owner/name`
+    pub path_overrides: HashMap<String, PathBuf>,
+    pub show_hidden: bool,
+    // Refreshed fork list received mid-sync, applied once the batch finishes
+    pub pending_refresh: Option<Vec<Fork>>,
+    // Local usage statistics (never leave the machine)
+    pub usage_enabled: bool,
+    pub usage: UsageSession,
+    pub usage_stats: Option<UsageStats>,
+    // Local clone reconciliation overlay
+    pub reconcile: Vec<LocalFinding>,
+    pub reconcile_selected: usize,
+    pub reconcile_scanning: bool,
+    pub reconcile_confirm_remove: bool,
+    // Per-fork reminders (by `owner/name`) and the reminders overlay cursor
+    pub reminders: HashMap<String, Reminder>,
+    pub reminders_selected: usize,
+    // Active profile (`--profile`), the cursor in the `S` overlay, and the
+    // profile picked there, which ends the session
+    pub profile: Option<String>,
+    pub profiles_selected: usize,
+    pub switch_profile: Option<ProfileSwitch>,
+    // Fork IDs left over from an interrupted batch, offered for resume
+    pub resume: Vec<String>,
+    // Auto-stashes left over from an interrupted run, and the overlay cursor
+    pub stashes: Vec<StashRecord>,
+    pub stashes_selected: usize,
+    // Audit log overlay: the latest external commands, newest first
+    pub audit: Vec<AuditEntry>,
+    pub audit_selected: usize,
+    // Backup ref the Undo modal would put the current fork's branch back to
+    pub undo: Option<BackupRecord>,
+    // Active text prompt (`Mode::Input`)
+    pub input: Option<InputPrompt>,
+    // Per-fork output log for this session (hook output), by `owner/name`
+    pub logs: HashMap<String, Vec<String>>,
+    // On-disk clone sizes in bytes, by `owner/name` (filled in the background)
+    pub disk_usage: HashMap<String, u64>,
+    // What syncing would bring in, by `owner/name` (fetched for the details pane)
+    pub upstream_commits: HashMap<String, UpstreamCommitsCheck>,
+    // Local git status of the clone under the cursor, by `owner/name`
+    pub git_status: Option<(String, GitStatusCheck)>,
+    // Incoming-change preview overlay
+    pub preview: Option<Preview>,
+    // Sync plan overlay, over the confirm modal
+    pub plan: Option<PlanView>,
+    pub confirm_list: ConfirmList,
+    pub local_work: LocalWork,
+    // Forks in the Archive/Delete modal, what each could lose (by
+    // `owner/name`), and whether the user acknowledged it
+    pub risk_targets: Vec<usize>,
+    pub risks: HashMap<String, RiskCheck>,
+    pub risks_acknowledged: bool,
+    // Archived/deleted forks to drop from the list once the batch is done
+    pub pending_removals: Vec<String>,
+    // When the batch and its in-flight forks started, and how long finished
+    // ones took (by `owner/name`; for the ETA and the Time column)
+    pub batch_started: Option<Instant>,
+    pub fork_started: HashMap<usize, Instant>,
+    pub fork_durations: HashMap<String, Duration>,
+    // The command each in-flight fork is running
+    pub commands: HashMap<usize, String>,
+    // Final status of each fork synced this session (by `owner/name`), and
+    // the commits pulled in all of them; batch statuses reset between rounds
+    pub outcomes: HashMap<String, SyncStatus>,
+    pub commits_pulled: u64,
+    // How far behind upstream each fork is (batched lookup), by `owner/name`
+    pub behind: HashMap<String, u32>,
+    // When each fork last synced (from the sync history), by `owner/name`
+    pub last_synced: HashMap<String, DateTime<Utc>>,
+    // Latest failures of each fork (from the sync history), newest first
+    pub failures: HashMap<String, Vec<FailureRecord>>,
+    // Fork list sort (column, descending) and where its header cells were
+    // drawn (for mouse clicks)
+    pub sort: Option<(SortColumn, bool)>,
+    pub list_header: Vec<(Rect, SortColumn)>,
+    // Fork rows that fit in the list (paging), and a pending `g` of `gg`
+    pub list_rows: usize,
+    pub pending_g: bool,
+    pub details: DetailsPane,
+}
+
+impl App {
+    pub fn new(
+        forks: Vec<Fork>,
+        dry_run: bool,
+        tool_homes: ToolHomes,
+        cache_status: CacheStatus,
+        store: Option<Box<dyn ForkStore>>,
+    ) -> Self {
+        let len = forks.len();
+        let ignored = store
+            .as_ref()
+            .and_then(|s| s.ignored_forks().ok())
+            .unwrap_or_default();
+        let reminders = store
+            .as_ref()
+            .and_then(|s| s.reminders().ok())
+            .unwrap_or_default();
+        let path_overrides = store
+            .as_ref()
+            .and_then(|s| s.path_overrides().ok())
+            .unwrap_or_default();
+        let last_synced = store
+            .as_ref()
+            .and_then(|s| s.last_synced().ok())
+            .unwrap_or_default();
+        let failures = store
+            .as_ref()
+            .and_then(|s| s.recent_failures(FAILURES_SHOWN).ok())
+            .unwrap_or_default();
+        let mut app = Self {
+            forks,
+            statuses: vec![SyncStatus::Pending; len],
+            state: TableState::default(),
+            selected: vec![false; len],
+            mode: Mode::Selecting,
+            dry_run,
+            config: Config::default(),
+            db_file: PathBuf::new(),
+            tool_homes,
+            clone_root: 0,
+            jobs: 1,
+            queue: None,
+            auth_paused: false,
+            stopping: false,
+            spinner_tick: 0,
+            last_tick: Instant::now(),
+            last_activity: Instant::now(),
+            refreshed_at: None,
+            modal_button: 1,
+            modal_action: ModalAction::Sync,
+            search_query: String::new(),
+            search_results: Vec::new(),
+            fuzzy_matcher: SkimMatcherV2::default(),
+            stats_cache: None,
+            changes: None,
+            status_message: None,
+            cache_status,
+            toasts: VecDeque::new(),
+            forks_truncated: false,
+            error_details: None,
+            error_scroll: 0,
+            previous_mode: None,
+            store,
+            ignored,
+            path_overrides,
+            show_hidden: false,
+            pending_refresh: None,
+            usage_enabled: true,
+            usage: UsageSession::new(),
+            usage_stats: None,
+            reconcile: Vec::new(),
+            reconcile_selected: 0,
+            reconcile_scanning: false,
+            reconcile_confirm_remove: false,
+            reminders,
+            reminders_selected: 0,
+            profile: None,
+            profiles_selected: 0,
+            switch_profile: None,
+            resume: Vec::new(),
+            stashes: Vec::new(),
+            stashes_selected: 0,
+            audit: Vec::new(),
+            audit_selected: 0,
+            undo: None,
+            input: None,
+            logs: HashMap::new(),
+            disk_usage: HashMap::new(),
+            upstream_commits: HashMap::new(),
+            git_status: None,
+            preview: None,
+            plan: None,
+            confirm_list: ConfirmList::default(),
+            local_work: LocalWork::default(),
+            risk_targets: Vec::new(),
+            risks: HashMap::new(),
+            risks_acknowledged: false,
+            pending_removals: Vec::new(),
+            batch_started: None,
+            fork_started: HashMap::new(),
+            fork_durations: HashMap::new(),
+            commands: HashMap::new(),
+            outcomes: HashMap::new(),
+            commits_pulled: 0,
+            behind: HashMap::new(),
+            last_synced,
+            failures,
+            sort: None,
+            list_header: Vec::new(),
+            list_rows: 0,
+            pending_g: false,
+            details: DetailsPane::default(),
+        };
+        app.update_search();
+        app
+    }
+
+    /// Options for a sync batch; local-only while GitHub is unreachable.
+    pub fn sync_options(&self) -> SyncOptions {
+        SyncOptions {
+            local_only: self.cache_status == CacheStatus::Offline,
+            ..self.config.sync_options(self.dry_run)
+        }
+    }
+
+    /// Visible GitHub forks that aren't cloned yet (for `B`, clone all).
+    pub fn uncloned_visible(&self) -> Vec<usize> {
+        self.visible_forks()
+            .iter()
+            .copied()
+            .filter(|&i| self.forks[i].is_github_fork() && !self.forks[i].is_cloned)
+            .collect()
+    }
+
+    pub fn visible_forks(&self) -> &[usize] {
+        &self.search_results
+    }
+
+    pub fn current_fork_index(&self) -> Option<usize> {
+        let visible_idx = self.state.selected()?;
+        self.search_results.get(visible_idx).copied()
+    }
+
+    pub fn current_fork(&self) -> Option<&Fork> {
+        self.current_fork_index().map(|i| &self.forks[i])
+    }
+
+    pub fn next(&mut self) {
+        let visible = self.visible_forks();
+        if visible.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % visible.len(),
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    pub fn previous(&mut self) {
+        let visible = self.visible_forks();
+        if visible.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => {
+                if i == 0 {
+                    visible.len() - 1
+                } else {
+                    i - 1
+                }
+            }
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    pub fn toggle_selection(&mut self) {
+        if let Some(idx) = self.current_fork_index() {
+            self.selected[idx] = !self.selected[idx];
+        }
+    }
+
+    pub fn selected_count(&self) -> usize {
+        self.selected.iter().filter(|&&s| s).count()
+    }
+
+    pub fn tick_spinner(&mut self) {
+        if self.last_tick.elapsed() >= Duration::from_millis(80) {
+            self.spinner_tick = (self.spinner_tick + 1) % SPINNER_FRAMES.len();
+            self.last_tick = Instant::now();
+        }
+        // Clear old status messages
+        if let Some((_, time)) = &self.status_message {
+            if time.elapsed() > Duration::from_secs(3) {
+                self.status_message = None;
+            }
+        }
+        // Clear expired toasts
+        self.clear_expired_toasts();
+    }
+
+    pub fn spinner(&self) -> &'static str {
+        SPINNER_FRAMES[self.spinner_tick]
+    }
+
+    pub fn mark_selected_as_pending(&mut self) {
+        self.reset_timings();
+        for (i, selected) in self.selected.iter().enumerate() {
+            if *selected {
+                self.statuses[i] = SyncStatus::Pending;
+            }
+        }
+    }
+
+    pub fn is_all_done(&self) -> bool {
+        self.statuses
+            .iter()
+            .enumerate()
+            .all(|(i, status)| !self.selected[i] || status.is_finished())
+    }
+
+    /// Whether the running batch is in slow mode.
+    pub fn is_slow_mode(&self) -> bool {
+        self.queue.as_ref().is_some_and(|q| q.is_slow())
+    }
+
+    /// Whether the running batch is held (`p`).
+    pub fn is_held(&self) -> bool {
+        self.queue.as_ref().is_some_and(|q| q.is_held())
+    }
+
+    pub fn reset_for_next_round(&mut self) {
+        self.queue = None;
+        self.announce_batch_time();
+        // Syncing may have touched the clones' working trees
+        self.local_work.scanned = false;
+        for i in 0..self.forks.len() {
+            match self.statuses[i] {
+                SyncStatus::Synced(_) => self.selected[i] = false,
+                // Keep the recovery instructions visible until the next batch
+                SyncStatus::NeedsAttention(_) => {
+                    self.selected[i] = false;
+                    continue;
+                }
+                _ => {}
+            }
+            self.statuses[i] = SyncStatus::Pending;
+        }
+        self.modal_button = 1;
+    }
+
+    /// Count finished forks of the batch: (synced, skipped, failed, needs attention).
+    pub fn summary(&self) -> (usize, usize, usize, usize) {
+        let mut synced = 0;
+        let mut skipped = 0;
+        let mut failed = 0;
+        let mut attention = 0;
+        for (i, status) in self.statuses.iter().enumerate() {
+            if !self.selected[i] {
+                continue;
+            }
+            match status {
+                SyncStatus::Synced(_) => synced += 1,
+                SyncStatus::Skipped(_) => skipped += 1,
+                SyncStatus::Failed(_) => failed += 1,
+                SyncStatus::NeedsAttention(_) => attention += 1,
+                _ => {}
+            }
+        }
+        (synced, skipped, failed, attention)
+    }
+
+    pub fn show_message(&mut self, msg: &str) {
+        self.status_message = Some((msg.to_string(), Instant::now()));
+    }
+
+    /// Get forks selected for syncing as (index, fork) pairs.
+    pub fn forks_to_sync(&self) -> Vec<(usize, Fork)> {
+        self.forks
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.selected[*i])
+            .map(|(i, f)| (i, f.clone()))
+            .collect()
+    }
+
+    /// Remove a fork from the list (e.g., after archiving).
+    pub fn remove_fork(&mut self, idx: usize) {
+        if idx < self.forks.len() {
+            self.forks.remove(idx);
+            self.statuses.remove(idx);
+            self.selected.remove(idx);
+            self.update_search();
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use super::App;
+
+impl App {
+    /// Move the cursor by `delta` rows, stopping at either end (unlike
+    /// `next`/`previous`, which wrap).
+    fn move_cursor(&mut self, delta: isize) {
+        let len = self.visible_forks().len();
+        if len == 0 {
+            return;
+        }
+        let current = self.state.selected().unwrap_or(0);
+        let target = current.saturating_add_signed(delta).min(len - 1);
+        self.state.select(Some(target));
+    }
+
+    /// Rows of forks that fit in the list (set while rendering).
+    fn page_rows(&self) -> isize {
+        isize::try_from(self.list_rows.max(1)).unwrap_or(1)
+    }
+
+    pub fn page_down(&mut self) {
+        self.move_cursor(self.page_rows());
+    }
+
+    pub fn page_up(&mut self) {
+        self.move_cursor(-self.page_rows());
+    }
+
+    pub fn half_page_down(&mut self) {
+        self.move_cursor((self.page_rows() / 2).max(1));
+    }
+
+    pub fn half_page_up(&mut self) {
+        self.move_cursor(-(self.page_rows() / 2).max(1));
+    }
+
+    pub fn jump_to_first(&mut self) {
+        if !self.visible_forks().is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn jump_to_last(&mut self) {
+        let len = self.visible_forks().len();
+        if len > 0 {
+            self.state.select(Some(len - 1));
+        }
+    }
+
+    /// First visible row to draw so the cursor stays centered, clamped so
+    /// the end of the list fills the last page.
+    pub fn list_window_start(&self, rows: usize) -> usize {
+        let len = self.visible_forks().len();
+        let selected = self.state.selected().unwrap_or(0);
+        selected
+            .saturating_sub(rows / 2)
+            .min(len.saturating_sub(rows))
+    }
+
+    /// Move the cursor to the first visible repo, from the cursor on (or
+    /// after it, for `next`), whose name starts with `prefix` (ignoring
+    /// case). Wraps around; stays put when nothing matches.
+    pub fn jump_to_prefix(&mut self, prefix: &str, next: bool) {
+        let visible = self.visible_forks();
+        if prefix.is_empty() || visible.is_empty() {
+            return;
+        }
+        let prefix = prefix.to_lowercase();
+        let current = self.state.selected().unwrap_or(0);
+        let skip = usize::from(next);
+        let found = (skip..visible.len() + skip)
+            .map(|offset| (current + offset) % visible.len())
+            .find(|&pos| {
+                self.forks[visible[pos]]
+                    .name
+                    .to_lowercase()
+                    .starts_with(&prefix)
+            });
+        if let Some(pos) = found {
+            self.state.select(Some(pos));
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use super::App;
+use crate::types::{Fork, Mode, PlanView, SyncPlan};
+
+impl App {
+    /// Open the plan overlay over the Sync/Resume confirm modal. Returns the
+    /// forks to work out the plans of.
+    pub fn open_plan(&mut self) -> Vec<Fork> {
+        self.plan = Some(PlanView::default());
+        self.mode = Mode::PlanOverlay;
+        self.confirm_targets()
+            .into_iter()
+            .map(|i| self.forks[i].clone())
+            .collect()
+    }
+
+    /// Record the worked-out plans, unless the overlay has been closed since.
+    pub fn set_plan(&mut self, plans: Vec<SyncPlan>) {
+        if let Some(view) = &mut self.plan {
+            view.plans = Some(plans);
+        }
+    }
+
+    pub fn scroll_plan(&mut self, down: bool) {
+        if let Some(view) = &mut self.plan {
+            view.scroll = if down {
+                view.scroll.saturating_add(1)
+            } else {
+                view.scroll.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Back to the confirm modal the plan was opened from.
+    pub fn close_plan(&mut self) {
+        self.plan = None;
+        self.mode = Mode::ConfirmModal;
+    }
+}
@@ -0,0 +1,58 @@
+use super::App;
+use crate::types::{Fork, IncomingChanges, ModalAction, Mode, Preview};
+
+impl App {
+    /// Open the incoming-change preview of the clone under the cursor.
+    /// Returns the fork to fetch into, if it has a clone.
+    pub fn open_preview(&mut self) -> Option<Fork> {
+        let fork = self.current_fork()?;
+        if !fork.is_cloned {
+            self.show_message("Preview needs a local clone (c: Clone)");
+            return None;
+        }
+        let fork = fork.clone();
+        self.preview = Some(Preview {
+            id: fork.full_name(),
+            result: None,
+            scroll: 0,
+        });
+        self.mode = Mode::PreviewOverlay;
+        Some(fork)
+    }
+
+    /// Record what a previewed fork would bring in, unless the preview has
+    /// been closed since.
+    pub fn set_preview(&mut self, id: &str, result: Result<IncomingChanges, String>) {
+        if let Some(preview) = self.preview.as_mut().filter(|p| p.id == id) {
+            preview.result = Some(result);
+        }
+    }
+
+    pub fn scroll_preview(&mut self, down: bool) {
+        if let Some(preview) = &mut self.preview {
+            preview.scroll = if down {
+                preview.scroll.saturating_add(1)
+            } else {
+                preview.scroll.saturating_sub(1)
+            };
+        }
+    }
+
+    pub fn close_preview(&mut self) {
+        self.preview = None;
+        self.mode = Mode::Selecting;
+    }
+
+    /// Go on to sync the previewed fork, through the usual confirm modal.
+    pub fn sync_previewed(&mut self) {
+        let Some(preview) = self.preview.take() else {
+            return;
+        };
+        self.mode = Mode::Selecting;
+        if let Some(idx) = self.forks.iter().position(|f| f.full_name() == preview.id) {
+            self.selected[idx] = true;
+            self.modal_action = ModalAction::Sync;
+            self.mode = Mode::ConfirmModal;
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use super::App;
+use crate::config::Profile;
+use crate::types::{Mode, ProfileSwitch, SyncStatus};
+
+impl App {
+    /// Apply the active profile's ignores and startup filter.
+    pub fn apply_profile(&mut self, name: Option<&str>, profile: &Profile) {
+        self.profile = name.map(str::to_string);
+        self.ignored.extend(profile.ignore.iter().cloned());
+        if let Some(filter) = &profile.filter {
+            self.search_query.clone_from(filter);
+        }
+        self.update_search();
+    }
+
+    /// Profiles in the `S` overlay: no profile first, then config order.
+    pub fn profile_choices(&self) -> Vec<Option<&str>> {
+        std::iter::once(None)
+            .chain(self.config.profiles.keys().map(|k| Some(k.as_str())))
+            .collect()
+    }
+
+    pub fn open_profiles(&mut self) {
+        if self.config.profiles.is_empty() {
+            self.show_message("No profiles in config.toml ([profiles.<name>])");
+            return;
+        }
+        let active = self.profile.as_deref();
+        self.profiles_selected = self
+            .profile_choices()
+            .iter()
+            .position(|&p| p == active)
+            .unwrap_or(0);
+        self.mode = Mode::ProfilesOverlay;
+    }
+
+    pub fn profiles_next(&mut self) {
+        let len = self.profile_choices().len();
+        self.profiles_selected = (self.profiles_selected + 1) % len;
+    }
+
+    pub fn profiles_previous(&mut self) {
+        let len = self.profile_choices().len();
+        self.profiles_selected = self.profiles_selected.checked_sub(1).unwrap_or(len - 1);
+    }
+
+    /// Switch to the selected profile, unless a fork operation is still running.
+    pub fn choose_profile(&mut self) {
+        let choice = self.profile_choices()[self.profiles_selected].map(str::to_string);
+        self.mode = Mode::Selecting;
+        if choice == self.profile {
+            return;
+        }
+        let busy = self
+            .statuses
+            .iter()
+            .any(|s| *s != SyncStatus::Pending && !s.is_finished());
+        if busy {
+            self.show_message("Wait for running operations to finish");
+            return;
+        }
+        self.switch_profile = Some(choice.map_or(ProfileSwitch::Default, ProfileSwitch::Named));
+    }
+}
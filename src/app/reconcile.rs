@@ -0,0 +1,156 @@
+use super::App;
+use crate::sync::unsaved_work;
+use crate::trash::delete_clone;
+use crate::types::{FindingKind, LocalFinding, Mode};
+use std::path::Path;
+
+impl App {
+    /// Open the reconciliation overlay; results arrive via `LocalScanned`.
+    pub fn open_reconcile(&mut self) {
+        self.reconcile.clear();
+        self.reconcile_selected = 0;
+        self.reconcile_scanning = true;
+        self.reconcile_confirm_remove = false;
+        self.mode = Mode::ReconcileOverlay;
+    }
+
+    pub fn set_reconcile_findings(&mut self, findings: Vec<LocalFinding>) {
+        self.reconcile = findings;
+        self.reconcile_selected = 0;
+        self.reconcile_scanning = false;
+    }
+
+    pub fn current_finding(&self) -> Option<&LocalFinding> {
+        self.reconcile.get(self.reconcile_selected)
+    }
+
+    pub fn reconcile_next(&mut self) {
+        if !self.reconcile.is_empty() {
+            self.reconcile_selected = (self.reconcile_selected + 1) % self.reconcile.len();
+        }
+        self.reconcile_confirm_remove = false;
+    }
+
+    pub fn reconcile_previous(&mut self) {
+        if !self.reconcile.is_empty() {
+            self.reconcile_selected = self
+                .reconcile_selected
+                .checked_sub(1)
+                .unwrap_or(self.reconcile.len() - 1);
+        }
+        self.reconcile_confirm_remove = false;
+    }
+
+    /// Move an orphan into the slot of the uncloned fork its origin points at.
+    pub fn adopt_finding(&mut self) {
+        let Some(finding) = self.current_finding() else {
+            return;
+        };
+        let FindingKind::Orphan {
+            adopt_as: Some(fork),
+        } = &finding.kind
+        else {
+            self.show_message("No uncloned fork matches this repo's origin");
+            return;
+        };
+        let fork = fork.clone();
+        if self.move_finding() {
+            self.show_message(&format!("Adopted as {fork}"));
+        }
+    }
+
+    /// Move the repo to where its origin URL says it belongs.
+    pub fn relocate_finding(&mut self) {
+        if self.move_finding() {
+            self.show_message("Relocated");
+        }
+    }
+
+    /// Delete the repo from disk (into the trash with `trash_deleted_clones`).
+    /// Requires pressing the key twice.
+    pub fn remove_finding(&mut self) {
+        let Some(path) = self.current_finding().map(|f| f.path.clone()) else {
+            return;
+        };
+        let trash = self.config.trash_deleted_clones;
+        if !self.reconcile_confirm_remove {
+            self.reconcile_confirm_remove = true;
+            let verb = if trash { "trash" } else { "delete" };
+            let work = unsaved_work(&path);
+            let warning = if work.is_empty() {
+                String::new()
+            } else {
+                format!(" (it has {}!)", work.join(" and "))
+            };
+            self.show_message(&format!(
+                "Press X again to {verb} {}{warning}",
+                path.display()
+            ));
+            return;
+        }
+
+        self.reconcile_confirm_remove = false;
+        // Clones live at `<tool home>/<owner>/<name>`
+        let dir_name = |p: Option<&Path>| {
+            p.and_then(Path::file_name)
+                .map_or_else(String::new, |n| n.to_string_lossy().to_string())
+        };
+        let owner = dir_name(path.parent());
+        let name = dir_name(Some(&path));
+        let homes = trash.then_some(&self.tool_homes);
+        match delete_clone(homes, &owner, &name, &path) {
+            Ok(()) => {
+                self.finish_finding();
+                let done = if trash { "Trashed" } else { "Removed" };
+                self.show_message(&format!("{done} {}", path.display()));
+            }
+            Err(e) => self.show_message(&format!("Remove failed: {e:#}")),
+        }
+    }
+
+    /// Rename the current finding to its suggested path. Returns true on success.
+    fn move_finding(&mut self) -> bool {
+        let Some(finding) = self.current_finding() else {
+            return false;
+        };
+        let Some(target) = finding.suggested_path.clone() else {
+            self.show_message("No free location matches this repo's origin");
+            return false;
+        };
+        let source = finding.path.clone();
+
+        match move_dir(&source, &target) {
+            Ok(()) => {
+                self.finish_finding();
+                true
+            }
+            Err(e) => {
+                self.show_message(&format!("Move failed: {e}"));
+                false
+            }
+        }
+    }
+
+    /// Drop the current finding and re-detect which forks are cloned.
+    fn finish_finding(&mut self) {
+        self.reconcile.remove(self.reconcile_selected);
+        if self.reconcile_selected >= self.reconcile.len() {
+            self.reconcile_selected = self.reconcile.len().saturating_sub(1);
+        }
+        self.refresh_clone_state();
+    }
+
+    /// Re-check `is_cloned` for every fork against the filesystem.
+    pub fn refresh_clone_state(&mut self) {
+        for fork in &mut self.forks {
+            fork.is_cloned = fork.local_path.exists();
+        }
+    }
+}
+
+fn move_dir(source: &Path, target: &Path) -> std::io::Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(source, target)
+}
@@ -0,0 +1,138 @@
+use super::App;
+use crate::types::{InputPurpose, Mode, Reminder, Toast};
+use chrono::{Local, NaiveDate};
+
+impl App {
+    fn today() -> NaiveDate {
+        Local::now().date_naive()
+    }
+
+    pub fn reminder_for(&self, idx: usize) -> Option<&Reminder> {
+        self.forks
+            .get(idx)
+            .and_then(|f| self.reminders.get(&f.full_name()))
+    }
+
+    pub fn is_reminder_due(&self, idx: usize) -> bool {
+        self.reminder_for(idx)
+            .is_some_and(|r| r.is_due(Self::today()))
+    }
+
+    /// All reminders sorted by due date (soonest first).
+    pub fn sorted_reminders(&self) -> Vec<(&String, &Reminder)> {
+        let mut reminders: Vec<_> = self.reminders.iter().collect();
+        reminders.sort_by(|a, b| a.1.due.cmp(&b.1.due).then_with(|| a.0.cmp(b.0)));
+        reminders
+    }
+
+    /// Toast about reminders that have come due. Called once at startup.
+    pub fn announce_due_reminders(&mut self) {
+        let today = Self::today();
+        let due = self.reminders.values().filter(|r| r.is_due(today)).count();
+        if due > 0 {
+            let noun = if due == 1 { "reminder" } else { "reminders" };
+            self.add_toast(Toast::warning(format!("{due} fork {noun} due (press A)")));
+        }
+    }
+
+    /// Prompt for the current fork's reminder, pre-filled with the existing one.
+    pub fn prompt_reminder(&mut self) {
+        let Some(fork) = self.current_fork() else {
+            return;
+        };
+        let id = fork.full_name();
+        let initial = self
+            .reminders
+            .get(&id)
+            .map(|r| format!("{} {}", r.due, r.note).trim_end().to_string())
+            .unwrap_or_default();
+        self.open_prompt(
+            InputPurpose::Reminder(id),
+            "Remind (YYYY-MM-DD or 2w/3m, then note; empty clears)",
+            initial,
+        );
+    }
+
+    pub(super) fn apply_reminder_input(&mut self, id: &str, input: &str) {
+        if input.trim().is_empty() {
+            self.set_reminder(id, None);
+            return;
+        }
+        match Reminder::parse(input, Self::today()) {
+            Some(reminder) => self.set_reminder(id, Some(reminder)),
+            None => self.show_message("Couldn't parse date (use YYYY-MM-DD, 10d, 3w or 2m)"),
+        }
+    }
+
+    fn set_reminder(&mut self, id: &str, reminder: Option<Reminder>) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.set_reminder(id, reminder.as_ref()) {
+                self.show_message(&format!("Failed to save reminder: {e}"));
+                return;
+            }
+        }
+        if let Some(reminder) = reminder {
+            self.show_message(&format!("Reminder for {id} set to {}", reminder.due));
+            self.reminders.insert(id.to_string(), reminder);
+        } else if self.reminders.remove(id).is_some() {
+            self.show_message(&format!("Reminder for {id} cleared"));
+        }
+    }
+
+    pub fn open_reminders(&mut self) {
+        self.reminders_selected = 0;
+        self.mode = Mode::RemindersOverlay;
+    }
+
+    pub fn reminders_next(&mut self) {
+        if !self.reminders.is_empty() {
+            self.reminders_selected = (self.reminders_selected + 1) % self.reminders.len();
+        }
+    }
+
+    pub fn reminders_previous(&mut self) {
+        if !self.reminders.is_empty() {
+            self.reminders_selected = self
+                .reminders_selected
+                .checked_sub(1)
+                .unwrap_or(self.reminders.len() - 1);
+        }
+    }
+
+    fn selected_reminder_id(&self) -> Option<String> {
+        self.sorted_reminders()
+            .get(self.reminders_selected)
+            .map(|(id, _)| (*id).clone())
+    }
+
+    /// Close the overlay and move the list cursor to the selected reminder's fork.
+    pub fn jump_to_reminder(&mut self) {
+        let Some(id) = self.selected_reminder_id() else {
+            return;
+        };
+        let Some(idx) = self.forks.iter().position(|f| f.full_name() == id) else {
+            self.show_message(&format!("{id} is no longer in the fork list"));
+            return;
+        };
+        if !self.search_results.contains(&idx) {
+            self.search_query.clear();
+            self.update_search();
+        }
+        match self.search_results.iter().position(|&i| i == idx) {
+            Some(pos) => {
+                self.state.select(Some(pos));
+                self.mode = Mode::Selecting;
+            }
+            None => self.show_message(&format!("{id} is hidden (press H to show ignored)")),
+        }
+    }
+
+    pub fn clear_selected_reminder(&mut self) {
+        if let Some(id) = self.selected_reminder_id() {
+            self.set_reminder(&id, None);
+            if self.reminders_selected >= self.reminders.len() {
+                self.reminders_selected = self.reminders.len().saturating_sub(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use super::App;
+use crate::types::{
+    FailureRecord, Fork, ModalAction, Mode, SyncResult, SyncStatus, Toast, FAILURES_SHOWN,
+};
+use chrono::Utc;
+use std::sync::mpsc;
+
+impl App {
+    /// Persist a starting batch so it can be resumed if the app is closed or
+    /// crashes mid-run. Dry runs aren't persisted.
+    pub fn persist_batch(&mut self, forks: &[(usize, Fork)]) {
+        if self.dry_run {
+            return;
+        }
+        let ids: Vec<String> = forks.iter().map(|(_, f)| f.full_name()).collect();
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_sync_queue(&ids) {
+                self.show_message(&format!("Failed to save sync queue: {e}"));
+            }
+        }
+    }
+
+    /// Apply a status update; forks that finished leave the persisted queue.
+    pub fn set_status(&mut self, idx: usize, status: SyncStatus) {
+        if idx >= self.statuses.len() {
+            return;
+        }
+        tracing::info!(fork = %self.forks[idx].full_name(), ?status, "status");
+        self.record_timing(idx, &status);
+        self.record_outcome(idx, &status);
+        if status.is_finished() && !self.dry_run {
+            let sync_batch = self.queue.is_some()
+                && matches!(self.modal_action, ModalAction::Sync | ModalAction::Resume);
+            if let Some(store) = &self.store {
+                let id = self.forks[idx].full_name();
+                let _ = store.remove_from_sync_queue(&id);
+                let _ = store.remove_stash(&id);
+                if sync_batch {
+                    if let Err(e) = store.record_sync(&id, &status) {
+                        tracing::warn!(fork = %id, "Failed to record sync history: {e}");
+                    }
+                }
+            }
+            if sync_batch && matches!(status, SyncStatus::Synced(_)) {
+                self.last_synced
+                    .insert(self.forks[idx].full_name(), Utc::now());
+            }
+            if let Some(message) = status.failure().filter(|_| sync_batch) {
+                let failures = self
+                    .failures
+                    .entry(self.forks[idx].full_name())
+                    .or_default();
+                failures.insert(
+                    0,
+                    FailureRecord {
+                        at: Utc::now(),
+                        message: message.to_string(),
+                    },
+                );
+                failures.truncate(FAILURES_SHOWN);
+            }
+            // Whatever was upstream is (likely) in the fork now
+            if matches!(status, SyncStatus::Synced(_)) {
+                self.upstream_commits.remove(&self.forks[idx].full_name());
+            }
+            // and its clone may have moved, so read it again
+            if self
+                .git_status
+                .as_ref()
+                .is_some_and(|(id, _)| *id == self.forks[idx].full_name())
+            {
+                self.git_status = None;
+            }
+        }
+        self.statuses[idx] = status;
+    }
+
+    /// Drop the fork under the cursor from the running batch if it hasn't
+    /// started yet (`x` while syncing).
+    pub fn skip_queued(&mut self) {
+        let Some(idx) = self.current_fork_index() else {
+            return;
+        };
+        if self.queue.as_ref().is_some_and(|q| q.remove(idx)) {
+            self.set_status(idx, SyncStatus::Skipped("user".to_string()));
+        } else if self.selected[idx] {
+            self.show_message("Only forks that haven't started can be skipped");
+        }
+    }
+
+    /// Add the fork under the cursor to the running sync batch (`Space`
+    /// while syncing), and to the persisted queue so it's resumed too.
+    pub fn enqueue_current(&mut self, tx: &mpsc::Sender<SyncResult>) {
+        let Some(idx) = self.current_fork_index() else {
+            return;
+        };
+        if self.stopping || !matches!(self.modal_action, ModalAction::Sync | ModalAction::Resume) {
+            self.show_message("Forks can only be added to a running sync");
+            return;
+        }
+        if self.selected[idx] {
+            self.show_message("Already in this batch");
+            return;
+        }
+        let Some(queue) = &self.queue else {
+            return;
+        };
+        let fork = self.forks[idx].clone();
+        queue.push((idx, fork.clone()), &self.sync_options(), tx);
+        self.selected[idx] = true;
+        self.statuses[idx] = SyncStatus::Pending;
+        if let Some(store) = self.store.as_ref().filter(|_| !self.dry_run) {
+            let mut ids = store.sync_queue().unwrap_or_default();
+            ids.push(fork.full_name());
+            let _ = store.save_sync_queue(&ids);
+        }
+        self.show_message(&format!("Added {} to the batch", fork.full_name()));
+    }
+
+    /// Stop the running batch after Ctrl-C: forks in flight finish (putting
+    /// back their branch and stash), and the rest are skipped but stay in the
+    /// persisted queue, so the next launch offers to resume them.
+    pub fn stop_batch(&mut self) {
+        self.stopping = true;
+        if let Some(queue) = &self.queue {
+            for (idx, _) in queue.drain() {
+                self.statuses[idx] = SyncStatus::Skipped("interrupted".to_string());
+            }
+        }
+        self.add_toast(Toast::warning(
+            "Stopping: finishing forks in flight (Ctrl-C again to quit now)",
+        ));
+    }
+
+    /// Offer to resume forks left over from an interrupted batch.
+    pub fn offer_resume(&mut self) {
+        let Some(queued) = self.store.as_ref().and_then(|s| s.sync_queue().ok()) else {
+            return;
+        };
+        self.resume = queued
+            .into_iter()
+            .filter(|id| self.forks.iter().any(|f| &f.full_name() == id))
+            .collect();
+        if !self.resume.is_empty() {
+            self.modal_action = ModalAction::Resume;
+            self.mode = Mode::ConfirmModal;
+        }
+    }
+
+    /// Select exactly the forks of the interrupted batch.
+    pub fn select_resume(&mut self) {
+        for (i, fork) in self.forks.iter().enumerate() {
+            self.selected[i] = self.resume.contains(&fork.full_name());
+        }
+        self.resume.clear();
+    }
+
+    /// Forget the interrupted batch.
+    pub fn discard_resume(&mut self) {
+        self.resume.clear();
+        if let Some(store) = &self.store {
+            let _ = store.save_sync_queue(&[]);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::App;
+use crate::types::{Fork, ForkRisks, ModalAction, Mode, RiskCheck};
+
+impl App {
+    /// Forks an Archive/Delete would act on: the selected GitHub forks, or
+    /// the one under the cursor if nothing is selected.
+    pub fn risky_action_targets(&self) -> Vec<usize> {
+        if self.selected_count() == 0 {
+            return self
+                .current_fork_index()
+                .filter(|&i| self.forks[i].is_github_fork())
+                .into_iter()
+                .collect();
+        }
+        (0..self.forks.len())
+            .filter(|&i| self.selected[i] && self.forks[i].is_github_fork())
+            .collect()
+    }
+
+    /// Open the Archive/Delete modal for `targets`; what each fork could lose
+    /// (open PRs, branches ahead of upstream) is checked in the background.
+    pub fn begin_risky_action(&mut self, action: ModalAction, targets: Vec<usize>) {
+        self.modal_action = action;
+        self.mode = Mode::ConfirmModal;
+        self.risks = targets
+            .iter()
+            .map(|&i| (self.forks[i].full_name(), RiskCheck::Checking))
+            .collect();
+        self.risk_targets = targets;
+        self.risks_acknowledged = false;
+    }
+
+    /// Record a risk check, unless the modal has been closed since.
+    pub fn set_risks(&mut self, id: &str, result: Result<ForkRisks, String>) {
+        if let Some(check) = self.risks.get_mut(id) {
+            *check = match result {
+                Ok(risks) => RiskCheck::Found(risks),
+                Err(e) => RiskCheck::Failed(e),
+            };
+        }
+    }
+
+    /// Whether PROCEED may go ahead now. Anything at risk (or a failed
+    /// check) needs a second PROCEED to acknowledge it.
+    pub fn confirm_risky_action(&mut self) -> bool {
+        if self.risks.values().any(|c| *c == RiskCheck::Checking) {
+            self.show_message("Still checking for open PRs and unmerged branches...");
+            return false;
+        }
+        let needs_ack = self.risks.values().any(|check| match check {
+            RiskCheck::Found(risks) => !risks.is_empty(),
+            RiskCheck::Checking | RiskCheck::Failed(_) => true,
+        });
+        if needs_ack && !self.risks_acknowledged {
+            self.risks_acknowledged = true;
+            return false;
+        }
+        self.risks.clear();
+        self.risks_acknowledged = false;
+        true
+    }
+
+    pub fn clear_risks(&mut self) {
+        self.risks.clear();
+        self.risk_targets.clear();
+        self.risks_acknowledged = false;
+    }
+
+    /// The forks the confirmed Archive/Delete acts on, with their indices.
+    pub fn take_risk_targets(&mut self) -> Vec<(usize, Fork)> {
+        std::mem::take(&mut self.risk_targets)
+            .into_iter()
+            .filter_map(|i| self.forks.get(i).map(|f| (i, f.clone())))
+            .collect()
+    }
+
+    /// Drop an archived/deleted fork (by `owner/name`) from the list. While a
+    /// batch is running this waits until it's done, so the indices its queue
+    /// holds stay valid.
+    pub fn remove_fork_when_idle(&mut self, id: String) {
+        if self.mode == Mode::Syncing {
+            self.pending_removals.push(id);
+        } else if let Some(idx) = self.fork_index(&id) {
+            self.remove_fork(idx);
+        }
+    }
+
+    pub fn apply_pending_removals(&mut self) {
+        for id in std::mem::take(&mut self.pending_removals) {
+            if let Some(idx) = self.fork_index(&id) {
+                self.remove_fork(idx);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use super::App;
+use crate::filter::ForkFilter;
+use crate::types::Fork;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::collections::HashSet;
+
+/// How much a match in each field counts: the repo name first, then
+/// language and owner, then the free-form description.
+const NAME_WEIGHT: i64 = 3;
+const LANGUAGE_WEIGHT: i64 = 2;
+const OWNER_WEIGHT: i64 = 2;
+const DESCRIPTION_WEIGHT: i64 = 1;
+
+/// Score `fork` against the search terms. Every term has to match one of the
+/// fields; each counts with its best weighted field score.
+fn search_score(matcher: &SkimMatcherV2, fork: &Fork, terms: &[&str]) -> Option<i64> {
+    let name = format!("{}/{}", fork.parent_owner, fork.name);
+    let fields = [
+        (Some(name.as_str()), NAME_WEIGHT),
+        (fork.primary_language.as_deref(), LANGUAGE_WEIGHT),
+        (Some(fork.owner.as_str()), OWNER_WEIGHT),
+        (fork.description.as_deref(), DESCRIPTION_WEIGHT),
+    ];
+    terms.iter().try_fold(0, |total, term| {
+        fields
+            .iter()
+            .filter_map(|(field, weight)| {
+                matcher
+                    .fuzzy_match((*field)?, term)
+                    .map(|score| score * weight)
+            })
+            .max()
+            .map(|best| total + best)
+    })
+}
+
+impl App {
+    /// Character positions in the fork's `parent_owner/name` that match the
+    /// active search, for highlighting in the list. Empty without a search.
+    pub fn name_match_indices(&self, idx: usize) -> HashSet<usize> {
+        let Some(fork) = self.forks.get(idx) else {
+            return HashSet::new();
+        };
+        let name = format!("{}/{}", fork.parent_owner, fork.name);
+        let (_, terms) = ForkFilter::parse_query(&self.search_query);
+        terms
+            .into_iter()
+            .filter_map(|term| self.fuzzy_matcher.fuzzy_indices(&name, term))
+            .flat_map(|(_, indices)| indices)
+            .collect()
+    }
+
+    /// Narrow the list to the search: its quick-filter tokens (`lang:rust`,
+    /// `is:cloned`, ...) first, then fuzzy matching on the remaining terms.
+    pub fn update_search(&mut self) {
+        let (filter, terms) = ForkFilter::parse_query(&self.search_query);
+        let visible = |(i, fork): &(usize, &Fork)| {
+            (self.show_hidden || !self.is_ignored(*i)) && filter.matches(fork)
+        };
+        if terms.is_empty() {
+            self.search_results = self
+                .forks
+                .iter()
+                .enumerate()
+                .filter(visible)
+                .map(|(i, _)| i)
+                .collect();
+        } else {
+            let mut results: Vec<(usize, i64)> = self
+                .forks
+                .iter()
+                .enumerate()
+                .filter(visible)
+                .filter_map(|(i, fork)| {
+                    search_score(&self.fuzzy_matcher, fork, &terms).map(|score| (i, score))
+                })
+                .collect();
+            results.sort_by_key(|r| std::cmp::Reverse(r.1));
+            self.search_results = results.into_iter().map(|(i, _)| i).collect();
+        }
+        let mut results = std::mem::take(&mut self.search_results);
+        self.apply_sort(&mut results);
+        self.search_results = results;
+        // Reset selection to first result
+        if self.search_results.is_empty() {
+            self.state.select(None);
+        } else {
+            self.state.select(Some(0));
+        }
+    }
+}
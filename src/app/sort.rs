@@ -0,0 +1,95 @@
+use super::App;
+use crate::types::{SortColumn, SyncStatus};
+use ratatui::layout::Rect;
+use std::cmp::Ordering;
+
+/// Statuses that need a look sort first.
+fn status_rank(status: &SyncStatus) -> u8 {
+    match status {
+        SyncStatus::Failed(_) => 0,
+        SyncStatus::NeedsAttention(_) => 1,
+        SyncStatus::Skipped(_) => 2,
+        SyncStatus::Synced(_) => 4,
+        SyncStatus::Pending => 5,
+        _ => 3,
+    }
+}
+
+impl App {
+    /// Sort by `column`: ascending first, descending on the second press,
+    /// back to the default order on the third.
+    pub fn cycle_sort(&mut self, column: SortColumn) {
+        self.sort = match self.sort {
+            Some((current, false)) if current == column => Some((column, true)),
+            Some((current, true)) if current == column => None,
+            _ => Some((column, false)),
+        };
+        let current = self.current_fork_index();
+        self.update_search();
+        if let Some(pos) = current.and_then(|c| self.search_results.iter().position(|&i| i == c)) {
+            self.state.select(Some(pos));
+        }
+    }
+
+    /// Sort by the header cell at (`x`, `y`), if any (mouse click).
+    pub fn click_header(&mut self, x: u16, y: u16) {
+        let clicked = self
+            .list_header
+            .iter()
+            .find(|(rect, _)| rect.contains((x, y).into()))
+            .map(|&(_, column)| column);
+        if let Some(column) = clicked {
+            self.cycle_sort(column);
+        }
+    }
+
+    /// Where each sortable header cell was last drawn.
+    pub fn set_list_header(&mut self, cells: Vec<(Rect, SortColumn)>) {
+        self.list_header = cells;
+    }
+
+    /// Order `results` by the active sort column. The sort is stable, so
+    /// search ranking breaks ties.
+    pub(super) fn apply_sort(&self, results: &mut [usize]) {
+        let Some((column, descending)) = self.sort else {
+            return;
+        };
+        results.sort_by(|&a, &b| {
+            let order = self.compare_by(column, a, b);
+            if descending {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+    }
+
+    fn compare_by(&self, column: SortColumn, a: usize, b: usize) -> Ordering {
+        let (fa, fb) = (&self.forks[a], &self.forks[b]);
+        match column {
+            SortColumn::Repository => {
+                (&fa.parent_owner, &fa.name).cmp(&(&fb.parent_owner, &fb.name))
+            }
+            SortColumn::Status => {
+                status_rank(&self.statuses[a]).cmp(&status_rank(&self.statuses[b]))
+            }
+            SortColumn::Language => fa.primary_language.cmp(&fb.primary_language),
+            SortColumn::Behind => self.behind_by(a).cmp(&self.behind_by(b)),
+            SortColumn::LastSync => self
+                .last_synced
+                .get(&fa.full_name())
+                .cmp(&self.last_synced.get(&fb.full_name())),
+            SortColumn::Path => fa.local_path.cmp(&fb.local_path),
+        }
+    }
+
+    /// How far behind the fork was at its last sync this session, or else
+    /// how far behind it is according to the batched lookup.
+    pub fn behind_by(&self, idx: usize) -> Option<u32> {
+        let id = self.forks[idx].full_name();
+        match self.outcomes.get(&id) {
+            Some(SyncStatus::Synced(Some(n))) => Some(*n),
+            _ => self.behind.get(&id).copied(),
+        }
+    }
+}
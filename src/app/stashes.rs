@@ -0,0 +1,96 @@
+use super::App;
+use crate::sync::{find_stash, restore_stash};
+use crate::types::{ModalAction, Mode, StashRecord, Toast};
+
+impl App {
+    /// Keep an auto-stash in the cache until its fork's sync finishes.
+    pub fn record_stash(&mut self, stash: &StashRecord) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.record_stash(stash) {
+                tracing::warn!(fork = %stash.id, "Failed to record auto-stash: {e}");
+            }
+        }
+    }
+
+    /// Offer to restore auto-stashes left behind by an interrupted run.
+    /// Records whose stash is gone (restored or dropped by hand) are
+    /// forgotten. Called once at startup, after `offer_resume`.
+    pub fn offer_stash_recovery(&mut self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let (leftover, gone): (Vec<_>, Vec<_>) = store
+            .stashes()
+            .unwrap_or_default()
+            .into_iter()
+            .partition(|stash| find_stash(stash).is_some());
+        for stash in gone {
+            let _ = store.remove_stash(&stash.id);
+        }
+        self.stashes = leftover;
+        if !self.stashes.is_empty() {
+            self.stashes_selected = 0;
+            self.mode = Mode::StashesOverlay;
+        }
+    }
+
+    /// Check out the selected stash's branch and pop it.
+    pub fn restore_selected_stash(&mut self) {
+        let Some(stash) = self.stashes.get(self.stashes_selected).cloned() else {
+            return;
+        };
+        match restore_stash(&stash) {
+            Ok(()) => {
+                self.add_toast(Toast::success(format!(
+                    "Restored changes in {} on {}",
+                    stash.id, stash.branch
+                )));
+                self.forget_selected_stash();
+            }
+            Err(e) => self.show_message(&format!("{}: {e}", stash.id)),
+        }
+    }
+
+    /// Stop tracking the selected stash; it stays in the clone's stash list.
+    pub fn forget_selected_stash(&mut self) {
+        if self.stashes_selected >= self.stashes.len() {
+            return;
+        }
+        let stash = self.stashes.remove(self.stashes_selected);
+        if let Some(store) = &self.store {
+            let _ = store.remove_stash(&stash.id);
+        }
+        self.stashes_selected = self
+            .stashes_selected
+            .min(self.stashes.len().saturating_sub(1));
+        if self.stashes.is_empty() {
+            self.close_stashes();
+        }
+    }
+
+    /// Close the overlay (stashes still listed are offered again next
+    /// launch), going on to the resume prompt if there is one.
+    pub fn close_stashes(&mut self) {
+        self.mode = if self.resume.is_empty() {
+            Mode::Selecting
+        } else {
+            self.modal_action = ModalAction::Resume;
+            Mode::ConfirmModal
+        };
+    }
+
+    pub fn stashes_next(&mut self) {
+        if !self.stashes.is_empty() {
+            self.stashes_selected = (self.stashes_selected + 1) % self.stashes.len();
+        }
+    }
+
+    pub fn stashes_previous(&mut self) {
+        if !self.stashes.is_empty() {
+            self.stashes_selected = self
+                .stashes_selected
+                .checked_sub(1)
+                .unwrap_or(self.stashes.len() - 1);
+        }
+    }
+}
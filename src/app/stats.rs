@@ -0,0 +1,156 @@
+use super::App;
+use crate::types::{ForkStats, SyncStatus};
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use std::collections::HashMap;
+
+/// Buckets of the behind-by histogram: label and the largest count in it.
+const BEHIND_BUCKETS: [(&str, u32); 5] = [
+    ("0", 0),
+    ("1-9", 9),
+    ("10-99", 99),
+    ("100-999", 999),
+    ("1000+", u32::MAX),
+];
+
+/// Weeks of sync history in the stats dashboard trend.
+const TREND_WEEKS: u32 = 12;
+
+/// Months of fork creation activity in the stats dashboard.
+const CREATED_MONTHS: u32 = 12;
+
+impl App {
+    /// Remember how a fork's sync ended, for the stats dashboard.
+    pub(super) fn record_outcome(&mut self, idx: usize, status: &SyncStatus) {
+        if !status.is_finished() {
+            return;
+        }
+        if let SyncStatus::Synced(Some(n)) = status {
+            self.commits_pulled += u64::from(*n);
+        }
+        self.outcomes
+            .insert(self.forks[idx].full_name(), status.clone());
+    }
+
+    /// (bucket, forks) of how far behind upstream the forks synced this
+    /// session were.
+    fn behind_by_histogram(&self) -> Vec<(&'static str, u64)> {
+        let mut counts = [0; BEHIND_BUCKETS.len()];
+        for status in self.outcomes.values() {
+            if let SyncStatus::Synced(Some(n)) = status {
+                if let Some(bucket) = BEHIND_BUCKETS.iter().position(|(_, max)| n <= max) {
+                    counts[bucket] += 1;
+                }
+            }
+        }
+        BEHIND_BUCKETS
+            .iter()
+            .zip(counts)
+            .map(|((label, _), count)| (*label, count))
+            .collect()
+    }
+
+    /// (month, forks created) for the last `CREATED_MONTHS` months up to
+    /// `today`, oldest first.
+    fn created_by_month(&self, today: NaiveDate) -> Vec<(String, u64)> {
+        let current = today.with_day(1).unwrap_or(today);
+        let months: Vec<NaiveDate> = (0..CREATED_MONTHS)
+            .rev()
+            .filter_map(|ago| current.checked_sub_months(Months::new(ago)))
+            .collect();
+        let mut counts = vec![0; months.len()];
+        for created in self.forks.iter().filter_map(|f| f.created_at) {
+            let created = created.date_naive();
+            if let Some(i) = months
+                .iter()
+                .position(|m| m.year() == created.year() && m.month() == created.month())
+            {
+                counts[i] += 1;
+            }
+        }
+        months
+            .iter()
+            .zip(counts)
+            .map(|(month, count)| (month.format("%b").to_string(), count))
+            .collect()
+    }
+
+    /// Skip reasons of this session with how many forks each, most common
+    /// first.
+    fn skipped_reasons(&self) -> Vec<(String, u64)> {
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+        for status in self.outcomes.values() {
+            if let SyncStatus::Skipped(reason) = status {
+                *counts.entry(reason.as_str()).or_insert(0) += 1;
+            }
+        }
+        let mut reasons: Vec<(String, u64)> = counts
+            .into_iter()
+            .map(|(reason, n)| (reason.to_string(), n))
+            .collect();
+        reasons.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        reasons
+    }
+
+    pub fn compute_stats(&mut self) {
+        let mut lang_counts: HashMap<String, u64> = HashMap::new();
+        let mut cloned = 0;
+        let mut uncloned = 0;
+        let mut synced = 0;
+        let mut pending = 0;
+        let mut failed = 0;
+
+        for (i, fork) in self.forks.iter().enumerate() {
+            if fork.is_cloned {
+                cloned += 1;
+            } else {
+                uncloned += 1;
+            }
+
+            let lang = fork
+                .primary_language
+                .clone()
+                .unwrap_or_else(|| "Unknown".to_string());
+            *lang_counts.entry(lang).or_insert(0) += 1;
+
+            match &self.statuses[i] {
+                SyncStatus::Synced(_) => synced += 1,
+                SyncStatus::Failed(_) | SyncStatus::Skipped(_) | SyncStatus::NeedsAttention(_) => {
+                    failed += 1;
+                }
+                _ => pending += 1,
+            }
+        }
+
+        let mut by_language: Vec<(String, u64)> = lang_counts.into_iter().collect();
+        by_language.sort_by_key(|l| std::cmp::Reverse(l.1));
+        by_language.truncate(8); // Top 8 languages
+
+        self.stats_cache = Some(ForkStats {
+            by_language,
+            total: self.forks.len(),
+            cloned,
+            uncloned,
+            synced,
+            pending,
+            failed,
+            disk_usage: self.disk_usage.values().sum(),
+            behind_by: self.behind_by_histogram(),
+            commits_pulled: self.commits_pulled,
+            skipped_reasons: self.skipped_reasons(),
+            created_by_month: self.created_by_month(Utc::now().date_naive()),
+            trend: self
+                .store
+                .as_ref()
+                .and_then(|s| s.sync_trend(TREND_WEEKS).ok())
+                .unwrap_or_default(),
+        });
+    }
+
+    /// Load what changed between the last two refreshes from the cache.
+    pub fn compute_changes(&mut self) {
+        self.changes = self
+            .store
+            .as_ref()
+            .and_then(|s| s.snapshot_diff().ok().flatten());
+    }
+}
@@ -0,0 +1,42 @@
+use super::App;
+use crate::types::{ErrorDetails, Mode, Toast};
+use std::time::Duration;
+
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+pub const MAX_TOASTS: usize = 3;
+
+impl App {
+    /// Add a toast notification.
+    pub fn add_toast(&mut self, toast: Toast) {
+        self.toasts.push_back(toast);
+        // Keep only the most recent toasts
+        while self.toasts.len() > MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// Clear expired toasts.
+    pub fn clear_expired_toasts(&mut self) {
+        self.toasts
+            .retain(|toast| toast.created_at.elapsed() < TOAST_DURATION);
+    }
+
+    /// Show an error popup with optional action.
+    pub fn show_error_popup(&mut self, details: ErrorDetails) {
+        self.previous_mode = Some(self.mode.clone());
+        self.error_details = Some(details);
+        self.error_scroll = 0;
+        self.modal_button = 0; // Select action button by default
+        self.mode = Mode::ErrorPopup;
+    }
+
+    /// Dismiss the error popup and return to previous mode.
+    pub fn dismiss_error_popup(&mut self) {
+        self.error_details = None;
+        if let Some(mode) = self.previous_mode.take() {
+            self.mode = mode;
+        } else {
+            self.mode = Mode::Selecting;
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use super::App;
+use crate::sync::restore_backup;
+use crate::types::{BackupRecord, Fork, ModalAction, Mode, Toast};
+
+impl App {
+    /// Keep the backup ref made before a clone was reset or rebased, so its
+    /// last sync can be undone (`u`).
+    pub fn record_backup(&mut self, backup: &BackupRecord) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.record_backup(backup) {
+                tracing::warn!(fork = %backup.id, "Failed to record backup ref: {e}");
+            }
+        }
+    }
+
+    /// Ask to undo the last sync of the fork under the cursor, if it reset
+    /// or rebased the clone.
+    pub fn prompt_undo(&mut self) {
+        let Some(id) = self.current_fork().map(Fork::full_name) else {
+            return;
+        };
+        self.undo = self
+            .store
+            .as_ref()
+            .and_then(|store| store.backup(&id).ok().flatten());
+        if self.undo.is_some() {
+            self.modal_action = ModalAction::Undo;
+            self.mode = Mode::ConfirmModal;
+        } else {
+            self.show_message(&format!(
+                "No sync of {id} to undo (only resets and rebases are)"
+            ));
+        }
+    }
+
+    /// Put the branch back to its backup ref, then forget the backup.
+    pub fn undo_last_sync(&mut self) {
+        let Some(backup) = self.undo.take() else {
+            return;
+        };
+        if self.dry_run {
+            self.show_message(&format!(
+                "Would put {} back to {}",
+                backup.branch, backup.ref_name
+            ));
+            return;
+        }
+        match restore_backup(&backup) {
+            Ok(()) => {
+                self.add_toast(Toast::success(format!(
+                    "Put {} of {} back to {}",
+                    backup.branch,
+                    backup.id,
+                    &backup.oid[..backup.oid.len().min(7)]
+                )));
+                if let Some(store) = &self.store {
+                    let _ = store.remove_backup(&backup.id);
+                }
+                self.git_status = None;
+            }
+            Err(e) => self.show_message(&format!("{}: {e}", backup.id)),
+        }
+    }
+}
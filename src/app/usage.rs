@@ -0,0 +1,65 @@
+use super::App;
+use chrono::Datelike;
+use crossterm::event::KeyCode;
+use std::collections::HashMap;
+
+impl App {
+    /// Count a key press in the local usage statistics.
+    pub fn track_key(&mut self, key: KeyCode) {
+        if !self.usage_enabled {
+            return;
+        }
+        let name = match key {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            other => format!("{other:?}"),
+        };
+        self.usage.record_key(&name);
+    }
+
+    /// Count a feature use (sync, clone, search...) in the local usage statistics.
+    pub fn track_action(&mut self, action: &str) {
+        if self.usage_enabled {
+            self.usage.record_action(action);
+        }
+    }
+
+    /// Load this year's usage statistics, including the current session.
+    pub fn compute_usage_stats(&mut self) {
+        let year = self.usage.started_at.year();
+        let mut stats = self
+            .store
+            .as_ref()
+            .and_then(|s| s.usage_stats(year).ok())
+            .unwrap_or_default();
+
+        stats.sessions += 1;
+        merge_counts(&mut stats.keys, &self.usage.keys);
+        merge_counts(&mut stats.actions, &self.usage.actions);
+        self.usage_stats = Some(stats);
+    }
+
+    /// Persist the current session's counters. Called once on exit.
+    pub fn save_usage(&self) {
+        if !self.usage_enabled {
+            return;
+        }
+        if let Some(store) = &self.store {
+            if let Err(e) = store.record_usage(&self.usage) {
+                tracing::warn!("Failed to save usage statistics: {e}");
+            }
+        }
+    }
+}
+
+/// Add session counts into stored counts, keeping them sorted by count.
+fn merge_counts(stored: &mut Vec<(String, u64)>, session: &HashMap<String, u64>) {
+    for (name, count) in session {
+        if let Some(entry) = stored.iter_mut().find(|(n, _)| n == name) {
+            entry.1 += count;
+        } else {
+            stored.push((name.clone(), *count));
+        }
+    }
+    stored.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+}
@@ -0,0 +1,93 @@
+use super::SqliteStore;
+use crate::types::{CachedResponse, API_CACHE_LIMIT};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+
+// ============================================================
+// API RESPONSE CACHE
+// ============================================================
+
+impl SqliteStore {
+    pub(super) fn upsert_api_responses(&self, responses: &[CachedResponse]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for response in responses {
+            tx.execute(
+                "INSERT OR REPLACE INTO api_cache
+                     (request, etag, last_modified, output, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    response.request,
+                    response.etag,
+                    response.last_modified,
+                    response.output,
+                    response.fetched_at.to_rfc3339(),
+                ],
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM api_cache WHERE request NOT IN
+                 (SELECT request FROM api_cache ORDER BY fetched_at DESC LIMIT ?1)",
+            params![API_CACHE_LIMIT as i64],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub(super) fn load_api_responses(&self) -> Result<Vec<CachedResponse>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT request, etag, last_modified, output, fetched_at FROM api_cache")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(request, etag, last_modified, output, at)| {
+                Some(CachedResponse {
+                    request,
+                    etag,
+                    last_modified,
+                    output,
+                    fetched_at: DateTime::parse_from_rfc3339(&at).ok()?.with_timezone(&Utc),
+                })
+            })
+            .collect())
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_api_responses_replace_by_request() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let response = |output: &str| CachedResponse {
+            request: "repos/me/serde/compare/main...serde-rs:main --jq .ahead_by".to_string(),
+            etag: Some("W/\"abc\"".to_string()),
+            last_modified: None,
+            output: output.to_string(),
+            fetched_at: "2026-10-16T12:00:00Z".parse().unwrap(),
+        };
+        store.upsert_api_responses(&[response("3")]).unwrap();
+        store.upsert_api_responses(&[response("5")]).unwrap();
+        assert_eq!(store.load_api_responses().unwrap(), vec![response("5")]);
+    }
+}
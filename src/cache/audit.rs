@@ -0,0 +1,112 @@
+use super::SqliteStore;
+use crate::types::{AuditEntry, AUDIT_LIMIT};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+
+// ============================================================
+// COMMAND AUDIT LOG
+// ============================================================
+
+impl SqliteStore {
+    pub(super) fn insert_commands(&self, entries: &[AuditEntry]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for entry in entries {
+            tx.execute(
+                "INSERT INTO command_audit
+                     (started_at, command, cwd, exit_code, duration_ms, output)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    entry.started_at.to_rfc3339(),
+                    entry.command,
+                    entry.cwd,
+                    entry.exit_code,
+                    i64::try_from(entry.duration_ms).unwrap_or(i64::MAX),
+                    entry.output,
+                ],
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM command_audit WHERE rowid <= (SELECT MAX(rowid) FROM command_audit) - ?1",
+            params![AUDIT_LIMIT as i64],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub(super) fn load_commands(&self, limit: usize) -> Result<Vec<AuditEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT started_at, command, cwd, exit_code, duration_ms, output
+             FROM command_audit ORDER BY rowid DESC LIMIT ?1",
+        )?;
+        let rows = stmt
+            .query_map(params![i64::try_from(limit).unwrap_or(i64::MAX)], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<i32>>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, String>(5)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(at, command, cwd, exit_code, duration_ms, output)| {
+                Some(AuditEntry {
+                    started_at: DateTime::parse_from_rfc3339(&at).ok()?.with_timezone(&Utc),
+                    command,
+                    cwd,
+                    exit_code,
+                    duration_ms: duration_ms.unsigned_abs(),
+                    output,
+                })
+            })
+            .collect())
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_command_audit_keeps_the_latest() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let entry = |n: usize| AuditEntry {
+            started_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            command: format!("git -C /src/me/cargo fetch upstream #{n}"),
+            cwd: "/home/me".to_string(),
+            exit_code: n.is_multiple_of(2).then_some(0),
+            duration_ms: 120,
+            output: String::new(),
+        };
+        let entries: Vec<AuditEntry> = (0..AUDIT_LIMIT + 2).map(entry).collect();
+        store.insert_commands(&entries[..2]).unwrap();
+        store.insert_commands(&entries[2..]).unwrap();
+
+        let recent = store.load_commands(3).unwrap();
+        assert_eq!(
+            recent,
+            vec![
+                entry(AUDIT_LIMIT + 1),
+                entry(AUDIT_LIMIT),
+                entry(AUDIT_LIMIT - 1),
+            ]
+        );
+        assert_eq!(store.load_commands(usize::MAX).unwrap().len(), AUDIT_LIMIT);
+        assert_eq!(
+            store.load_commands(usize::MAX).unwrap().last(),
+            Some(&entry(2))
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use super::SqliteStore;
+use crate::types::{FailureRecord, WeekTrend};
+use anyhow::Result;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use rusqlite::params;
+use std::collections::HashMap;
+
+// ============================================================
+// SYNC HISTORY (success-rate trend)
+// ============================================================
+
+impl SqliteStore {
+    pub(super) fn add_history(
+        &self,
+        id: &str,
+        outcome: &str,
+        detail: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO sync_history (id, finished_at, outcome, detail) VALUES (?1, ?2, ?3, ?4)",
+            params![id, at.to_rfc3339(), outcome, detail],
+        )?;
+        Ok(())
+    }
+
+    /// Syncs and failures per week (starting Monday) for the `weeks` weeks
+    /// up to `today`.
+    pub(super) fn weekly_history(&self, today: NaiveDate, weeks: u32) -> Result<Vec<WeekTrend>> {
+        let since = WeekTrend::week_of(today) - Days::new(u64::from(weeks.saturating_sub(1)) * 7);
+        // 'weekday 0' moves to the coming Sunday, so 6 days back is Monday
+        let mut stmt = self.conn.prepare(
+            "SELECT date(finished_at, 'weekday 0', '-6 days') AS week,
+                    COUNT(*),
+                    SUM(outcome = 'failed')
+             FROM sync_history
+             WHERE date(finished_at) >= ?1
+             GROUP BY week",
+        )?;
+        let counts = stmt
+            .query_map(params![since.to_string()], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    (row.get::<_, i64>(1)?, row.get::<_, i64>(2)?),
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|(week, (syncs, failures))| {
+                let week = week.parse::<NaiveDate>().ok()?;
+                Some((week, (syncs.unsigned_abs(), failures.unsigned_abs())))
+            })
+            .collect::<HashMap<_, _>>();
+        Ok(WeekTrend::fill(&counts, today, weeks))
+    }
+
+    pub(super) fn load_last_synced(&self) -> Result<HashMap<String, DateTime<Utc>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, MAX(finished_at) FROM sync_history
+             WHERE outcome = 'synced'
+             GROUP BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, at)| {
+                let at = DateTime::parse_from_rfc3339(&at).ok()?;
+                Some((id, at.with_timezone(&Utc)))
+            })
+            .collect())
+    }
+
+    pub(super) fn load_recent_failures(
+        &self,
+        per_fork: usize,
+    ) -> Result<HashMap<String, Vec<FailureRecord>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, finished_at, detail FROM (
+                 SELECT id, finished_at, detail,
+                        ROW_NUMBER() OVER (PARTITION BY id ORDER BY finished_at DESC) AS n
+                 FROM sync_history
+                 WHERE detail IS NOT NULL
+             )
+             WHERE n <= ?1
+             ORDER BY finished_at DESC",
+        )?;
+        let rows = stmt
+            .query_map(params![per_fork], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut failures: HashMap<String, Vec<FailureRecord>> = HashMap::new();
+        for (id, at, message) in rows {
+            if let Ok(at) = DateTime::parse_from_rfc3339(&at) {
+                failures.entry(id).or_default().push(FailureRecord {
+                    at: at.with_timezone(&Utc),
+                    message,
+                });
+            }
+        }
+        Ok(failures)
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_weekly_history() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let at = |date: &str| {
+            format!("{date}T12:00:00Z")
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        };
+        // Sunday 2026-10-18 still belongs to the week of Monday 2026-10-12
+        store
+            .add_history("a/one", "synced", None, at("2026-10-12"))
+            .unwrap();
+        store
+            .add_history("a/two", "failed", Some("checkout failed"), at("2026-10-18"))
+            .unwrap();
+        store
+            .add_history("a/one", "synced", None, at("2026-10-05"))
+            .unwrap();
+        // Too old for a three-week trend
+        store
+            .add_history("a/one", "failed", Some("diverged"), at("2026-09-01"))
+            .unwrap();
+
+        let today = "2026-10-14".parse().unwrap();
+        let trend = store.weekly_history(today, 3).unwrap();
+        let weeks: Vec<(String, u64, u64)> = trend
+            .iter()
+            .map(|w| (w.week.to_string(), w.syncs, w.failures))
+            .collect();
+        assert_eq!(
+            weeks,
+            vec![
+                ("2026-09-28".to_string(), 0, 0),
+                ("2026-10-05".to_string(), 1, 0),
+                ("2026-10-12".to_string(), 2, 1),
+            ]
+        );
+        assert_eq!(trend[2].failure_percent(), 50);
+
+        let last = store.load_last_synced().unwrap();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last["a/one"], at("2026-10-12"));
+
+        store
+            .add_history(
+                "a/two",
+                "attention",
+                Some("merge conflict"),
+                at("2026-10-19"),
+            )
+            .unwrap();
+        let failures = store.load_recent_failures(1).unwrap();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(
+            failures["a/two"],
+            vec![FailureRecord {
+                at: at("2026-10-19"),
+                message: "merge conflict".to_string(),
+            }]
+        );
+        assert_eq!(failures["a/one"][0].message, "diverged");
+    }
+}
@@ -0,0 +1,127 @@
+//! On-disk format of the JSON cache file.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+/// A fork as cached; local state is recomputed on load like in `SQLite`.
+#[derive(Serialize, Deserialize)]
+pub(super) struct CachedFork {
+    pub(super) name: String,
+    pub(super) owner: String,
+    pub(super) parent_owner: String,
+    pub(super) parent_name: String,
+    pub(super) default_branch: String,
+    pub(super) description: Option<String>,
+    pub(super) primary_language: Option<String>,
+    pub(super) created_at: Option<DateTime<Utc>>,
+    pub(super) updated_at: Option<DateTime<Utc>>,
+    pub(super) upstream_pushed_at: Option<DateTime<Utc>>,
+    pub(super) visibility: Option<String>,
+    pub(super) fetched_at: DateTime<Utc>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(super) struct YearUsage {
+    pub(super) sessions: u64,
+    pub(super) keys: BTreeMap<String, u64>,
+    pub(super) actions: BTreeMap<String, u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct StoredReminder {
+    pub(super) due: NaiveDate,
+    pub(super) note: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct StoredStash {
+    pub(super) path: PathBuf,
+    pub(super) oid: String,
+    pub(super) branch: String,
+    pub(super) stashed_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct StoredBackup {
+    pub(super) path: PathBuf,
+    pub(super) branch: String,
+    pub(super) ref_name: String,
+    pub(super) oid: String,
+    pub(super) created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct StoredCommand {
+    pub(super) started_at: DateTime<Utc>,
+    pub(super) command: String,
+    pub(super) cwd: String,
+    pub(super) exit_code: Option<i32>,
+    pub(super) duration_ms: u64,
+    pub(super) output: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct StoredResponse {
+    pub(super) etag: Option<String>,
+    pub(super) last_modified: Option<String>,
+    pub(super) output: String,
+    pub(super) fetched_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct Snapshot {
+    pub(super) taken_at: DateTime<Utc>,
+    pub(super) heads: BTreeMap<String, Option<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct HistoryEntry {
+    pub(super) id: String,
+    pub(super) finished_at: DateTime<Utc>,
+    pub(super) outcome: String,
+    /// Why it failed or needed attention
+    pub(super) detail: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(super) struct Data {
+    pub(super) forks: BTreeMap<String, CachedFork>,
+    pub(super) last_full_sync: Option<DateTime<Utc>>,
+    pub(super) ignored: BTreeSet<String>,
+    pub(super) usage: BTreeMap<i32, YearUsage>,
+    pub(super) reminders: BTreeMap<String, StoredReminder>,
+    pub(super) path_overrides: BTreeMap<String, PathBuf>,
+    pub(super) sync_queue: Vec<String>,
+    pub(super) auto_stashes: BTreeMap<String, StoredStash>,
+    pub(super) backup_refs: BTreeMap<String, StoredBackup>,
+    pub(super) latest_snapshot: Option<Snapshot>,
+    pub(super) previous_snapshot: Option<Snapshot>,
+    pub(super) sync_history: Vec<HistoryEntry>,
+    /// Oldest first
+    pub(super) command_audit: Vec<StoredCommand>,
+    /// By request
+    pub(super) api_cache: BTreeMap<String, StoredResponse>,
+}
+
+impl Data {
+    /// Drop the least recently fetched API responses beyond `limit`.
+    pub(super) fn prune_api_cache(&mut self, limit: usize) {
+        let excess = self.api_cache.len().saturating_sub(limit);
+        if excess == 0 {
+            return;
+        }
+        let mut by_age: Vec<_> = self
+            .api_cache
+            .iter()
+            .map(|(request, r)| (r.fetched_at, request.clone()))
+            .collect();
+        by_age.sort();
+        for (_, request) in by_age.into_iter().take(excess) {
+            self.api_cache.remove(&request);
+        }
+    }
+}
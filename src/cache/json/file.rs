@@ -0,0 +1,87 @@
+//! Reading and rewriting the JSON file, locked against other writers.
+
+use super::data::Data;
+use super::JsonStore;
+use crate::lock::FileLock;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+impl JsonStore {
+    /// Use the JSON file at `path`, creating its directory if needed. The
+    /// file itself is written on the first change.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+        let store = Self {
+            path: path.to_path_buf(),
+        };
+        store.read()?;
+        Ok(store)
+    }
+
+    pub(super) fn read(&self) -> Result<Data> {
+        if !self.path.exists() {
+            return Ok(Data::default());
+        }
+        let contents = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid cache file {}", self.path.display()))
+    }
+
+    /// Read, change and write back the whole file, locked throughout.
+    pub(super) fn update(&self, change: impl FnOnce(&mut Data)) -> Result<()> {
+        let lock_path = self.path.with_extension("json.lock");
+        let _lock = FileLock::acquire(&lock_path)
+            .with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+        let mut data = self.read()?;
+        change(&mut data);
+        // A name of our own, so concurrent writers never rename each
+        // other's half-written file
+        let thread: String = format!("{:?}", std::thread::current().id())
+            .chars()
+            .filter(char::is_ascii_digit)
+            .collect();
+        let tmp = self
+            .path
+            .with_extension(format!("json.{}-{thread}.tmp", std::process::id()));
+        std::fs::write(&tmp, serde_json::to_string_pretty(&data)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to replace {}", self.path.display()))
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ForkStore;
+
+    #[test]
+    fn test_concurrent_stores_keep_every_change() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("forks.json");
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let store = JsonStore::open(&path).unwrap();
+                std::thread::spawn(move || {
+                    for n in 0..10 {
+                        store
+                            .set_ignored(&format!("me/{writer}-{n}"), true)
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        let store = JsonStore::open(&path).unwrap();
+        assert_eq!(store.ignored_forks().unwrap().len(), 40);
+    }
+}
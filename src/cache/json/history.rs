@@ -0,0 +1,54 @@
+//! Sync history queries over the JSON file's entries (grouped in SQL by the
+//! `SQLite` store).
+
+use super::data::HistoryEntry;
+use crate::types::{FailureRecord, WeekTrend};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+
+/// (syncs, failures) by week start.
+pub(super) fn weekly_counts(history: &[HistoryEntry]) -> HashMap<NaiveDate, (u64, u64)> {
+    let mut counts: HashMap<NaiveDate, (u64, u64)> = HashMap::new();
+    for entry in history {
+        let week = WeekTrend::week_of(entry.finished_at.date_naive());
+        let (syncs, failures) = counts.entry(week).or_default();
+        *syncs += 1;
+        if entry.outcome == "failed" {
+            *failures += 1;
+        }
+    }
+    counts
+}
+
+pub(super) fn last_synced(history: Vec<HistoryEntry>) -> HashMap<String, DateTime<Utc>> {
+    let mut last: HashMap<String, DateTime<Utc>> = HashMap::new();
+    for entry in history {
+        if entry.outcome == "synced" {
+            let at = last.entry(entry.id).or_insert(entry.finished_at);
+            *at = (*at).max(entry.finished_at);
+        }
+    }
+    last
+}
+
+/// The last `per_fork` failures of each fork, newest first (entries are
+/// appended as syncs finish, so oldest first).
+pub(super) fn recent_failures(
+    history: Vec<HistoryEntry>,
+    per_fork: usize,
+) -> HashMap<String, Vec<FailureRecord>> {
+    let mut failures: HashMap<String, Vec<FailureRecord>> = HashMap::new();
+    for entry in history.into_iter().rev() {
+        let Some(message) = entry.detail else {
+            continue;
+        };
+        let records = failures.entry(entry.id).or_default();
+        if records.len() < per_fork {
+            records.push(FailureRecord {
+                at: entry.finished_at,
+                message,
+            });
+        }
+    }
+    failures
+}
@@ -0,0 +1,456 @@
+mod data;
+mod file;
+mod history;
+
+use crate::paths::ToolHomes;
+use crate::types::{
+    AuditEntry, BackupRecord, CachedResponse, FailureRecord, Fork, ForkStore, Reminder, RepoKind,
+    SnapshotDiff, StashRecord, SyncStatus, UsageSession, UsageStats, WeekTrend, API_CACHE_LIMIT,
+    AUDIT_LIMIT,
+};
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Utc};
+use data::{
+    CachedFork, HistoryEntry, Snapshot, StoredBackup, StoredCommand, StoredReminder,
+    StoredResponse, StoredStash,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// `ForkStore` kept in a single human-readable JSON file.
+///
+/// Every call reads the file and every change rewrites it (via a temporary
+/// file) while holding a lock on `<file>.lock`, so background threads and
+/// other processes with their own `JsonStore` don't lose each other's
+/// changes.
+pub struct JsonStore {
+    path: PathBuf,
+}
+
+/// Counters sorted like the `SQLite` store: by count (descending), then name.
+fn sorted_counts(counts: &BTreeMap<String, u64>) -> Vec<(String, u64)> {
+    let mut counts: Vec<(String, u64)> = counts.iter().map(|(k, &n)| (k.clone(), n)).collect();
+    counts.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    counts
+}
+
+impl ForkStore for JsonStore {
+    fn load_forks(&self, tool_homes: &ToolHomes) -> Result<Vec<Fork>> {
+        let mut forks: Vec<Fork> = self
+            .read()?
+            .forks
+            .into_values()
+            .map(|f| {
+                let (local_path, is_cloned) = tool_homes.locate(&f.owner, &f.name);
+                Fork {
+                    name: f.name,
+                    owner: f.owner,
+                    parent_owner: f.parent_owner,
+                    parent_name: f.parent_name,
+                    default_branch: f.default_branch,
+                    local_path,
+                    is_cloned,
+                    description: f.description,
+                    primary_language: f.primary_language,
+                    created_at: f.created_at,
+                    updated_at: f.updated_at,
+                    upstream_head: None,
+                    upstream_pushed_at: f.upstream_pushed_at,
+                    visibility: f.visibility,
+                    kind: RepoKind::Fork,
+                }
+            })
+            .collect();
+        forks.sort_by_key(|f| std::cmp::Reverse(f.created_at));
+        Ok(forks)
+    }
+
+    fn save_forks(&self, forks: &[Fork]) -> Result<()> {
+        let now = Utc::now();
+        self.update(|data| {
+            for fork in forks {
+                data.forks.insert(
+                    fork.full_name(),
+                    CachedFork {
+                        name: fork.name.clone(),
+                        owner: fork.owner.clone(),
+                        parent_owner: fork.parent_owner.clone(),
+                        parent_name: fork.parent_name.clone(),
+                        default_branch: fork.default_branch.clone(),
+                        description: fork.description.clone(),
+                        primary_language: fork.primary_language.clone(),
+                        created_at: fork.created_at,
+                        updated_at: fork.updated_at,
+                        upstream_pushed_at: fork.upstream_pushed_at,
+                        visibility: fork.visibility.clone(),
+                        fetched_at: now,
+                    },
+                );
+            }
+        })
+    }
+
+    fn remove_fork(&self, id: &str) -> Result<()> {
+        self.update(|data| {
+            data.forks.remove(id);
+        })
+    }
+
+    fn prune_forks(&self, keep: &[Fork]) -> Result<Vec<String>> {
+        let keep: HashSet<String> = keep.iter().map(Fork::full_name).collect();
+        let mut removed = Vec::new();
+        self.update(|data| {
+            data.forks.retain(|id, _| {
+                let kept = keep.contains(id);
+                if !kept {
+                    removed.push(id.clone());
+                }
+                kept
+            });
+        })?;
+        Ok(removed)
+    }
+
+    fn is_empty(&self) -> Result<bool> {
+        Ok(self.read()?.forks.is_empty())
+    }
+
+    fn last_full_sync(&self) -> Result<Option<DateTime<Utc>>> {
+        Ok(self.read()?.last_full_sync)
+    }
+
+    fn set_last_full_sync(&self, when: DateTime<Utc>) -> Result<()> {
+        self.update(|data| data.last_full_sync = Some(when))
+    }
+
+    fn ignored_forks(&self) -> Result<HashSet<String>> {
+        Ok(self.read()?.ignored.into_iter().collect())
+    }
+
+    fn set_ignored(&self, id: &str, ignored: bool) -> Result<()> {
+        self.update(|data| {
+            if ignored {
+                data.ignored.insert(id.to_string());
+            } else {
+                data.ignored.remove(id);
+            }
+        })
+    }
+
+    fn record_usage(&self, session: &UsageSession) -> Result<()> {
+        self.update(|data| {
+            let year = data.usage.entry(session.started_at.year()).or_default();
+            year.sessions += 1;
+            for (key, n) in &session.keys {
+                *year.keys.entry(key.clone()).or_insert(0) += n;
+            }
+            for (action, n) in &session.actions {
+                *year.actions.entry(action.clone()).or_insert(0) += n;
+            }
+        })
+    }
+
+    fn usage_stats(&self, year: i32) -> Result<UsageStats> {
+        let data = self.read()?;
+        Ok(data
+            .usage
+            .get(&year)
+            .map(|usage| UsageStats {
+                sessions: usage.sessions,
+                keys: sorted_counts(&usage.keys),
+                actions: sorted_counts(&usage.actions),
+            })
+            .unwrap_or_default())
+    }
+
+    fn reminders(&self) -> Result<HashMap<String, Reminder>> {
+        Ok(self
+            .read()?
+            .reminders
+            .into_iter()
+            .map(|(id, r)| {
+                let reminder = Reminder {
+                    due: r.due,
+                    note: r.note,
+                };
+                (id, reminder)
+            })
+            .collect())
+    }
+
+    fn set_reminder(&self, id: &str, reminder: Option<&Reminder>) -> Result<()> {
+        self.update(|data| match reminder {
+            Some(r) => {
+                let stored = StoredReminder {
+                    due: r.due,
+                    note: r.note.clone(),
+                };
+                data.reminders.insert(id.to_string(), stored);
+            }
+            None => {
+                data.reminders.remove(id);
+            }
+        })
+    }
+
+    fn path_overrides(&self) -> Result<HashMap<String, PathBuf>> {
+        Ok(self.read()?.path_overrides.into_iter().collect())
+    }
+
+    fn set_path_override(&self, id: &str, path: Option<&Path>) -> Result<()> {
+        self.update(|data| match path {
+            Some(path) => {
+                data.path_overrides
+                    .insert(id.to_string(), path.to_path_buf());
+            }
+            None => {
+                data.path_overrides.remove(id);
+            }
+        })
+    }
+
+    fn save_sync_queue(&self, ids: &[String]) -> Result<()> {
+        self.update(|data| data.sync_queue = ids.to_vec())
+    }
+
+    fn remove_from_sync_queue(&self, id: &str) -> Result<()> {
+        self.update(|data| data.sync_queue.retain(|queued| queued != id))
+    }
+
+    fn sync_queue(&self) -> Result<Vec<String>> {
+        Ok(self.read()?.sync_queue)
+    }
+
+    fn record_stash(&self, stash: &StashRecord) -> Result<()> {
+        let stored = StoredStash {
+            path: stash.path.clone(),
+            oid: stash.oid.clone(),
+            branch: stash.branch.clone(),
+            stashed_at: stash.stashed_at,
+        };
+        self.update(|data| {
+            data.auto_stashes.insert(stash.id.clone(), stored);
+        })
+    }
+
+    fn remove_stash(&self, id: &str) -> Result<()> {
+        self.update(|data| {
+            data.auto_stashes.remove(id);
+        })
+    }
+
+    fn stashes(&self) -> Result<Vec<StashRecord>> {
+        let mut stashes: Vec<StashRecord> = self
+            .read()?
+            .auto_stashes
+            .into_iter()
+            .map(|(id, s)| StashRecord {
+                id,
+                path: s.path,
+                oid: s.oid,
+                branch: s.branch,
+                stashed_at: s.stashed_at,
+            })
+            .collect();
+        stashes.sort_by_key(|s| s.stashed_at);
+        Ok(stashes)
+    }
+
+    fn record_backup(&self, backup: &BackupRecord) -> Result<()> {
+        let stored = StoredBackup {
+            path: backup.path.clone(),
+            branch: backup.branch.clone(),
+            ref_name: backup.ref_name.clone(),
+            oid: backup.oid.clone(),
+            created_at: backup.created_at,
+        };
+        self.update(|data| {
+            data.backup_refs.insert(backup.id.clone(), stored);
+        })
+    }
+
+    fn remove_backup(&self, id: &str) -> Result<()> {
+        self.update(|data| {
+            data.backup_refs.remove(id);
+        })
+    }
+
+    fn backup(&self, id: &str) -> Result<Option<BackupRecord>> {
+        Ok(self.read()?.backup_refs.remove(id).map(|b| BackupRecord {
+            id: id.to_string(),
+            path: b.path,
+            branch: b.branch,
+            ref_name: b.ref_name,
+            oid: b.oid,
+            created_at: b.created_at,
+        }))
+    }
+
+    fn record_commands(&self, entries: &[AuditEntry]) -> Result<()> {
+        let stored = entries.iter().map(|e| StoredCommand {
+            started_at: e.started_at,
+            command: e.command.clone(),
+            cwd: e.cwd.clone(),
+            exit_code: e.exit_code,
+            duration_ms: e.duration_ms,
+            output: e.output.clone(),
+        });
+        self.update(|data| {
+            data.command_audit.extend(stored);
+            let excess = data.command_audit.len().saturating_sub(AUDIT_LIMIT);
+            data.command_audit.drain(..excess);
+        })
+    }
+
+    fn recent_commands(&self, limit: usize) -> Result<Vec<AuditEntry>> {
+        Ok(self
+            .read()?
+            .command_audit
+            .into_iter()
+            .rev()
+            .take(limit)
+            .map(|c| AuditEntry {
+                started_at: c.started_at,
+                command: c.command,
+                cwd: c.cwd,
+                exit_code: c.exit_code,
+                duration_ms: c.duration_ms,
+                output: c.output,
+            })
+            .collect())
+    }
+
+    fn save_api_responses(&self, responses: &[CachedResponse]) -> Result<()> {
+        self.update(|data| {
+            for r in responses {
+                data.api_cache.insert(
+                    r.request.clone(),
+                    StoredResponse {
+                        etag: r.etag.clone(),
+                        last_modified: r.last_modified.clone(),
+                        output: r.output.clone(),
+                        fetched_at: r.fetched_at,
+                    },
+                );
+            }
+            data.prune_api_cache(API_CACHE_LIMIT);
+        })
+    }
+
+    fn api_responses(&self) -> Result<Vec<CachedResponse>> {
+        Ok(self
+            .read()?
+            .api_cache
+            .into_iter()
+            .map(|(request, r)| CachedResponse {
+                request,
+                etag: r.etag,
+                last_modified: r.last_modified,
+                output: r.output,
+                fetched_at: r.fetched_at,
+            })
+            .collect())
+    }
+
+    fn record_snapshot(&self, forks: &[Fork]) -> Result<()> {
+        let snapshot = Snapshot {
+            taken_at: Utc::now(),
+            heads: forks
+                .iter()
+                .map(|f| (f.full_name(), f.upstream_head.clone()))
+                .collect(),
+        };
+        self.update(|data| {
+            data.previous_snapshot = data.latest_snapshot.replace(snapshot);
+        })
+    }
+
+    fn snapshot_diff(&self) -> Result<Option<SnapshotDiff>> {
+        let data = self.read()?;
+        let (Some(previous), Some(latest)) = (data.previous_snapshot, data.latest_snapshot) else {
+            return Ok(None);
+        };
+        let previous_heads: HashMap<_, _> = previous.heads.into_iter().collect();
+        let latest_heads: HashMap<_, _> = latest.heads.into_iter().collect();
+        let mut diff = SnapshotDiff::between(&previous_heads, &latest_heads);
+        diff.since = Some(previous.taken_at);
+        Ok(Some(diff))
+    }
+
+    fn record_sync(&self, id: &str, status: &SyncStatus) -> Result<()> {
+        let Some(outcome) = status.outcome() else {
+            return Ok(());
+        };
+        let entry = HistoryEntry {
+            id: id.to_string(),
+            finished_at: Utc::now(),
+            outcome: outcome.to_string(),
+            detail: status.failure().map(str::to_string),
+        };
+        self.update(|data| data.sync_history.push(entry))
+    }
+
+    fn sync_trend(&self, weeks: u32) -> Result<Vec<WeekTrend>> {
+        let counts = history::weekly_counts(&self.read()?.sync_history);
+        Ok(WeekTrend::fill(&counts, Utc::now().date_naive(), weeks))
+    }
+
+    fn last_synced(&self) -> Result<HashMap<String, DateTime<Utc>>> {
+        Ok(history::last_synced(self.read()?.sync_history))
+    }
+
+    fn recent_failures(&self, per_fork: usize) -> Result<HashMap<String, Vec<FailureRecord>>> {
+        Ok(history::recent_failures(
+            self.read()?.sync_history,
+            per_fork,
+        ))
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fork(name: &str) -> Fork {
+        Fork {
+            created_at: Some(Utc::now()),
+            upstream_head: Some(format!("{name}-head")),
+            visibility: Some("public".to_string()),
+            ..Fork::test("me", name)
+        }
+    }
+
+    #[test]
+    fn test_json_store_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("repo-syncer-json-{}", std::process::id()));
+        let store = JsonStore::open(&dir.join("forks.json")).unwrap();
+        assert!(store.is_empty().unwrap());
+
+        store.save_forks(&[fork("cargo"), fork("serde")]).unwrap();
+        store.set_ignored("me/serde", true).unwrap();
+        store.record_snapshot(&[fork("cargo")]).unwrap();
+        store
+            .record_snapshot(&[fork("cargo"), fork("serde")])
+            .unwrap();
+        assert_eq!(
+            store.prune_forks(&[fork("cargo")]).unwrap(),
+            vec!["me/serde"]
+        );
+
+        // A second handle sees the same file, like a background thread would
+        let reopened = JsonStore::open(&dir.join("forks.json")).unwrap();
+        let homes = ToolHomes::new(vec![dir.clone()]);
+        let forks = reopened.load_forks(&homes).unwrap();
+        assert_eq!(forks.len(), 1);
+        assert_eq!(forks[0].visibility.as_deref(), Some("public"));
+        assert!(reopened.ignored_forks().unwrap().contains("me/serde"));
+        let diff = reopened.snapshot_diff().unwrap().unwrap();
+        assert_eq!(diff.added, vec!["me/serde"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,412 @@
+mod api;
+mod audit;
+mod history;
+mod json;
+mod schema;
+mod session;
+mod snapshot;
+mod user;
+
+pub use json::JsonStore;
+
+use crate::config::StoreBackend;
+use crate::paths::ToolHomes;
+use crate::types::{
+    AuditEntry, BackupRecord, CachedResponse, FailureRecord, Fork, ForkStore, Reminder, RepoKind,
+    SnapshotDiff, StashRecord, SyncStatus, UsageSession, UsageStats, WeekTrend,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Open the configured store. The JSON store sits next to where the
+/// database would be, with a `.json` extension.
+pub fn open_store(backend: StoreBackend, db_file: &Path) -> Result<Box<dyn ForkStore>> {
+    Ok(match backend {
+        StoreBackend::Sqlite => Box::new(SqliteStore::open(db_file)?),
+        StoreBackend::Json => Box::new(JsonStore::open(&db_file.with_extension("json"))?),
+    })
+}
+
+/// `SQLite`-backed implementation of `ForkStore`.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Open or create the `SQLite` database at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+
+        let conn = Connection::open(path).context("Failed to open cache database")?;
+
+        let store = Self { conn };
+        store.init_schema()?;
+
+        Ok(store)
+    }
+
+    /// Get a metadata value.
+    pub(super) fn get_metadata(&self, key: &str) -> Result<Option<String>> {
+        let result = self.conn.query_row(
+            "SELECT value FROM metadata WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Set a metadata value.
+    pub(super) fn set_metadata(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO metadata (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Save a single fork to the store.
+    #[allow(dead_code)]
+    pub fn save_fork(&self, fork: &Fork) -> Result<()> {
+        self.save_forks(std::slice::from_ref(fork))
+    }
+
+    /// Check if a fork exists in the store.
+    #[allow(dead_code)]
+    pub fn has_fork(&self, owner: &str, name: &str) -> Result<bool> {
+        let id = format!("{owner}/{name}");
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM forks WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Get the number of stored forks.
+    #[allow(dead_code)]
+    pub fn fork_count(&self) -> Result<i64> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM forks", [], |row| row.get(0))?;
+        Ok(count)
+    }
+}
+
+fn parse_timestamp(s: Option<String>) -> Option<DateTime<Utc>> {
+    s.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+// ============================================================
+// ForkStore TRAIT IMPLEMENTATION
+// ============================================================
+
+impl ForkStore for SqliteStore {
+    fn load_forks(&self, tool_homes: &ToolHomes) -> Result<Vec<Fork>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, owner, parent_owner, parent_name, default_branch,
+                    description, primary_language, created_at, updated_at,
+                    upstream_pushed_at, visibility
+             FROM forks
+             ORDER BY created_at DESC NULLS LAST",
+        )?;
+
+        let forks = stmt
+            .query_map([], |row| {
+                let name: String = row.get(1)?;
+                let owner: String = row.get(2)?;
+                let parent_owner: String = row.get(3)?;
+                let parent_name: String = row.get(4)?;
+                let default_branch: String = row.get(5)?;
+                let description: Option<String> = row.get(6)?;
+                let primary_language: Option<String> = row.get(7)?;
+                let created_at: Option<String> = row.get(8)?;
+                let updated_at: Option<String> = row.get(9)?;
+                let upstream_pushed_at: Option<String> = row.get(10)?;
+                let visibility: Option<String> = row.get(11)?;
+
+                let (local_path, is_cloned) = tool_homes.locate(&owner, &name);
+
+                Ok(Fork {
+                    name,
+                    owner,
+                    parent_owner,
+                    parent_name,
+                    default_branch,
+                    local_path,
+                    is_cloned,
+                    description,
+                    primary_language,
+                    created_at: parse_timestamp(created_at),
+                    updated_at: parse_timestamp(updated_at),
+                    upstream_head: None,
+                    upstream_pushed_at: parse_timestamp(upstream_pushed_at),
+                    visibility,
+                    kind: RepoKind::Fork,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(forks)
+    }
+
+    fn save_forks(&self, forks: &[Fork]) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+
+        for fork in forks {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO forks
+                 (id, name, owner, parent_owner, parent_name, default_branch,
+                  description, primary_language, created_at, updated_at, fetched_at,
+                  upstream_pushed_at, visibility)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    fork.full_name(),
+                    fork.name,
+                    fork.owner,
+                    fork.parent_owner,
+                    fork.parent_name,
+                    fork.default_branch,
+                    fork.description,
+                    fork.primary_language,
+                    fork.created_at.map(|dt| dt.to_rfc3339()),
+                    fork.updated_at.map(|dt| dt.to_rfc3339()),
+                    now,
+                    fork.upstream_pushed_at.map(|dt| dt.to_rfc3339()),
+                    fork.visibility,
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn remove_fork(&self, id: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM forks WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    fn prune_forks(&self, keep: &[Fork]) -> Result<Vec<String>> {
+        let keep: HashSet<String> = keep.iter().map(Fork::full_name).collect();
+        let mut stmt = self.conn.prepare("SELECT id FROM forks")?;
+        let stale: Vec<String> = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|id| !keep.contains(id))
+            .collect();
+        for id in &stale {
+            self.remove_fork(id)?;
+        }
+        Ok(stale)
+    }
+
+    fn is_empty(&self) -> Result<bool> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM forks", [], |row| row.get(0))?;
+        Ok(count == 0)
+    }
+
+    fn last_full_sync(&self) -> Result<Option<DateTime<Utc>>> {
+        self.get_metadata("last_full_sync")?
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| Ok(dt.with_timezone(&Utc)))
+            .transpose()
+    }
+
+    fn set_last_full_sync(&self, when: DateTime<Utc>) -> Result<()> {
+        self.set_metadata("last_full_sync", &when.to_rfc3339())
+    }
+
+    fn ignored_forks(&self) -> Result<HashSet<String>> {
+        self.load_ignored()
+    }
+
+    fn set_ignored(&self, id: &str, ignored: bool) -> Result<()> {
+        self.store_ignored(id, ignored)
+    }
+
+    fn record_usage(&self, session: &UsageSession) -> Result<()> {
+        self.add_usage(session)
+    }
+
+    fn usage_stats(&self, year: i32) -> Result<UsageStats> {
+        self.load_usage(year)
+    }
+
+    fn reminders(&self) -> Result<HashMap<String, Reminder>> {
+        self.load_reminders()
+    }
+
+    fn set_reminder(&self, id: &str, reminder: Option<&Reminder>) -> Result<()> {
+        self.store_reminder(id, reminder)
+    }
+
+    fn path_overrides(&self) -> Result<HashMap<String, PathBuf>> {
+        self.load_path_overrides()
+    }
+
+    fn set_path_override(&self, id: &str, path: Option<&Path>) -> Result<()> {
+        self.store_path_override(id, path)
+    }
+
+    fn save_sync_queue(&self, ids: &[String]) -> Result<()> {
+        self.replace_sync_queue(ids)
+    }
+
+    fn remove_from_sync_queue(&self, id: &str) -> Result<()> {
+        self.dequeue(id)
+    }
+
+    fn sync_queue(&self) -> Result<Vec<String>> {
+        self.load_sync_queue()
+    }
+
+    fn record_stash(&self, stash: &StashRecord) -> Result<()> {
+        self.insert_stash(stash)
+    }
+
+    fn remove_stash(&self, id: &str) -> Result<()> {
+        self.delete_stash(id)
+    }
+
+    fn stashes(&self) -> Result<Vec<StashRecord>> {
+        self.load_stashes()
+    }
+
+    fn record_backup(&self, backup: &BackupRecord) -> Result<()> {
+        self.insert_backup(backup)
+    }
+
+    fn remove_backup(&self, id: &str) -> Result<()> {
+        self.delete_backup(id)
+    }
+
+    fn backup(&self, id: &str) -> Result<Option<BackupRecord>> {
+        self.load_backup(id)
+    }
+
+    fn record_commands(&self, entries: &[AuditEntry]) -> Result<()> {
+        self.insert_commands(entries)
+    }
+
+    fn recent_commands(&self, limit: usize) -> Result<Vec<AuditEntry>> {
+        self.load_commands(limit)
+    }
+
+    fn save_api_responses(&self, responses: &[CachedResponse]) -> Result<()> {
+        self.upsert_api_responses(responses)
+    }
+
+    fn api_responses(&self) -> Result<Vec<CachedResponse>> {
+        self.load_api_responses()
+    }
+
+    fn record_snapshot(&self, forks: &[Fork]) -> Result<()> {
+        self.rotate_snapshots(forks)
+    }
+
+    fn snapshot_diff(&self) -> Result<Option<SnapshotDiff>> {
+        self.diff_snapshots()
+    }
+
+    fn record_sync(&self, id: &str, status: &SyncStatus) -> Result<()> {
+        match status.outcome() {
+            Some(outcome) => self.add_history(id, outcome, status.failure(), Utc::now()),
+            None => Ok(()),
+        }
+    }
+
+    fn sync_trend(&self, weeks: u32) -> Result<Vec<WeekTrend>> {
+        self.weekly_history(Utc::now().date_naive(), weeks)
+    }
+
+    fn last_synced(&self) -> Result<HashMap<String, DateTime<Utc>>> {
+        self.load_last_synced()
+    }
+
+    fn recent_failures(&self, per_fork: usize) -> Result<HashMap<String, Vec<FailureRecord>>> {
+        self.load_recent_failures(per_fork)
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_fork() -> Fork {
+        Fork {
+            description: Some("A test repo".to_string()),
+            primary_language: Some("Rust".to_string()),
+            created_at: Some(Utc::now()),
+            updated_at: Some(Utc::now()),
+            ..Fork::test("testuser", "test-repo")
+        }
+    }
+
+    #[test]
+    fn test_store_roundtrip() {
+        // Use in-memory database for testing
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let fork = test_fork();
+        store.save_fork(&fork).unwrap();
+
+        let forks = store
+            .load_forks(&ToolHomes::new(vec![PathBuf::from("/tmp")]))
+            .unwrap();
+        assert_eq!(forks.len(), 1);
+        assert_eq!(forks[0].name, "test-repo");
+        assert_eq!(forks[0].owner, "testuser");
+    }
+
+    #[test]
+    fn test_prune_forks() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let kept = test_fork();
+        let gone = Fork {
+            name: "deleted".to_string(),
+            ..test_fork()
+        };
+        store.save_forks(&[kept.clone(), gone]).unwrap();
+
+        let removed = store.prune_forks(std::slice::from_ref(&kept)).unwrap();
+        assert_eq!(removed, vec!["testuser/deleted".to_string()]);
+        assert!(store.has_fork("testuser", "test-repo").unwrap());
+        assert!(!store.has_fork("testuser", "deleted").unwrap());
+    }
+
+    #[test]
+    fn test_trait_object() {
+        // Verify SqliteStore can be used as dyn ForkStore
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let store_ref: &dyn ForkStore = &store;
+        assert!(store_ref.is_empty().unwrap());
+    }
+}
@@ -0,0 +1,190 @@
+use super::SqliteStore;
+use anyhow::{Context, Result};
+
+const SCHEMA_VERSION: i32 = 15;
+
+impl SqliteStore {
+    /// Initialize the database schema.
+    pub(super) fn init_schema(&self) -> Result<()> {
+        // Check schema version
+        let version = self
+            .get_metadata("schema_version")
+            .unwrap_or(None)
+            .and_then(|v| v.parse::<i32>().ok())
+            .unwrap_or(0);
+
+        if version < SCHEMA_VERSION {
+            // Create or migrate schema
+            self.conn
+                .execute_batch(
+                    r"
+                CREATE TABLE IF NOT EXISTS forks (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    owner TEXT NOT NULL,
+                    parent_owner TEXT NOT NULL,
+                    parent_name TEXT NOT NULL,
+                    default_branch TEXT NOT NULL,
+                    description TEXT,
+                    primary_language TEXT,
+                    created_at TEXT,
+                    updated_at TEXT,
+                    fetched_at TEXT NOT NULL,
+                    upstream_pushed_at TEXT,
+                    visibility TEXT
+                );
+
+                CREATE TABLE IF NOT EXISTS metadata (
+                    key TEXT PRIMARY KEY,
+                    value TEXT
+                );
+
+                CREATE TABLE IF NOT EXISTS ignored_forks (
+                    id TEXT PRIMARY KEY,
+                    ignored_at TEXT NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS usage_counters (
+                    year INTEGER NOT NULL,
+                    category TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    count INTEGER NOT NULL,
+                    PRIMARY KEY (year, category, name)
+                );
+
+                CREATE TABLE IF NOT EXISTS reminders (
+                    id TEXT PRIMARY KEY,
+                    due TEXT NOT NULL,
+                    note TEXT NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS path_overrides (
+                    id TEXT PRIMARY KEY,
+                    path TEXT NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS sync_queue (
+                    id TEXT PRIMARY KEY,
+                    position INTEGER NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS auto_stashes (
+                    id TEXT PRIMARY KEY,
+                    path TEXT NOT NULL,
+                    oid TEXT NOT NULL,
+                    branch TEXT NOT NULL,
+                    stashed_at TEXT NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS backup_refs (
+                    id TEXT PRIMARY KEY,
+                    path TEXT NOT NULL,
+                    branch TEXT NOT NULL,
+                    ref_name TEXT NOT NULL,
+                    oid TEXT NOT NULL,
+                    created_at TEXT NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS refresh_snapshots (
+                    generation INTEGER NOT NULL,
+                    id TEXT NOT NULL,
+                    upstream_head TEXT,
+                    PRIMARY KEY (generation, id)
+                );
+
+                CREATE TABLE IF NOT EXISTS sync_history (
+                    id TEXT NOT NULL,
+                    finished_at TEXT NOT NULL,
+                    outcome TEXT NOT NULL,
+                    detail TEXT
+                );
+
+                CREATE TABLE IF NOT EXISTS command_audit (
+                    started_at TEXT NOT NULL,
+                    command TEXT NOT NULL,
+                    cwd TEXT NOT NULL,
+                    exit_code INTEGER,
+                    duration_ms INTEGER NOT NULL,
+                    output TEXT NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS api_cache (
+                    request TEXT PRIMARY KEY,
+                    etag TEXT,
+                    last_modified TEXT,
+                    output TEXT NOT NULL,
+                    fetched_at TEXT NOT NULL
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_sync_history_finished_at
+                    ON sync_history(finished_at);
+                CREATE INDEX IF NOT EXISTS idx_forks_fetched_at ON forks(fetched_at);
+                CREATE INDEX IF NOT EXISTS idx_forks_created_at ON forks(created_at);
+                ",
+                )
+                .context("Failed to create schema")?;
+
+            // Columns added after a table was first created
+            self.add_column_if_missing("forks", "upstream_pushed_at", "TEXT")?;
+            self.add_column_if_missing("forks", "visibility", "TEXT")?;
+            self.add_column_if_missing("sync_history", "detail", "TEXT")?;
+
+            self.set_metadata("schema_version", &SCHEMA_VERSION.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    fn add_column_if_missing(&self, table: &str, column: &str, decl: &str) -> Result<()> {
+        let exists = self
+            .conn
+            .prepare(&format!("PRAGMA table_info({table})"))?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .filter_map(std::result::Result::ok)
+            .any(|name| name == column);
+        if !exists {
+            self.conn
+                .execute(
+                    &format!("ALTER TABLE {table} ADD COLUMN {column} {decl}"),
+                    [],
+                )
+                .with_context(|| format!("Failed to add {table}.{column}"))?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_migrates_old_forks_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE forks (id TEXT PRIMARY KEY, fetched_at TEXT, created_at TEXT);
+             CREATE TABLE metadata (key TEXT PRIMARY KEY, value TEXT);
+             INSERT INTO metadata VALUES ('schema_version', '6');",
+        )
+        .unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        // Adding the column again is a no-op
+        store
+            .add_column_if_missing("forks", "upstream_pushed_at", "TEXT")
+            .unwrap();
+        store
+            .conn
+            .execute(
+                "UPDATE forks SET upstream_pushed_at = NULL, visibility = NULL",
+                [],
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,227 @@
+use super::SqliteStore;
+use crate::types::{BackupRecord, StashRecord};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use std::path::PathBuf;
+
+// ============================================================
+// SYNC QUEUE (resume interrupted batches)
+// ============================================================
+
+impl SqliteStore {
+    pub(super) fn replace_sync_queue(&self, ids: &[String]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM sync_queue", [])?;
+        for (position, id) in ids.iter().enumerate() {
+            tx.execute(
+                "INSERT OR REPLACE INTO sync_queue (id, position) VALUES (?1, ?2)",
+                params![id, position as i64],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub(super) fn dequeue(&self, id: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM sync_queue WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub(super) fn load_sync_queue(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM sync_queue ORDER BY position")?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(ids)
+    }
+}
+
+// ============================================================
+// AUTO-STASHES (recover stashes of interrupted runs)
+// ============================================================
+
+impl SqliteStore {
+    pub(super) fn insert_stash(&self, stash: &StashRecord) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO auto_stashes (id, path, oid, branch, stashed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                stash.id,
+                stash.path.to_string_lossy(),
+                stash.oid,
+                stash.branch,
+                stash.stashed_at.to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn delete_stash(&self, id: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM auto_stashes WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub(super) fn load_stashes(&self) -> Result<Vec<StashRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, path, oid, branch, stashed_at FROM auto_stashes ORDER BY stashed_at",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        let stashes = rows
+            .into_iter()
+            .filter_map(|(id, path, oid, branch, at)| {
+                Some(StashRecord {
+                    id,
+                    path: PathBuf::from(path),
+                    oid,
+                    branch,
+                    stashed_at: DateTime::parse_from_rfc3339(&at).ok()?.with_timezone(&Utc),
+                })
+            })
+            .collect();
+        Ok(stashes)
+    }
+}
+
+// ============================================================
+// BACKUP REFS (undo the last destructive sync)
+// ============================================================
+
+impl SqliteStore {
+    pub(super) fn insert_backup(&self, backup: &BackupRecord) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO backup_refs (id, path, branch, ref_name, oid, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                backup.id,
+                backup.path.to_string_lossy(),
+                backup.branch,
+                backup.ref_name,
+                backup.oid,
+                backup.created_at.to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn delete_backup(&self, id: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM backup_refs WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub(super) fn load_backup(&self, id: &str) -> Result<Option<BackupRecord>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT path, branch, ref_name, oid, created_at FROM backup_refs WHERE id = ?1",
+                params![id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+        Ok(row.and_then(|(path, branch, ref_name, oid, at)| {
+            Some(BackupRecord {
+                id: id.to_string(),
+                path: PathBuf::from(path),
+                branch,
+                ref_name,
+                oid,
+                created_at: DateTime::parse_from_rfc3339(&at).ok()?.with_timezone(&Utc),
+            })
+        }))
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ForkStore;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_sync_queue_resume() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let ids = vec![
+            "a/one".to_string(),
+            "b/two".to_string(),
+            "c/three".to_string(),
+        ];
+        store.save_sync_queue(&ids).unwrap();
+        store.remove_from_sync_queue("b/two").unwrap();
+        assert_eq!(store.sync_queue().unwrap(), vec!["a/one", "c/three"]);
+
+        store.save_sync_queue(&[]).unwrap();
+        assert!(store.sync_queue().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_auto_stashes() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let stash = |id: &str, oid: &str| StashRecord {
+            id: id.to_string(),
+            path: PathBuf::from("/src/me/cargo"),
+            oid: oid.to_string(),
+            branch: "feature".to_string(),
+            stashed_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        };
+        store.record_stash(&stash("me/cargo", "abc")).unwrap();
+        store.record_stash(&stash("me/cargo", "def")).unwrap();
+        store.record_stash(&stash("me/serde", "123")).unwrap();
+        store.remove_stash("me/serde").unwrap();
+        assert_eq!(store.stashes().unwrap(), vec![stash("me/cargo", "def")]);
+    }
+
+    #[test]
+    fn test_backup_refs() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let backup = |oid: &str| BackupRecord {
+            id: "me/cargo".to_string(),
+            path: PathBuf::from("/src/me/cargo"),
+            branch: "main".to_string(),
+            ref_name: format!("refs/repo-syncer/backup/{oid}"),
+            oid: oid.to_string(),
+            created_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        };
+        assert_eq!(store.backup("me/cargo").unwrap(), None);
+        store.record_backup(&backup("abc")).unwrap();
+        store.record_backup(&backup("def")).unwrap();
+        assert_eq!(store.backup("me/cargo").unwrap(), Some(backup("def")));
+        store.remove_backup("me/cargo").unwrap();
+        assert_eq!(store.backup("me/cargo").unwrap(), None);
+    }
+}
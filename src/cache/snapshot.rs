@@ -0,0 +1,98 @@
+use super::SqliteStore;
+use crate::types::{Fork, SnapshotDiff};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use std::collections::HashMap;
+
+// ============================================================
+// REFRESH SNAPSHOTS (generation 0 = latest, 1 = previous)
+// ============================================================
+
+impl SqliteStore {
+    pub(super) fn rotate_snapshots(&self, forks: &[Fork]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM refresh_snapshots WHERE generation = 1", [])?;
+        tx.execute(
+            "UPDATE refresh_snapshots SET generation = 1 WHERE generation = 0",
+            [],
+        )?;
+        for fork in forks {
+            tx.execute(
+                "INSERT OR REPLACE INTO refresh_snapshots (generation, id, upstream_head)
+                 VALUES (0, ?1, ?2)",
+                params![fork.full_name(), fork.upstream_head],
+            )?;
+        }
+        tx.commit()?;
+
+        if let Some(taken) = self.get_metadata("snapshot_taken_at")? {
+            self.set_metadata("previous_snapshot_taken_at", &taken)?;
+        }
+        self.set_metadata("snapshot_taken_at", &Utc::now().to_rfc3339())
+    }
+
+    pub(super) fn diff_snapshots(&self) -> Result<Option<SnapshotDiff>> {
+        let previous = self.load_snapshot(1)?;
+        if previous.is_empty() {
+            return Ok(None);
+        }
+        let latest = self.load_snapshot(0)?;
+
+        let mut diff = SnapshotDiff::between(&previous, &latest);
+        diff.since = self
+            .get_metadata("previous_snapshot_taken_at")?
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        Ok(Some(diff))
+    }
+
+    fn load_snapshot(&self, generation: i64) -> Result<HashMap<String, Option<String>>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, upstream_head FROM refresh_snapshots WHERE generation = ?1")?;
+        let heads = stmt
+            .query_map(params![generation], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+        Ok(heads)
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ForkStore;
+    use rusqlite::Connection;
+
+    fn fork(name: &str, head: &str) -> Fork {
+        Fork {
+            upstream_head: Some(head.to_string()),
+            ..Fork::test("me", name)
+        }
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        store
+            .record_snapshot(&[fork("kept", "a1"), fork("moved", "b1"), fork("gone", "c1")])
+            .unwrap();
+        assert_eq!(store.snapshot_diff().unwrap(), None);
+
+        store
+            .record_snapshot(&[fork("kept", "a1"), fork("moved", "b2"), fork("new", "d1")])
+            .unwrap();
+        let diff = store.snapshot_diff().unwrap().unwrap();
+        assert_eq!(diff.added, vec!["me/new"]);
+        assert_eq!(diff.removed, vec!["me/gone"]);
+        assert_eq!(diff.upstream_moved, vec!["me/moved"]);
+        assert!(diff.since.is_some());
+    }
+}
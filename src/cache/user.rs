@@ -0,0 +1,226 @@
+use super::SqliteStore;
+use crate::types::{Reminder, UsageSession, UsageStats};
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate, Utc};
+use rusqlite::params;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+// ============================================================
+// USER DATA (ignore list, usage counters, reminders, path overrides)
+// ============================================================
+
+impl SqliteStore {
+    pub(super) fn load_ignored(&self) -> Result<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT id FROM ignored_forks")?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<HashSet<String>, _>>()?;
+        Ok(ids)
+    }
+
+    pub(super) fn store_ignored(&self, id: &str, ignored: bool) -> Result<()> {
+        if ignored {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO ignored_forks (id, ignored_at) VALUES (?1, ?2)",
+                params![id, Utc::now().to_rfc3339()],
+            )?;
+        } else {
+            self.conn
+                .execute("DELETE FROM ignored_forks WHERE id = ?1", params![id])?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn add_usage(&self, session: &UsageSession) -> Result<()> {
+        let year = session.started_at.year();
+        let counters = std::iter::once(("session", "count", 1))
+            .chain(session.keys.iter().map(|(k, n)| ("key", k.as_str(), *n)))
+            .chain(
+                session
+                    .actions
+                    .iter()
+                    .map(|(a, n)| ("action", a.as_str(), *n)),
+            );
+
+        for (category, name, count) in counters {
+            self.conn.execute(
+                "INSERT INTO usage_counters (year, category, name, count)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (year, category, name) DO UPDATE SET count = count + ?4",
+                params![year, category, name, count],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn load_usage(&self, year: i32) -> Result<UsageStats> {
+        let mut stmt = self.conn.prepare(
+            "SELECT category, name, count FROM usage_counters
+             WHERE year = ?1 ORDER BY count DESC, name",
+        )?;
+        let rows = stmt.query_map(params![year], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, u64>(2)?,
+            ))
+        })?;
+
+        let mut stats = UsageStats::default();
+        for row in rows {
+            let (category, name, count) = row?;
+            match category.as_str() {
+                "session" => stats.sessions += count,
+                "key" => stats.keys.push((name, count)),
+                "action" => stats.actions.push((name, count)),
+                _ => {}
+            }
+        }
+        Ok(stats)
+    }
+
+    pub(super) fn load_reminders(&self) -> Result<HashMap<String, Reminder>> {
+        let mut stmt = self.conn.prepare("SELECT id, due, note FROM reminders")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut reminders = HashMap::new();
+        for row in rows {
+            let (id, due, note) = row?;
+            if let Ok(due) = NaiveDate::parse_from_str(&due, "%Y-%m-%d") {
+                reminders.insert(id, Reminder { due, note });
+            }
+        }
+        Ok(reminders)
+    }
+
+    pub(super) fn store_reminder(&self, id: &str, reminder: Option<&Reminder>) -> Result<()> {
+        if let Some(reminder) = reminder {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO reminders (id, due, note) VALUES (?1, ?2, ?3)",
+                params![id, reminder.due.to_string(), reminder.note],
+            )?;
+        } else {
+            self.conn
+                .execute("DELETE FROM reminders WHERE id = ?1", params![id])?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn load_path_overrides(&self) -> Result<HashMap<String, PathBuf>> {
+        let mut stmt = self.conn.prepare("SELECT id, path FROM path_overrides")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        rows.map(|row| Ok(row.map(|(id, path)| (id, PathBuf::from(path)))?))
+            .collect()
+    }
+
+    pub(super) fn store_path_override(&self, id: &str, path: Option<&Path>) -> Result<()> {
+        if let Some(path) = path {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO path_overrides (id, path) VALUES (?1, ?2)",
+                params![id, path.to_string_lossy()],
+            )?;
+        } else {
+            self.conn
+                .execute("DELETE FROM path_overrides WHERE id = ?1", params![id])?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ForkStore;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_ignored_forks() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        store.set_ignored("testuser/test-repo", true).unwrap();
+        assert!(store
+            .ignored_forks()
+            .unwrap()
+            .contains("testuser/test-repo"));
+
+        store.set_ignored("testuser/test-repo", false).unwrap();
+        assert!(store.ignored_forks().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_usage_accumulates() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let mut session = UsageSession::new();
+        session.record_key("j");
+        session.record_key("j");
+        session.record_action("sync");
+        store.record_usage(&session).unwrap();
+        store.record_usage(&session).unwrap();
+
+        let stats = store.usage_stats(session.started_at.year()).unwrap();
+        assert_eq!(stats.sessions, 2);
+        assert_eq!(stats.keys, vec![("j".to_string(), 4)]);
+        assert_eq!(stats.action_count(), 2);
+    }
+
+    #[test]
+    fn test_reminders_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let reminder = Reminder {
+            due: NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            note: "revisit after upstream 2.0 ships".to_string(),
+        };
+        store
+            .set_reminder("testuser/test-repo", Some(&reminder))
+            .unwrap();
+        assert_eq!(
+            store.reminders().unwrap().get("testuser/test-repo"),
+            Some(&reminder)
+        );
+
+        store.set_reminder("testuser/test-repo", None).unwrap();
+        assert!(store.reminders().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_path_overrides_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        let store = SqliteStore { conn };
+        store.init_schema().unwrap();
+
+        let path = Path::new("/home/me/src/old-layout/cargo");
+        store.set_path_override("me/cargo", Some(path)).unwrap();
+        assert_eq!(
+            store
+                .path_overrides()
+                .unwrap()
+                .get("me/cargo")
+                .map(PathBuf::as_path),
+            Some(path)
+        );
+
+        store.set_path_override("me/cargo", None).unwrap();
+        assert!(store.path_overrides().unwrap().is_empty());
+    }
+}
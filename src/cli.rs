@@ -1,13 +1,53 @@
-use clap::Parser;
+use crate::config::{parse_owner_limit, CloneProtocol};
+use crate::events::EventFormat;
+use crate::filter::ForkFilter;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
+use tracing::level_filters::LevelFilter;
 
 #[derive(Parser)]
+#[allow(clippy::struct_excessive_bools)] // CLI flags are naturally booleans
 #[command(name = "repo-syncer")]
 #[command(about = "Interactive TUI to sync GitHub forks with their upstream repositories")]
 pub struct Args {
-    /// Home directory for cloned repos (default: $HOME/dev/github.com)
-    #[arg(long, env = "TOOL_HOME")]
-    pub tool_home: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Home directory for cloned repos (repeatable; the first is where new
+    /// clones go; default: $HOME/dev/github.com)
+    #[arg(long, env = "TOOL_HOME", value_delimiter = ':')]
+    pub tool_home: Vec<PathBuf>,
+
+    /// Use a profile from config.toml (`[profiles.<name>]`), with its own
+    /// tool homes, GitHub host/account, ignores, filter and cache database
+    #[arg(long, env = "REPO_SYNCER_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Keep config.toml and the cache database in this directory
+    #[arg(long, env = "REPO_SYNCER_DATA_DIR")]
+    pub data_dir: Option<PathBuf>,
+
+    /// Log level for the log file: off, error, warn, info, debug or trace
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        default_value = "info",
+        env = "REPO_SYNCER_LOG"
+    )]
+    pub log_level: LevelFilter,
+
+    /// Log file (default: `repo-syncer.log` next to the cache database)
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Portable mode: keep config and cache next to the binary
+    #[arg(long, conflicts_with = "data_dir")]
+    pub portable: bool,
+
+    /// Work from the cache without GitHub: syncs only fast-forward local
+    /// clones from their `upstream` remote
+    #[arg(long)]
+    pub offline: bool,
 
     /// Dry run - show what would be done without making changes
     #[arg(long)]
@@ -20,4 +60,124 @@ pub struct Args {
     /// Force refresh from GitHub (ignore cache)
     #[arg(long, short = 'r')]
     pub refresh: bool,
+
+    /// Also list non-fork repos under tool home that have an `upstream` remote
+    #[arg(long)]
+    pub scan_upstreams: bool,
+
+    /// Don't record local usage statistics (they never leave this machine)
+    #[arg(long)]
+    pub no_usage_stats: bool,
+
+    /// Don't force-sync diverged forks, even when their extra commits are
+    /// already contained upstream
+    #[arg(long)]
+    pub no_auto_force: bool,
+
+    /// Clone with an explicit SSH or HTTPS URL instead of letting `gh` choose
+    #[arg(long, value_enum)]
+    pub clone_protocol: Option<CloneProtocol>,
+
+    /// Clone URL template, e.g. `git@github-work:{owner}/{name}.git`
+    #[arg(long)]
+    pub clone_url_template: Option<String>,
+
+    /// Max concurrent syncs against one upstream owner (repeatable), e.g.
+    /// `--owner-limit kubernetes=2`
+    #[arg(long, value_name = "OWNER=N", value_parser = parse_owner_limit)]
+    pub owner_limit: Vec<(String, usize)>,
+
+    /// Update submodules (`git submodule update --init --recursive`) after
+    /// syncing a local clone
+    #[arg(long)]
+    pub submodules: bool,
+
+    /// Fetch upstream tags into each synced clone and push the ones the fork
+    /// is missing (`gh repo sync` only moves branches)
+    #[arg(long)]
+    pub mirror_tags: bool,
+
+    /// After updating a clone's default branch, rebase the local branches
+    /// that track it onto the new tip (conflicting rebases are aborted)
+    #[arg(long)]
+    pub rebase_branches: bool,
+
+    /// Skip clones with uncommitted changes instead of stashing them
+    #[arg(long)]
+    pub skip_dirty: bool,
+
+    /// Refresh from GitHub after this many minutes without a keypress, if the
+    /// fork list is at least that old (0 disables; default 30)
+    #[arg(long, value_name = "MINUTES")]
+    pub idle_refresh: Option<u64>,
+
+    /// Hours the cached fork list counts as fresh before it is refreshed in
+    /// the background at startup (0 always refreshes; default 24)
+    #[arg(long, value_name = "HOURS")]
+    pub cache_ttl: Option<u64>,
+
+    /// Refuse to start while another repo-syncer with the same data
+    /// directory is running (clones are always locked while they sync)
+    #[arg(long)]
+    pub single_instance: bool,
+
+    /// Move the most recently trashed clone of OWNER/NAME (see
+    /// `trash_deleted_clones`) back into its tool home, then exit
+    #[arg(long, value_name = "OWNER/NAME")]
+    pub restore: Option<String>,
+
+    /// Clone every uncloned, non-ignored fork without starting the TUI, then
+    /// exit (uses --jobs and owner limits)
+    #[arg(long)]
+    pub clone_all: bool,
+
+    /// Sync every cloned, non-ignored fork without the TUI, printing one line
+    /// per status change (for CI logs), then exit
+    #[arg(long, conflicts_with = "clone_all")]
+    pub no_tui: bool,
+
+    /// Emit every sync event in this format as it happens (to stdout, or
+    /// --events-file)
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub events: Option<EventFormat>,
+
+    /// Append events to this file instead of stdout (required with the TUI)
+    #[arg(long, value_name = "PATH", requires = "events")]
+    pub events_file: Option<PathBuf>,
+
+    /// After a sync run (TUI or --no-tui), write Prometheus metrics (forks
+    /// total/behind, sync outcomes, last sync times) to this file, e.g. for
+    /// `node_exporter`'s textfile collector
+    #[arg(long, value_name = "PATH")]
+    pub metrics_file: Option<PathBuf>,
+
+    /// Number of forks to sync concurrently
+    #[arg(long, short = 'j', default_value_t = 1)]
+    pub jobs: usize,
+
+    /// Which forks `status`, `--clone-all`, `--no-tui` and `--yes` act on
+    #[command(flatten)]
+    pub filter: ForkFilter,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Manage the fork cache database
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Print the forks (cloned, behind, dirty, last synced) without starting
+    /// the TUI or changing anything
+    Status,
+    /// Check that gh, git and the tool home are set up, and print fixes for
+    /// anything that isn't
+    Doctor,
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    /// Fetch the fork list from GitHub and remove cached forks that were
+    /// deleted or archived since
+    Prune,
 }
@@ -0,0 +1,478 @@
+use crate::cli::Args;
+use crate::github::host;
+use crate::launcher::{check_keys, Launcher};
+use crate::sync::SyncOptions;
+use crate::token_api;
+use crate::tools::Tools;
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+// ============================================================
+// USER CONFIGURATION
+// ============================================================
+
+/// Protocol used to build clone URLs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum CloneProtocol {
+    Ssh,
+    Https,
+}
+
+impl CloneProtocol {
+    /// The clone URL template for this protocol on `host` (e.g. `github.com`).
+    pub fn template(self, host: &str) -> String {
+        match self {
+            Self::Ssh => format!("git@{host}:{{owner}}/{{name}}.git"),
+            Self::Https => format!("https://{host}/{{owner}}/{{name}}.git"),
+        }
+    }
+}
+
+/// Where the fork cache is kept (`store` in the config).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreBackend {
+    /// `forks.db`, an `SQLite` database.
+    #[default]
+    Sqlite,
+    /// `forks.json`, one human-readable file.
+    Json,
+}
+
+/// Shell commands run in a local clone around its sync (`sh -c`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Hooks {
+    /// Run before touching the clone; a nonzero exit skips the fork.
+    pub pre_sync: Option<String>,
+    /// Run after a successful sync, e.g. `cargo fetch` or `npm install`.
+    pub post_sync: Option<String>,
+}
+
+impl Hooks {
+    /// These (global) hooks with the per-repo overrides for `id` applied.
+    pub fn resolve(&self, repos: &HashMap<String, RepoConfig>, id: &str) -> Hooks {
+        let repo = repos.get(id);
+        Hooks {
+            pre_sync: repo
+                .and_then(|r| r.pre_sync.clone())
+                .or_else(|| self.pre_sync.clone()),
+            post_sync: repo
+                .and_then(|r| r.post_sync.clone())
+                .or_else(|| self.post_sync.clone()),
+        }
+    }
+}
+
+/// How a clone's default branch catches up with the synced remote branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LocalUpdate {
+    /// Fast-forward only; a clone that can't be fast-forwarded fails.
+    FfOnly,
+    /// `git reset --hard` to the remote branch.
+    Reset,
+    /// Rebase local commits onto the remote branch (unpushed commits are
+    /// kept instead of skipping the fork).
+    Rebase,
+}
+
+/// When `gh repo sync --force` may overwrite a diverged fork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForceSync {
+    Never,
+    /// Only when the fork's extra commits are already upstream (lossless).
+    Auto,
+    Always,
+}
+
+/// Per-repo settings (`[repos."owner/name"]`): hook overrides and how the
+/// fork is synced.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RepoConfig {
+    pub pre_sync: Option<String>,
+    pub post_sync: Option<String>,
+    /// Unset fast-forwards, resetting when the fork was force-synced.
+    pub update: Option<LocalUpdate>,
+    /// Overrides `auto_force_sync`.
+    pub force: Option<ForceSync>,
+    /// Keep a bare `--mirror` clone (for repos only referenced) instead of a
+    /// working tree.
+    pub mirror: bool,
+}
+
+/// A named setup selected with `--profile` or `S` (`[profiles.<name>]`).
+/// Each profile gets its own cache database.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// Replaces the top-level `tool_homes` when set.
+    pub tool_homes: Vec<PathBuf>,
+    /// GitHub host for `gh` (`GH_HOST`), e.g. a GitHub Enterprise server.
+    pub host: Option<String>,
+    /// `gh` account to use on that host (see `gh auth status`).
+    pub account: Option<String>,
+    /// Forks (`owner/name`) ignored in this profile, on top of the ignore list.
+    pub ignore: Vec<String>,
+    /// Search filter applied at startup, as if typed with `/`.
+    pub filter: Option<String>,
+}
+
+/// Settings read from `config.toml` (see `Paths` for where it lives).
+/// Every key is optional; CLI flags take precedence.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)] // Independent on/off settings
+pub struct Config {
+    /// Directories clones live under (`~` is expanded); the first is where
+    /// new clones go. Defaults to `$HOME/dev/github.com`.
+    pub tool_homes: Vec<PathBuf>,
+    /// Clone with an explicit `git clone` URL instead of letting `gh` choose.
+    pub clone_protocol: Option<CloneProtocol>,
+    /// Clone URL template with `{owner}` and `{name}` placeholders.
+    /// Overrides `clone_protocol`.
+    pub clone_url_template: Option<String>,
+    /// Force-sync diverged forks whose extra commits are already upstream.
+    pub auto_force_sync: bool,
+    /// Max concurrent syncs per upstream owner, e.g. `kubernetes = 2`.
+    pub owner_limits: HashMap<String, usize>,
+    /// Update submodules after syncing a local clone.
+    pub update_submodules: bool,
+    /// Mirror upstream tags to the fork after syncing a local clone.
+    pub mirror_tags: bool,
+    /// Rebase local branches that track the default branch after syncing.
+    pub rebase_branches: bool,
+    /// Skip clones with uncommitted changes instead of stashing them.
+    pub skip_dirty: bool,
+    /// `git reset --hard` a clone's default branch to `origin` when it can't
+    /// be fast-forwarded (e.g. after a force-sync), instead of failing.
+    pub reset_fallback: bool,
+    /// Refresh from GitHub after this many idle minutes if the fork list is
+    /// at least as old (0 disables).
+    pub idle_refresh_minutes: u64,
+    /// Hours a full refresh stays fresh; an older cache is refreshed in the
+    /// background at startup (0 refreshes on every start).
+    pub cache_ttl_hours: u64,
+    /// Cache backend: `sqlite` (default) or `json`.
+    pub store: StoreBackend,
+    /// Move local clones of deleted forks to `.trash` under their tool home
+    /// instead of removing them.
+    pub trash_deleted_clones: bool,
+    /// Leave clones with uncommitted changes or unpushed commits out of
+    /// select-all (`a`).
+    pub select_all_skips_dirty: bool,
+    /// The git and gh executables and their default arguments (`[tools]`).
+    pub tools: Tools,
+    /// External tools opened on the highlighted fork, by key
+    /// (`[launchers.z]`).
+    pub launchers: BTreeMap<char, Launcher>,
+    /// Hooks for every repo (`[hooks]`).
+    pub hooks: Hooks,
+    /// Per-repo hook overrides and sync strategy, keyed by `owner/name`
+    /// (`[repos."owner/name"]`).
+    pub repos: HashMap<String, RepoConfig>,
+    /// Named profiles (`[profiles.work]`).
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            clone_protocol: None,
+            clone_url_template: None,
+            auto_force_sync: true,
+            tool_homes: Vec::new(),
+            owner_limits: HashMap::new(),
+            update_submodules: false,
+            mirror_tags: false,
+            rebase_branches: false,
+            skip_dirty: false,
+            reset_fallback: false,
+            idle_refresh_minutes: 30,
+            cache_ttl_hours: 24,
+            store: StoreBackend::default(),
+            trash_deleted_clones: true,
+            select_all_skips_dirty: false,
+            tools: Tools::default(),
+            launchers: BTreeMap::new(),
+            hooks: Hooks::default(),
+            repos: HashMap::new(),
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Load the config file at `path`, falling back to defaults if it doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: Self = toml::from_str(&contents)
+            .with_context(|| format!("Invalid config in {}", path.display()))?;
+        check_keys(&config.launchers)
+            .with_context(|| format!("Invalid config in {}", path.display()))?;
+        Ok(config)
+    }
+
+    /// The profile called `name`, or `None` without one.
+    pub fn profile(&self, name: Option<&str>) -> Result<Option<&Profile>> {
+        let Some(name) = name else {
+            return Ok(None);
+        };
+        match self.profiles.get(name) {
+            Some(profile) => Ok(Some(profile)),
+            None if self.profiles.is_empty() => {
+                bail!("Unknown profile {name:?}: config.toml has no [profiles]")
+            }
+            None => bail!(
+                "Unknown profile {name:?} (have: {})",
+                self.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+
+    /// Apply the settings of profile `name` that replace top-level ones.
+    pub fn with_profile(mut self, name: Option<&str>) -> Result<Self> {
+        if let Some(profile) = self.profile(name)? {
+            if !profile.tool_homes.is_empty() {
+                self.tool_homes = profile.tool_homes.clone();
+            }
+        }
+        Ok(self)
+    }
+
+    /// Apply CLI overrides. A CLI protocol replaces a configured template.
+    pub fn merge_args(mut self, args: &Args) -> Self {
+        if let Some(protocol) = args.clone_protocol {
+            self.clone_protocol = Some(protocol);
+            self.clone_url_template = None;
+        }
+        if let Some(template) = &args.clone_url_template {
+            self.clone_url_template = Some(template.clone());
+        }
+        if args.no_auto_force {
+            self.auto_force_sync = false;
+        }
+        if args.submodules {
+            self.update_submodules = true;
+        }
+        if args.mirror_tags {
+            self.mirror_tags = true;
+        }
+        if args.rebase_branches {
+            self.rebase_branches = true;
+        }
+        if args.skip_dirty {
+            self.skip_dirty = true;
+        }
+        if let Some(minutes) = args.idle_refresh {
+            self.idle_refresh_minutes = minutes;
+        }
+        if let Some(hours) = args.cache_ttl {
+            self.cache_ttl_hours = hours;
+        }
+        if args.offline {
+            self.idle_refresh_minutes = 0;
+        }
+        self.owner_limits.extend(args.owner_limit.iter().cloned());
+        self
+    }
+
+    /// Options for a sync (or clone) batch.
+    pub fn sync_options(&self, dry_run: bool) -> SyncOptions {
+        SyncOptions {
+            dry_run,
+            local_only: false,
+            auto_force: self.auto_force_sync,
+            clone_url_template: self.clone_url_template(),
+            owner_limits: self.owner_limits.clone(),
+            update_submodules: self.update_submodules,
+            mirror_tags: self.mirror_tags,
+            rebase_branches: self.rebase_branches,
+            skip_dirty: self.skip_dirty,
+            reset_fallback: self.reset_fallback,
+            hooks: self.hooks.clone(),
+            repos: self.repos.clone(),
+            behind: None,
+        }
+    }
+
+    /// The clone URL template to use, if any (`None` means `gh repo clone`).
+    /// Without `gh`, clones use plain `git clone` over HTTPS.
+    pub fn clone_url_template(&self) -> Option<String> {
+        self.clone_url_template
+            .clone()
+            .or_else(|| self.clone_protocol.map(|p| p.template(&host())))
+            .or_else(|| token_api::enabled().then(|| CloneProtocol::Https.template(&host())))
+    }
+}
+
+/// Parse an `OWNER=N` per-owner concurrency limit.
+pub fn parse_owner_limit(s: &str) -> Result<(String, usize), String> {
+    let (owner, limit) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected OWNER=N, got `{s}`"))?;
+    let limit: usize = limit
+        .parse()
+        .map_err(|_| format!("invalid limit `{limit}`"))?;
+    if owner.is_empty() || limit == 0 {
+        return Err(format!("expected OWNER=N with N >= 1, got `{s}`"));
+    }
+    Ok((owner.to_string(), limit))
+}
+
+/// Expand a clone URL template for `owner/name`.
+pub fn expand_clone_url(template: &str, owner: &str, name: &str) -> String {
+    template.replace("{owner}", owner).replace("{name}", name)
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_url_template() {
+        let config: Config = toml::from_str(r#"clone_protocol = "ssh""#).unwrap();
+        let template = config.clone_url_template().unwrap();
+        assert_eq!(
+            expand_clone_url(&template, "octocat", "hello"),
+            "git@github.com:octocat/hello.git"
+        );
+
+        let config: Config = toml::from_str(
+            r#"
+            clone_protocol = "ssh"
+            clone_url_template = "git@github-work:{owner}/{name}.git"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.clone_url_template().as_deref(),
+            Some("git@github-work:{owner}/{name}.git")
+        );
+        assert!(config.auto_force_sync);
+    }
+
+    #[test]
+    fn test_owner_limits() {
+        let config: Config = toml::from_str("[owner_limits]\nkubernetes = 2").unwrap();
+        assert_eq!(config.owner_limits.get("kubernetes"), Some(&2));
+
+        assert_eq!(
+            parse_owner_limit("rust-lang=1"),
+            Ok(("rust-lang".to_string(), 1))
+        );
+        assert!(parse_owner_limit("rust-lang").is_err());
+        assert!(parse_owner_limit("rust-lang=0").is_err());
+    }
+
+    #[test]
+    fn test_repo_hooks_override_global() {
+        let config: Config = toml::from_str(
+            r#"
+            [hooks]
+            post_sync = "cargo fetch"
+
+            [repos."me/web"]
+            pre_sync = "! pgrep -f 'webpack --watch'"
+            post_sync = "npm install"
+            "#,
+        )
+        .unwrap();
+        let web = config.hooks.resolve(&config.repos, "me/web");
+        assert_eq!(web.post_sync.as_deref(), Some("npm install"));
+        assert!(web.pre_sync.is_some());
+        let cli = config.hooks.resolve(&config.repos, "me/cli");
+        assert_eq!(cli.post_sync.as_deref(), Some("cargo fetch"));
+        assert_eq!(cli.pre_sync, None);
+    }
+
+    #[test]
+    fn test_repo_sync_strategy() {
+        let config: Config = toml::from_str(
+            r#"
+            auto_force_sync = false
+
+            [repos."me/dotfiles"]
+            update = "rebase"
+
+            [repos."me/mirror"]
+            update = "reset"
+            force = "always"
+            "#,
+        )
+        .unwrap();
+        let dotfiles = &config.repos["me/dotfiles"];
+        assert_eq!(dotfiles.update, Some(LocalUpdate::Rebase));
+        assert_eq!(dotfiles.force, None);
+        let mirror = &config.repos["me/mirror"];
+        assert_eq!(mirror.update, Some(LocalUpdate::Reset));
+        assert_eq!(mirror.force, Some(ForceSync::Always));
+
+        assert!(toml::from_str::<Config>("[repos.\"me/x\"]\nupdate = \"merge\"").is_err());
+    }
+
+    #[test]
+    fn test_profile_replaces_tool_homes() {
+        let config: Config = toml::from_str(
+            r#"
+            tool_homes = ["/home/me/dev"]
+
+            [profiles.work]
+            tool_homes = ["/home/me/work"]
+            host = "github.example.com"
+
+            [profiles.personal]
+            ignore = ["me/dotfiles"]
+            "#,
+        )
+        .unwrap();
+        let work = config.clone().with_profile(Some("work")).unwrap();
+        assert_eq!(work.tool_homes, vec![PathBuf::from("/home/me/work")]);
+        let personal = config.clone().with_profile(Some("personal")).unwrap();
+        assert_eq!(personal.tool_homes, vec![PathBuf::from("/home/me/dev")]);
+        assert!(config.with_profile(Some("oss")).is_err());
+    }
+
+    #[test]
+    fn test_launchers_by_key() {
+        let config: Config = toml::from_str(
+            r#"
+            [launchers.z]
+            name = "lazygit"
+            command = "lazygit -p {path}"
+
+            [launchers.e]
+            name = "VS Code"
+            command = "code {path}"
+            wait = false
+            "#,
+        )
+        .unwrap();
+        assert!(config.launchers[&'z'].wait);
+        assert!(!config.launchers[&'e'].wait);
+        assert!(check_keys(&config.launchers).is_ok());
+
+        let taken: Config = toml::from_str("[launchers.x]\nname = \"x\"\ncommand = \"x\"").unwrap();
+        assert!(check_keys(&taken.launchers).is_err());
+        for key in ["'1'", "\"'\"", "G"] {
+            let toml = format!("[launchers.{key}]\nname = \"x\"\ncommand = \"x\"");
+            let taken: Config = toml::from_str(&toml).unwrap();
+            assert!(check_keys(&taken.launchers).is_err(), "{key}");
+        }
+        assert!(toml::from_str::<Config>("[launchers.gg]\nname = \"x\"\ncommand = \"x\"").is_err());
+    }
+}
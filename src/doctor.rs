@@ -0,0 +1,299 @@
+use crate::github::host;
+use crate::logging::LoggedOutput;
+use crate::paths::ToolHomes;
+use crate::token_api;
+use crate::tools;
+use std::path::Path;
+
+/// Oldest git with everything syncing relies on (`status --porcelain=v2`,
+/// `stash push`).
+const MIN_GIT: (u32, u32) = (2, 13);
+
+/// Token scopes checked by `doctor`, whether each is required, and what it's
+/// for.
+const SCOPES: [(&str, bool, &str); 3] = [
+    ("repo", true, "sync and clone private forks"),
+    (
+        "workflow",
+        false,
+        "sync forks whose upstream changed workflows",
+    ),
+    ("delete_repo", false, "delete forks"),
+];
+
+/// How a check went.
+pub enum Outcome {
+    Ok(String),
+    /// Some features won't work.
+    Warn {
+        problem: String,
+        fix: String,
+    },
+    /// Syncing won't work.
+    Fail {
+        problem: String,
+        fix: String,
+    },
+}
+
+pub struct Check {
+    pub name: &'static str,
+    pub outcome: Outcome,
+}
+
+impl Check {
+    fn new(name: &'static str, outcome: Outcome) -> Self {
+        Self { name, outcome }
+    }
+}
+
+fn fail(problem: impl Into<String>, fix: impl Into<String>) -> Outcome {
+    Outcome::Fail {
+        problem: problem.into(),
+        fix: fix.into(),
+    }
+}
+
+/// Checks that need no network: run at every startup. `gh` is skipped when
+/// offline.
+pub fn quick_checks(tool_homes: &ToolHomes, offline: bool) -> Vec<Check> {
+    let mut checks = Vec::new();
+    if !offline {
+        checks.push(Check::new("gh", check_gh()));
+        if matches!(checks[0].outcome, Outcome::Ok(_)) {
+            checks.push(Check::new("gh login", check_login()));
+        }
+    }
+    checks.push(Check::new("git", check_git()));
+    checks.push(Check::new(
+        "tool home",
+        check_writable(tool_homes.primary()),
+    ));
+    checks
+}
+
+/// Everything `repo-syncer doctor` checks: the quick checks plus the token's
+/// scopes, which asks GitHub.
+pub fn all_checks(tool_homes: &ToolHomes) -> Vec<Check> {
+    let mut checks = quick_checks(tool_homes, false);
+    if checks
+        .iter()
+        .any(|c| c.name == "gh login" && matches!(c.outcome, Outcome::Ok(_)))
+    {
+        checks.push(Check::new("token scopes", check_scopes()));
+    }
+    checks
+}
+
+fn check_gh() -> Outcome {
+    match tools::gh().arg("--version").logged_output() {
+        Ok(output) if output.status.success() => {
+            Outcome::Ok(first_line(&output.stdout).to_string())
+        }
+        _ if token_api::enabled() => Outcome::Warn {
+            problem: "gh is not installed; using GH_TOKEN/GITHUB_TOKEN to list and sync forks"
+                .to_string(),
+            fix: "install the GitHub CLI for everything else: https://cli.github.com".to_string(),
+        },
+        _ => fail(
+            "gh is not installed",
+            "install the GitHub CLI: https://cli.github.com",
+        ),
+    }
+}
+
+/// `gh auth token` only reads the stored token, so it works offline.
+fn check_login() -> Outcome {
+    let host = host();
+    match tools::gh()
+        .args(["auth", "token", "-h", &host])
+        .logged_output()
+    {
+        Ok(output) if output.status.success() => Outcome::Ok(format!("logged in to {host}")),
+        _ => fail(
+            format!("not logged in to {host}"),
+            format!("gh auth login -h {host}"),
+        ),
+    }
+}
+
+fn check_git() -> Outcome {
+    let Ok(output) = tools::git().arg("--version").logged_output() else {
+        return fail("git is not installed", "install git: https://git-scm.com");
+    };
+    let line = first_line(&output.stdout).to_string();
+    match parse_git_version(&line) {
+        Some(version) if version >= MIN_GIT => Outcome::Ok(line),
+        Some(_) => fail(
+            format!("{line} is too old"),
+            format!("upgrade to git {}.{} or newer", MIN_GIT.0, MIN_GIT.1),
+        ),
+        None => Outcome::Warn {
+            problem: format!("couldn't parse `{line}`"),
+            fix: format!(
+                "make sure git {}.{} or newer is on PATH",
+                MIN_GIT.0, MIN_GIT.1
+            ),
+        },
+    }
+}
+
+/// Create (if needed) and write a scratch file in the primary tool home,
+/// where new clones go.
+fn check_writable(dir: &Path) -> Outcome {
+    let probe = dir.join(".repo-syncer-probe");
+    let result = std::fs::create_dir_all(dir).and_then(|()| std::fs::write(&probe, b""));
+    let _ = std::fs::remove_file(&probe);
+    match result {
+        Ok(()) => Outcome::Ok(format!("{} is writable", dir.display())),
+        Err(e) => fail(
+            format!("can't write to {}: {e}", dir.display()),
+            "fix its permissions, or point --tool-home / TOOL_HOME elsewhere",
+        ),
+    }
+}
+
+fn check_scopes() -> Outcome {
+    let host = host();
+    let output = match tools::gh()
+        .args(["auth", "status", "-h", &host])
+        .logged_output()
+    {
+        Ok(output) => output,
+        Err(e) => return fail(e.to_string(), "reinstall the GitHub CLI"),
+    };
+    // Older `gh` versions print the status to stderr
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    if !output.status.success() {
+        return fail(
+            format!("the token for {host} is invalid or expired"),
+            format!("gh auth login -h {host}"),
+        );
+    }
+    let Some(scopes) = parse_scopes(&text) else {
+        return Outcome::Ok("not listed (fine-grained or environment token)".to_string());
+    };
+
+    let missing: Vec<_> = SCOPES
+        .iter()
+        .filter(|(scope, _, _)| !scopes.iter().any(|s| s == scope))
+        .collect();
+    if missing.is_empty() {
+        return Outcome::Ok(scopes.join(", "));
+    }
+    let problem = missing
+        .iter()
+        .map(|(scope, _, needed_to)| format!("missing {scope} (needed to {needed_to})"))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let fix = format!(
+        "gh auth refresh -h {host} -s {}",
+        missing
+            .iter()
+            .map(|(scope, _, _)| *scope)
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    if missing.iter().any(|(_, required, _)| *required) {
+        fail(problem, fix)
+    } else {
+        Outcome::Warn { problem, fix }
+    }
+}
+
+fn first_line(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes)
+        .unwrap_or_default()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+}
+
+/// `(major, minor)` from e.g. `git version 2.39.3 (Apple Git-146)`.
+fn parse_git_version(line: &str) -> Option<(u32, u32)> {
+    let version = line.strip_prefix("git version ")?;
+    let mut parts = version.split(['.', ' ']);
+    Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+}
+
+/// The scopes on the `Token scopes: 'repo', 'gist'` line of `gh auth status`.
+fn parse_scopes(status: &str) -> Option<Vec<String>> {
+    let line = status.lines().find(|l| l.contains("Token scopes:"))?;
+    let (_, list) = line.split_once("Token scopes:")?;
+    Some(
+        list.split(',')
+            .map(|s| s.trim().trim_matches(['\'', '"']).to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+    )
+}
+
+/// Print each check as `repo-syncer doctor` does, with fixes under problems.
+/// Returns the number of failed checks.
+pub fn print_report(checks: &[Check]) -> usize {
+    let mut failed = 0;
+    for check in checks {
+        match &check.outcome {
+            Outcome::Ok(detail) => println!("  ✓ {}: {detail}", check.name),
+            Outcome::Warn { problem, fix } => {
+                println!("  ! {}: {problem}\n      fix: {fix}", check.name);
+            }
+            Outcome::Fail { problem, fix } => {
+                failed += 1;
+                println!("  ✗ {}: {problem}\n      fix: {fix}", check.name);
+            }
+        }
+    }
+    failed
+}
+
+/// Warn about failed startup checks on stderr, before the TUI takes over the
+/// screen. Warnings are left for `doctor`.
+pub fn warn_on_startup(checks: &[Check]) {
+    for check in checks {
+        if let Outcome::Fail { problem, fix } = &check.outcome {
+            eprintln!("Warning: {}: {problem} (fix: {fix})", check.name);
+        }
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_version() {
+        assert_eq!(parse_git_version("git version 2.39.5"), Some((2, 39)));
+        assert_eq!(
+            parse_git_version("git version 2.39.3 (Apple Git-146)"),
+            Some((2, 39))
+        );
+        assert_eq!(parse_git_version("git version 3.0"), Some((3, 0)));
+        assert_eq!(parse_git_version("something else"), None);
+    }
+
+    #[test]
+    fn test_parse_scopes() {
+        let status = "github.com\n  \
+            ✓ Logged in to github.com account octocat (keyring)\n  \
+            - Token scopes: 'gist', 'read:org', 'repo'\n";
+        assert_eq!(
+            parse_scopes(status),
+            Some(vec![
+                "gist".to_string(),
+                "read:org".to_string(),
+                "repo".to_string()
+            ])
+        );
+        assert_eq!(parse_scopes("github.com\n  ✓ Logged in\n"), None);
+    }
+}
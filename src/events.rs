@@ -0,0 +1,215 @@
+use crate::types::{SyncResult, SyncStatus};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Formats for `--events`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum EventFormat {
+    /// One JSON object per line
+    Jsonl,
+}
+
+/// Where every `SyncResult` is written as it arrives (`--events`), for
+/// dashboards and wrapper scripts following long runs.
+pub struct EventSink {
+    out: Box<dyn Write>,
+    to_stdout: bool,
+}
+
+impl EventSink {
+    /// Append to the file at `path`, or write to stdout without one.
+    pub fn open(path: Option<&Path>) -> Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self {
+                out: Box::new(io::stdout()),
+                to_stdout: true,
+            });
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open events file {}", path.display()))?;
+        Ok(Self {
+            out: Box::new(file),
+            to_stdout: false,
+        })
+    }
+
+    /// Whether events go to stdout (so progress text has to go elsewhere).
+    pub fn is_stdout(&self) -> bool {
+        self.to_stdout
+    }
+
+    /// Write `result` as one line.
+    pub fn emit(&mut self, result: &SyncResult) {
+        let mut event = to_json(result);
+        event["time"] = json!(Utc::now().to_rfc3339());
+        let _ = writeln!(self.out, "{event}");
+        let _ = self.out.flush();
+    }
+}
+
+/// `result` as a JSON object with an `event` name, the fork it's about (if
+/// any) and its details.
+fn to_json(result: &SyncResult) -> Value {
+    match result {
+        SyncResult::StatusUpdate(id, status) => {
+            let mut event = json!({
+                "event": "status",
+                "fork": id,
+                "status": status_name(status),
+            });
+            match status {
+                SyncStatus::Synced(Some(commits)) => event["commits"] = json!(commits),
+                SyncStatus::Skipped(detail)
+                | SyncStatus::Failed(detail)
+                | SyncStatus::NeedsAttention(detail) => event["detail"] = json!(detail),
+                _ => {}
+            }
+            event
+        }
+        SyncResult::ForkCloned(id) => json!({"event": "cloned", "fork": id}),
+        SyncResult::ForkArchived(id) => json!({"event": "archived", "fork": id}),
+        SyncResult::ForkDeleted(id) => json!({"event": "deleted", "fork": id}),
+        SyncResult::ForkCreated(created, clone_error) => json!({
+            "event": "fork_created",
+            "fork": created.full_name(),
+            "clone_error": clone_error,
+        }),
+        SyncResult::ForksRefreshed(fetched) => json!({
+            "event": "refreshed",
+            "forks": fetched.forks.len(),
+            "truncated": fetched.truncated,
+        }),
+        SyncResult::ForksUpdated(delta) => json!({
+            "event": "updated",
+            "updated": delta.updated.len(),
+            "archived": delta.archived.len(),
+        }),
+        SyncResult::RefreshFailed(error) => json!({"event": "refresh_failed", "error": error}),
+        SyncResult::ActionableError(details) => json!({
+            "event": "error",
+            "title": details.title,
+            "message": details.message,
+        }),
+        SyncResult::LocalScanned(findings) => {
+            json!({"event": "local_scanned", "findings": findings.len()})
+        }
+        SyncResult::AuthRequired => json!({"event": "auth_required"}),
+        SyncResult::Log(id, lines) => json!({"event": "log", "fork": id, "lines": lines}),
+        SyncResult::DiskUsage(id, bytes) => {
+            json!({"event": "disk_usage", "fork": id, "bytes": bytes})
+        }
+        SyncResult::Behind(counts) => json!({"event": "behind", "forks": counts.len()}),
+        SyncResult::ForceSynced(id, discarded) => json!({
+            "event": "force_synced",
+            "fork": id,
+            "discarded": discarded,
+        }),
+        SyncResult::PullRequestOpened(id, url) => {
+            json!({"event": "pull_request", "fork": id, "url": url})
+        }
+        SyncResult::VisibilityChanged(id, visibility) => json!({
+            "event": "visibility",
+            "fork": id,
+            "visibility": visibility,
+        }),
+        SyncResult::RisksChecked(id, result) => lookup("risks", id, result.as_ref().err()),
+        SyncResult::UpstreamCommits(id, result) => {
+            let mut event = lookup("upstream_commits", id, result.as_ref().err());
+            if let Ok(commits) = result {
+                event["total"] = json!(commits.total);
+            }
+            event
+        }
+        SyncResult::GitStatus(id, result) => lookup("git_status", id, result.as_ref().err()),
+        SyncResult::Preview(id, result) => {
+            let mut event = lookup("preview", id, result.as_ref().err());
+            if let Ok(incoming) = result {
+                event["commits"] = json!(incoming.commits.len());
+            }
+            event
+        }
+        SyncResult::Plan(plans) => json!({"event": "plan", "forks": plans.len()}),
+        SyncResult::Dirty(id, dirty) => json!({"event": "dirty", "fork": id, "dirty": dirty}),
+        SyncResult::LocalWork(id, busy) => {
+            json!({"event": "local_work", "fork": id, "local_work": busy})
+        }
+        SyncResult::Command(id, line) => {
+            json!({"event": "command", "fork": id, "command": line})
+        }
+        SyncResult::BackedUp(backup) => json!({
+            "event": "backed_up",
+            "fork": backup.id,
+            "branch": backup.branch,
+            "ref": backup.ref_name,
+            "oid": backup.oid,
+        }),
+        SyncResult::Stashed(stash) => json!({
+            "event": "stashed",
+            "fork": stash.id,
+            "path": stash.path,
+            "branch": stash.branch,
+        }),
+    }
+}
+
+/// A finished lookup for the details pane or a modal.
+fn lookup(event: &str, id: &str, error: Option<&String>) -> Value {
+    json!({"event": event, "fork": id, "error": error})
+}
+
+/// Stable, machine-friendly name of a status.
+fn status_name(status: &SyncStatus) -> &'static str {
+    match status {
+        SyncStatus::Pending => "pending",
+        SyncStatus::Checking => "checking",
+        SyncStatus::Cloning => "cloning",
+        SyncStatus::Stashing => "stashing",
+        SyncStatus::Fetching => "fetching",
+        SyncStatus::Syncing => "syncing",
+        SyncStatus::Restoring => "restoring",
+        SyncStatus::Rebasing => "rebasing",
+        SyncStatus::Submodules => "submodules",
+        SyncStatus::Tags => "tags",
+        SyncStatus::RunningHook => "running_hook",
+        SyncStatus::OpeningPr => "opening_pr",
+        SyncStatus::ChangingVisibility => "changing_visibility",
+        SyncStatus::Archiving => "archiving",
+        SyncStatus::Deleting => "deleting",
+        SyncStatus::Synced(_) => "synced",
+        SyncStatus::Skipped(_) => "skipped",
+        SyncStatus::Failed(_) => "failed",
+        SyncStatus::NeedsAttention(_) => "needs_attention",
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_event() {
+        let id = || "me/cargo".to_string();
+        assert_eq!(
+            to_json(&SyncResult::StatusUpdate(id(), SyncStatus::Synced(Some(3)))),
+            json!({"event": "status", "fork": "me/cargo", "status": "synced", "commits": 3})
+        );
+        assert_eq!(
+            to_json(&SyncResult::StatusUpdate(
+                id(),
+                SyncStatus::Failed("boom".to_string())
+            )),
+            json!({"event": "status", "fork": "me/cargo", "status": "failed", "detail": "boom"})
+        );
+    }
+}
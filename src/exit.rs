@@ -0,0 +1,142 @@
+//! Exit codes, so scripts and CI jobs can gate on a run:
+//!
+//! | Code | Meaning                                                  |
+//! |------|----------------------------------------------------------|
+//! | 0    | Every fork synced (or was skipped), or nothing to do     |
+//! | 1    | Some forks failed                                        |
+//! | 2    | GitHub couldn't be reached, or `gh` isn't logged in      |
+//! | 3    | No failures, but some forks need attention (conflicts)   |
+//! | 4    | Any other error (config, cache, terminal, failed checks) |
+//! | 130  | Stopped with Ctrl-C (or SIGTERM)                         |
+
+use crate::interrupt;
+use std::fmt;
+use std::process::ExitCode;
+
+const FAILED: u8 = 1;
+const GITHUB: u8 = 2;
+const ATTENTION: u8 = 3;
+const ERROR: u8 = 4;
+
+/// Context for errors talking to GitHub: fetching forks, or `gh` not being
+/// logged in (exit code 2).
+#[derive(Debug)]
+pub struct GitHubUnavailable;
+
+impl fmt::Display for GitHubUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Couldn't reach GitHub")
+    }
+}
+
+/// The batch was stopped with Ctrl-C before every fork ran (exit code 130).
+#[derive(Debug)]
+pub struct Interrupted;
+
+impl fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Interrupted; forks that didn't start can be resumed on the next launch")
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// A batch that finished with failed forks (exit code 1), or forks that need
+/// attention (exit code 3).
+#[derive(Debug)]
+pub struct Unfinished {
+    pub failed: usize,
+    pub attention: usize,
+    pub total: usize,
+    /// What the batch did, e.g. "sync"
+    pub action: &'static str,
+}
+
+impl Unfinished {
+    /// `Err` if any fork failed or needs attention.
+    pub fn check(self) -> anyhow::Result<()> {
+        if self.failed > 0 || self.attention > 0 {
+            Err(self.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for Unfinished {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            failed,
+            attention,
+            total,
+            action,
+        } = self;
+        if *failed > 0 {
+            write!(f, "{failed} of {total} forks failed to {action}")?;
+        }
+        if *attention > 0 {
+            let sep = if *failed > 0 { ", " } else { "" };
+            write!(f, "{sep}{attention} of {total} forks need attention")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Unfinished {}
+
+/// The exit code for an error that ended the run.
+pub fn code(err: &anyhow::Error) -> ExitCode {
+    ExitCode::from(if let Some(unfinished) = err.downcast_ref::<Unfinished>() {
+        if unfinished.failed > 0 {
+            FAILED
+        } else {
+            ATTENTION
+        }
+    } else if err.downcast_ref::<GitHubUnavailable>().is_some() {
+        GITHUB
+    } else if err.downcast_ref::<Interrupted>().is_some() {
+        interrupt::EXIT_CODE as u8
+    } else {
+        ERROR
+    })
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_exit_codes() {
+        let unfinished = |failed, attention| Unfinished {
+            failed,
+            attention,
+            total: 5,
+            action: "sync",
+        };
+        assert!(unfinished(0, 0).check().is_ok());
+
+        let err = unfinished(2, 1).check().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2 of 5 forks failed to sync, 1 of 5 forks need attention"
+        );
+        assert_eq!(code(&err), ExitCode::from(FAILED));
+        assert_eq!(
+            code(&unfinished(0, 1).check().unwrap_err()),
+            ExitCode::from(ATTENTION)
+        );
+
+        let fetch: anyhow::Result<()> = Err(anyhow::anyhow!("gh graphql failed"));
+        assert_eq!(
+            code(&fetch.context(GitHubUnavailable).unwrap_err()),
+            ExitCode::from(GITHUB)
+        );
+        assert_eq!(code(&Interrupted.into()), ExitCode::from(130));
+        assert_eq!(code(&anyhow::anyhow!("bad config")), ExitCode::from(ERROR));
+    }
+}
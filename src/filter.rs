@@ -0,0 +1,140 @@
+use crate::types::Fork;
+
+/// Which forks a command acts on. The same filter backs the headless flags
+/// (`--only`, `--exclude`, `--language`, `--cloned`/`--uncloned`) and the
+/// TUI's quick-filter tokens in the search query (see `parse_query`).
+#[derive(clap::Args, Debug, Clone, Default, PartialEq)]
+pub struct ForkFilter {
+    /// Only forks matching this glob (repeatable), against `owner/name` of
+    /// the fork or its upstream, e.g. `kubernetes/*`
+    #[arg(long, value_name = "GLOB")]
+    pub only: Vec<String>,
+
+    /// Leave out forks matching this glob (repeatable), e.g. `*archive*`
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Only forks whose primary language is this (case-insensitive)
+    #[arg(long, value_name = "LANGUAGE")]
+    pub language: Option<String>,
+
+    /// Only forks with a local clone
+    #[arg(long, conflicts_with = "uncloned")]
+    pub cloned: bool,
+
+    /// Only forks without a local clone
+    #[arg(long)]
+    pub uncloned: bool,
+}
+
+impl ForkFilter {
+    /// Split a search query into its quick-filter tokens (`repo:GLOB`,
+    /// `-repo:GLOB`, `lang:LANGUAGE`, `is:cloned`, `is:uncloned`) and the
+    /// remaining fuzzy search terms.
+    pub fn parse_query(query: &str) -> (Self, Vec<&str>) {
+        let mut filter = Self::default();
+        let mut terms = Vec::new();
+        for token in query.split_whitespace() {
+            if let Some(glob) = token.strip_prefix("repo:") {
+                filter.only.push(glob.to_string());
+            } else if let Some(glob) = token.strip_prefix("-repo:") {
+                filter.exclude.push(glob.to_string());
+            } else if let Some(language) = token.strip_prefix("lang:") {
+                filter.language = Some(language.to_string());
+            } else if token == "is:cloned" {
+                filter.cloned = true;
+            } else if token == "is:uncloned" {
+                filter.uncloned = true;
+            } else {
+                terms.push(token);
+            }
+        }
+        (filter, terms)
+    }
+
+    pub fn matches(&self, fork: &Fork) -> bool {
+        let names = [
+            fork.full_name(),
+            format!("{}/{}", fork.parent_owner, fork.parent_name),
+        ];
+        let matches_any = |globs: &[String]| {
+            globs
+                .iter()
+                .any(|glob| names.iter().any(|name| glob_match(glob, name)))
+        };
+        (self.only.is_empty() || matches_any(&self.only))
+            && !matches_any(&self.exclude)
+            && self.language.as_ref().is_none_or(|language| {
+                fork.primary_language
+                    .as_ref()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(language))
+            })
+            && (!self.cloned || fork.is_cloned)
+            && (!self.uncloned || !fork.is_cloned)
+    }
+}
+
+/// Case-insensitive glob match where `*` is any run of characters and `?`
+/// any one character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Where the last `*` was, and the text position it currently absorbs up to
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("kubernetes/*", "kubernetes/kubectl"));
+        assert!(glob_match("*archive*", "me/old-ARCHIVE-2019"));
+        assert!(glob_match("me/cargo?", "me/cargo2"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("kubernetes/*", "me/kubectl"));
+        assert!(!glob_match("me/cargo?", "me/cargo"));
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let (filter, terms) = ForkFilter::parse_query("lang:Rust sync is:cloned -repo:*old* cli");
+        assert_eq!(
+            filter,
+            ForkFilter {
+                exclude: vec!["*old*".to_string()],
+                language: Some("Rust".to_string()),
+                cloned: true,
+                ..ForkFilter::default()
+            }
+        );
+        assert_eq!(terms, vec!["sync", "cli"]);
+    }
+}
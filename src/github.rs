@@ -1,8 +1,11 @@
-use crate::types::Fork;
+use crate::logging::LoggedOutput;
+use crate::paths::ToolHomes;
+use crate::token_api;
+use crate::tools;
+use crate::types::{FetchedForks, Fork, ForkDelta, RepoKind};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
-use std::path::Path;
 use std::process::Command;
 
 // ============================================================
@@ -56,6 +59,7 @@ struct GraphQLFork {
     created_at: String,
     updated_at: String,
     is_archived: bool,
+    visibility: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -64,9 +68,22 @@ struct GraphQLOwner {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct GraphQLParent {
     name: String,
     owner: GraphQLOwner,
+    default_branch_ref: Option<GraphQLHeadRef>,
+    pushed_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLHeadRef {
+    target: Option<GraphQLTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLTarget {
+    oid: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -122,20 +139,24 @@ struct GhLanguage {
     name: String,
 }
 
-/// Fetch all forks from GitHub for the authenticated user.
-#[allow(dead_code)]
-pub fn fetch_forks(tool_home: &Path) -> Result<Vec<Fork>> {
-    let output = Command::new("gh")
+/// Most forks the fallback asks `gh repo list` for; it pages through them
+/// itself, 100 at a time.
+const FALLBACK_FORK_LIMIT: usize = 10_000;
+
+/// Fetch up to `limit` forks from GitHub for the authenticated user,
+/// flagged as truncated if there were that many.
+pub fn fetch_forks(tool_homes: &ToolHomes, limit: usize) -> Result<FetchedForks> {
+    let output = tools::gh()
         .args([
             "repo",
             "list",
             "--fork",
             "--limit",
-            "200",
+            &limit.to_string(),
             "--json",
             "name,owner,parent,defaultBranchRef,isArchived,description,primaryLanguage",
         ])
-        .output()
+        .logged_output()
         .context("Failed to run gh CLI. Is it installed and authenticated?")?;
 
     if !output.status.success() {
@@ -146,6 +167,11 @@ pub fn fetch_forks(tool_home: &Path) -> Result<Vec<Fork>> {
     }
 
     let gh_forks: Vec<GhFork> = serde_json::from_slice(&output.stdout)?;
+    tracing::info!("fallback fetched {} forks", gh_forks.len());
+    let truncated = gh_forks.len() >= limit;
+    if truncated {
+        tracing::warn!("fallback hit its {limit}-fork limit; the list may be incomplete");
+    }
 
     let forks: Vec<Fork> = gh_forks
         .into_iter()
@@ -156,8 +182,7 @@ pub fn fetch_forks(tool_home: &Path) -> Result<Vec<Fork>> {
                 .default_branch_ref
                 .map_or_else(|| "main".to_string(), |b| b.name);
 
-            let local_path = tool_home.join(&f.owner.login).join(&f.name);
-            let is_cloned = local_path.exists();
+            let (local_path, is_cloned) = tool_homes.locate(&f.owner.login, &f.name);
 
             Some(Fork {
                 name: f.name,
@@ -171,11 +196,38 @@ pub fn fetch_forks(tool_home: &Path) -> Result<Vec<Fork>> {
                 primary_language: f.primary_language.map(|l| l.name),
                 created_at: None, // REST API doesn't provide this efficiently
                 updated_at: None,
+                upstream_head: None,
+                upstream_pushed_at: None,
+                visibility: None,
+                kind: RepoKind::Fork,
             })
         })
         .collect();
 
-    Ok(forks)
+    Ok(FetchedForks { forks, truncated })
+}
+
+/// Whether a `gh` error means the CLI isn't logged in (or its token expired).
+pub fn is_auth_error(err: &str) -> bool {
+    let err = err.to_lowercase();
+    [
+        "not logged in",
+        "gh auth login",
+        "bad credentials",
+        "http 401",
+    ]
+    .iter()
+    .any(|needle| err.contains(needle))
+}
+
+/// Check whether `gh` is currently authenticated (or, without `gh`, a token
+/// is set).
+pub fn is_authenticated() -> bool {
+    token_api::enabled()
+        || tools::gh()
+            .args(["auth", "status"])
+            .logged_output()
+            .is_ok_and(|o| o.status.success())
 }
 
 /// Truncate an error message for display in the TUI.
@@ -188,30 +240,75 @@ pub fn truncate_error(err: &str) -> String {
     }
 }
 
+// ============================================================
+// WEB URLS
+// ============================================================
+
+/// The GitHub host `gh` talks to: `GH_HOST` (set by a profile's `host`), or
+/// `github.com`.
+pub fn host() -> String {
+    std::env::var("GH_HOST")
+        .ok()
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "github.com".to_string())
+}
+
+/// The fork's settings page. Leaving the fork network ("detaching") is only
+/// offered there, under Danger Zone; GitHub has no API for it.
+pub fn settings_url(fork: &Fork) -> String {
+    format!("https://{}/{}/{}/settings", host(), fork.owner, fork.name)
+}
+
+/// GitHub compare view of the upstream commits the fork's default branch is
+/// missing (`<fork>/compare/<branch>...<parent_owner>:<parent_name>:<branch>`).
+pub fn compare_url(fork: &Fork) -> String {
+    format!(
+        "https://{}/{}/{}/compare/{branch}...{}:{}:{branch}",
+        host(),
+        fork.owner,
+        fork.name,
+        fork.parent_owner,
+        fork.parent_name,
+        branch = fork.default_branch
+    )
+}
+
+/// Open a URL with the platform's default handler.
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener).arg(url).spawn().map(|_| ())
+}
+
 // ============================================================
 // GRAPHQL FETCHING
 // ============================================================
 
+/// Query for one page of the viewer's forks; `{order}` is the order field.
 const GRAPHQL_QUERY: &str = r"
 query($cursor: String) {
   viewer {
     repositories(
       first: 100
       isFork: true
-      orderBy: {field: CREATED_AT, direction: DESC}
+      orderBy: {field: {order}, direction: DESC}
       after: $cursor
     ) {
       pageInfo { hasNextPage endCursor }
       nodes {
         name
         owner { login }
-        parent { name owner { login } }
+        parent { name owner { login } defaultBranchRef { target { oid } } pushedAt }
         defaultBranchRef { name }
         description
         primaryLanguage { name }
         createdAt
         updatedAt
         isArchived
+        visibility
       }
     }
   }
@@ -219,27 +316,66 @@ query($cursor: String) {
 ";
 
 /// Fetch all forks using GraphQL API (sorted by creation date, newest first).
-pub fn fetch_forks_graphql(tool_home: &Path) -> Result<Vec<Fork>> {
+pub fn fetch_forks_graphql(tool_homes: &ToolHomes) -> Result<Vec<Fork>> {
     let mut all_forks = Vec::new();
+    fetch_fork_pages("CREATED_AT", |node| {
+        if !node.is_archived {
+            all_forks.extend(fork_from_node(node, tool_homes));
+        }
+        true
+    })?;
+    Ok(all_forks)
+}
+
+/// Fetch only forks updated since `since`, paging by last update and stopping
+/// at the first older one. Much faster than a full fetch for large accounts,
+/// but it can't see deleted forks or upstream-only changes.
+pub fn fetch_forks_since(tool_homes: &ToolHomes, since: DateTime<Utc>) -> Result<ForkDelta> {
+    let mut delta = ForkDelta::default();
+    fetch_fork_pages("UPDATED_AT", |node| {
+        if parse_time(&node.updated_at).is_some_and(|at| at < since) {
+            return false;
+        }
+        if node.is_archived {
+            delta
+                .archived
+                .push(format!("{}/{}", node.owner.login, node.name));
+        } else {
+            delta.updated.extend(fork_from_node(node, tool_homes));
+        }
+        true
+    })?;
+    Ok(delta)
+}
+
+/// Page through the viewer's forks ordered by `order` (newest first), passing
+/// each to `visit` until it returns false or the pages run out.
+fn fetch_fork_pages(order: &str, mut visit: impl FnMut(GraphQLFork) -> bool) -> Result<()> {
+    let query = GRAPHQL_QUERY.replace("{order}", order);
     let mut cursor: Option<String> = None;
 
     loop {
-        let mut args = vec![
-            "api".to_string(),
-            "graphql".to_string(),
-            "-f".to_string(),
-            format!("query={GRAPHQL_QUERY}"),
-        ];
-
-        if let Some(ref c) = cursor {
-            args.push("-f".to_string());
-            args.push(format!("cursor={c}"));
-        }
+        let output = if token_api::enabled() {
+            token_api::graphql(&query, &serde_json::json!({ "cursor": cursor }))
+                .context("Failed to run curl for GraphQL query")?
+        } else {
+            let mut args = vec![
+                "api".to_string(),
+                "graphql".to_string(),
+                "-f".to_string(),
+                format!("query={query}"),
+            ];
+
+            if let Some(ref c) = cursor {
+                args.push("-f".to_string());
+                args.push(format!("cursor={c}"));
+            }
 
-        let output = Command::new("gh")
-            .args(&args)
-            .output()
-            .context("Failed to run gh CLI for GraphQL query")?;
+            tools::gh()
+                .args(&args)
+                .logged_output()
+                .context("Failed to run gh CLI for GraphQL query")?
+        };
 
         if !output.status.success() {
             anyhow::bail!(
@@ -260,62 +396,71 @@ pub fn fetch_forks_graphql(tool_home: &Path) -> Result<Vec<Fork>> {
         let repos = data.viewer.repositories;
 
         for node in repos.nodes {
-            if node.is_archived {
-                continue;
+            if !visit(node) {
+                return Ok(());
             }
-
-            let Some(parent) = node.parent else {
-                continue;
-            };
-
-            let default_branch = node
-                .default_branch_ref
-                .map_or_else(|| "main".to_string(), |b| b.name);
-
-            let local_path = tool_home.join(&node.owner.login).join(&node.name);
-            let is_cloned = local_path.exists();
-
-            let created_at = DateTime::parse_from_rfc3339(&node.created_at)
-                .ok()
-                .map(|dt| dt.with_timezone(&Utc));
-
-            let updated_at = DateTime::parse_from_rfc3339(&node.updated_at)
-                .ok()
-                .map(|dt| dt.with_timezone(&Utc));
-
-            all_forks.push(Fork {
-                name: node.name,
-                owner: node.owner.login,
-                parent_owner: parent.owner.login,
-                parent_name: parent.name,
-                default_branch,
-                local_path,
-                is_cloned,
-                description: node.description,
-                primary_language: node.primary_language.map(|l| l.name),
-                created_at,
-                updated_at,
-            });
         }
 
         if repos.page_info.has_next_page {
             cursor = repos.page_info.end_cursor;
         } else {
-            break;
+            return Ok(());
         }
     }
+}
 
-    Ok(all_forks)
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Convert a GraphQL node to a `Fork` (`None` if the parent is gone).
+fn fork_from_node(node: GraphQLFork, tool_homes: &ToolHomes) -> Option<Fork> {
+    let parent = node.parent?;
+
+    let default_branch = node
+        .default_branch_ref
+        .map_or_else(|| "main".to_string(), |b| b.name);
+
+    let (local_path, is_cloned) = tool_homes.locate(&node.owner.login, &node.name);
+
+    let upstream_head = parent
+        .default_branch_ref
+        .and_then(|r| r.target)
+        .map(|t| t.oid);
+
+    Some(Fork {
+        name: node.name,
+        owner: node.owner.login,
+        parent_owner: parent.owner.login,
+        parent_name: parent.name,
+        default_branch,
+        local_path,
+        is_cloned,
+        description: node.description,
+        primary_language: node.primary_language.map(|l| l.name),
+        created_at: parse_time(&node.created_at),
+        updated_at: parse_time(&node.updated_at),
+        upstream_head,
+        upstream_pushed_at: parent.pushed_at.as_deref().and_then(parse_time),
+        visibility: node.visibility.map(|v| v.to_lowercase()),
+        kind: RepoKind::Fork,
+    })
 }
 
 /// Fetch forks, trying GraphQL first with REST fallback.
-#[allow(dead_code)]
-pub fn fetch_forks_with_fallback(tool_home: &Path) -> Result<Vec<Fork>> {
-    match fetch_forks_graphql(tool_home) {
-        Ok(forks) => Ok(forks),
+pub fn fetch_forks_with_fallback(tool_homes: &ToolHomes) -> Result<FetchedForks> {
+    match fetch_forks_graphql(tool_homes) {
+        Ok(forks) => Ok(FetchedForks {
+            forks,
+            truncated: false,
+        }),
+        // The fallback needs `gh`
+        Err(e) if token_api::enabled() => Err(e),
         Err(e) => {
-            eprintln!("GraphQL fetch failed, falling back to REST: {e}");
-            fetch_forks(tool_home)
+            tracing::warn!("GraphQL fetch failed, falling back to REST: {e}");
+            fetch_forks(tool_homes, FALLBACK_FORK_LIMIT)
         }
     }
 }
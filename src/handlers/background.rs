@@ -0,0 +1,223 @@
+use crate::app::App;
+use crate::cache::open_store;
+use crate::exit::GitHubUnavailable;
+use crate::github::{fetch_forks_since, fetch_forks_with_fallback};
+use crate::local::{dir_size, find_local_issues};
+use crate::paths::ToolHomes;
+use crate::sync::{
+    check_dirty_async, check_local_work_async, fetch_behind_async, fetch_upstream_commits_async,
+    git_status_async,
+};
+use crate::types::{CacheStatus, FetchedForks, Fork, ForkDelta, ForkStore, SyncResult};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::{sync::mpsc, thread};
+
+/// Start a background refresh from GitHub. An `incremental` one only fetches
+/// forks updated since the last full refresh (falling back to a full refresh
+/// if there hasn't been one).
+pub fn start_background_refresh(
+    tool_homes: ToolHomes,
+    cache: Option<Box<dyn ForkStore>>,
+    incremental: bool,
+    tx: mpsc::Sender<SyncResult>,
+) {
+    thread::spawn(move || {
+        let since = cache
+            .as_ref()
+            .filter(|_| incremental)
+            .and_then(|c| c.last_full_sync().ok().flatten());
+        if let Some(since) = since {
+            tracing::info!(%since, "incremental refresh");
+            let result = match fetch_forks_since(&tool_homes, since) {
+                Ok(delta) => {
+                    if let Some(cache) = &cache {
+                        save_delta(cache.as_ref(), &delta);
+                    }
+                    SyncResult::ForksUpdated(delta)
+                }
+                Err(e) => SyncResult::RefreshFailed(e.to_string()),
+            };
+            let _ = tx.send(result);
+            return;
+        }
+
+        tracing::info!("full refresh");
+        match fetch_forks_with_fallback(&tool_homes) {
+            Ok(fetched) => {
+                if let Some(cache) = &cache {
+                    save_full_refresh(cache.as_ref(), &fetched);
+                }
+                let _ = tx.send(SyncResult::ForksRefreshed(fetched));
+            }
+            Err(e) => {
+                let _ = tx.send(SyncResult::RefreshFailed(e.to_string()));
+            }
+        }
+    });
+}
+
+/// Cache the result of a full refresh: save the forks, drop the ones that are
+/// gone, and record the snapshot and refresh time. A truncated list is only
+/// saved: forks past its end aren't gone.
+pub fn save_full_refresh(cache: &dyn ForkStore, fetched: &FetchedForks) {
+    let forks = &fetched.forks;
+    if let Err(e) = cache.save_forks(forks) {
+        tracing::warn!("Failed to save to cache: {e}");
+    }
+    if fetched.truncated {
+        return;
+    }
+    if let Err(e) = cache.prune_forks(forks) {
+        tracing::warn!("Failed to prune the cache: {e}");
+    }
+    if let Err(e) = cache.record_snapshot(forks) {
+        tracing::warn!("Failed to record refresh snapshot: {e}");
+    }
+    if let Err(e) = cache.set_last_full_sync(Utc::now()) {
+        tracing::warn!("Failed to update last sync time: {e}");
+    }
+}
+
+fn save_delta(cache: &dyn ForkStore, delta: &ForkDelta) {
+    if let Err(e) = cache.save_forks(&delta.updated) {
+        tracing::warn!("Failed to save to cache: {e}");
+    }
+    for id in &delta.archived {
+        if let Err(e) = cache.remove_fork(id) {
+            tracing::warn!("Failed to remove {id} from cache: {e}");
+        }
+    }
+}
+
+/// Mark the fork list as refreshing and start a background refresh
+/// (see `start_background_refresh`).
+pub fn start_refresh(app: &mut App, incremental: bool, tx: &mpsc::Sender<SyncResult>) {
+    app.cache_status = CacheStatus::Stale { refreshing: true };
+    let cache = open_store(app.config.store, &app.db_file).ok();
+    start_background_refresh(app.tool_homes.clone(), cache, incremental, tx.clone());
+}
+
+/// Start the background lookups the UI is waiting for: the details pane,
+/// the Sync confirm modal and the select-all scan.
+pub fn start_lookups(app: &mut App, tx: &mpsc::Sender<SyncResult>) {
+    if let Some(fork) = app.wants_upstream_commits() {
+        fetch_upstream_commits_async(fork, tx.clone());
+    }
+    if let Some((id, path)) = app.wants_git_status() {
+        git_status_async(id, path, tx.clone());
+    }
+    if let Some(forks) = app.wants_dirty_check() {
+        check_dirty_async(forks, tx.clone());
+    }
+    if let Some(forks) = app.wants_local_work_scan() {
+        check_local_work_async(forks, tx.clone());
+    }
+}
+
+/// Measure the on-disk size of every cloned fork in the background.
+pub fn start_disk_usage_scan(forks: Vec<Fork>, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        for fork in forks.iter().filter(|f| f.is_cloned) {
+            let size = dir_size(&fork.local_path);
+            if tx
+                .send(SyncResult::DiskUsage(fork.full_name(), size))
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+}
+
+/// Look up how far behind upstream the forks are for the Behind column,
+/// unless GitHub is unreachable.
+pub fn start_behind_lookup(app: &App, tx: &mpsc::Sender<SyncResult>) {
+    if app.cache_status != CacheStatus::Offline {
+        fetch_behind_async(app.forks.clone(), tx.clone());
+    }
+}
+
+/// Scan tool home for orphaned/mismatched clones in the background.
+pub fn start_local_scan(tool_homes: ToolHomes, forks: Vec<Fork>, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        let findings = tool_homes
+            .roots()
+            .iter()
+            .flat_map(|root| find_local_issues(root, &forks))
+            .collect();
+        let _ = tx.send(SyncResult::LocalScanned(findings));
+    });
+}
+
+/// Load forks with cache support (only from the cache when `offline`).
+/// The cache is stale once its last full refresh is `ttl_hours` old.
+/// Returns (forks, `cache_status`) tuple.
+pub fn load_forks_with_cache(
+    cache: Option<&dyn ForkStore>,
+    tool_homes: &ToolHomes,
+    force_refresh: bool,
+    offline: bool,
+    ttl_hours: u64,
+) -> Result<(FetchedForks, CacheStatus)> {
+    let cached = |forks| FetchedForks {
+        forks,
+        truncated: false,
+    };
+    if offline {
+        let Some(cache) = cache.filter(|c| !c.is_empty().unwrap_or(true)) else {
+            anyhow::bail!("--offline needs a cached fork list; run once while online");
+        };
+        return Ok((cached(cache.load_forks(tool_homes)?), CacheStatus::Offline));
+    }
+
+    // If no cache available, fetch directly
+    let Some(cache) = cache else {
+        let fetched = fetch_forks_with_fallback(tool_homes).context(GitHubUnavailable)?;
+        return Ok((fetched, CacheStatus::Fresh));
+    };
+
+    // Check if we should use cache or refresh
+    let cache_empty = cache.is_empty().unwrap_or(true);
+
+    if force_refresh || cache_empty {
+        // Fetch fresh data from GitHub
+        match fetch_forks_with_fallback(tool_homes) {
+            Ok(fetched) => {
+                save_full_refresh(cache, &fetched);
+                Ok((fetched, CacheStatus::Fresh))
+            }
+            Err(e) => {
+                // If fetch failed but we have cache, use it
+                if cache_empty {
+                    Err(e.context(GitHubUnavailable))
+                } else {
+                    eprintln!("Warning: GitHub fetch failed, using cache: {e}");
+                    let forks = cache.load_forks(tool_homes)?;
+                    Ok((cached(forks), CacheStatus::Offline))
+                }
+            }
+        }
+    } else {
+        // Load from cache
+        let forks = cache.load_forks(tool_homes)?;
+
+        // Check if the last full refresh is older than the TTL
+        let is_stale = cache
+            .last_full_sync()
+            .ok()
+            .flatten()
+            .is_none_or(|last_sync| {
+                let age = Utc::now() - last_sync;
+                age.num_hours() >= i64::try_from(ttl_hours).unwrap_or(i64::MAX)
+            });
+
+        let cache_status = if is_stale {
+            CacheStatus::Stale { refreshing: false }
+        } else {
+            CacheStatus::Fresh
+        };
+
+        Ok((cached(forks), cache_status))
+    }
+}
@@ -0,0 +1,7 @@
+mod background;
+mod modals;
+mod selecting;
+
+pub use background::*;
+pub use modals::*;
+pub use selecting::*;
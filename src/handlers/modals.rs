@@ -0,0 +1,402 @@
+use super::background::start_local_scan;
+use crate::app::App;
+use crate::github::{open_url, settings_url};
+use crate::interrupt;
+use crate::sync::{
+    archive_forks_async, clone_fork_async, create_pr_async, delete_forks_async, force_sync_async,
+    plan_sync_async, set_visibility_async, start_cloning, start_syncing,
+};
+use crate::tools;
+use crate::types::{Fork, ModalAction, Mode, Retry, SyncResult, SyncStatus, Toast};
+use anyhow::Result;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::{
+    event::KeyCode,
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{io, sync::mpsc};
+
+pub fn handle_reconcile_overlay(app: &mut App, key: KeyCode, tx: &mpsc::Sender<SyncResult>) {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q' | 'L') => app.mode = Mode::Selecting,
+        KeyCode::Down | KeyCode::Char('j') => app.reconcile_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.reconcile_previous(),
+        KeyCode::Char('a') => app.adopt_finding(),
+        KeyCode::Char('m') => app.relocate_finding(),
+        KeyCode::Char('X') => app.remove_finding(),
+        KeyCode::Char('r') => {
+            app.open_reconcile();
+            start_local_scan(app.tool_homes.clone(), app.forks.clone(), tx.clone());
+        }
+        _ => {}
+    }
+}
+
+pub fn handle_reminders_overlay(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q' | 'A') => app.mode = Mode::Selecting,
+        KeyCode::Down | KeyCode::Char('j') => app.reminders_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.reminders_previous(),
+        KeyCode::Enter => app.jump_to_reminder(),
+        KeyCode::Char('x') => app.clear_selected_reminder(),
+        _ => {}
+    }
+}
+
+pub fn handle_stashes_overlay(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_stashes(),
+        KeyCode::Down | KeyCode::Char('j') => app.stashes_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.stashes_previous(),
+        KeyCode::Enter => app.restore_selected_stash(),
+        KeyCode::Char('x') => app.forget_selected_stash(),
+        _ => {}
+    }
+}
+
+pub fn handle_preview_overlay(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q' | 'f') => app.close_preview(),
+        KeyCode::Down | KeyCode::Char('j') => app.scroll_preview(true),
+        KeyCode::Up | KeyCode::Char('k') => app.scroll_preview(false),
+        KeyCode::Enter => app.sync_previewed(),
+        _ => {}
+    }
+}
+
+pub fn handle_audit_overlay(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q' | 'T') => app.close_audit(),
+        KeyCode::Down | KeyCode::Char('j') => app.audit_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.audit_previous(),
+        _ => {}
+    }
+}
+
+pub fn handle_plan_overlay(app: &mut App, key: KeyCode, tx: &mpsc::Sender<SyncResult>) {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q' | 'p') => app.close_plan(),
+        KeyCode::Down | KeyCode::Char('j') => app.scroll_plan(true),
+        KeyCode::Up | KeyCode::Char('k') => app.scroll_plan(false),
+        KeyCode::Enter | KeyCode::Char('y') => {
+            app.close_plan();
+            app.modal_button = 1;
+            execute_modal_action(app, tx);
+        }
+        _ => {}
+    }
+}
+
+pub fn handle_profiles_overlay(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q' | 'S') => app.mode = Mode::Selecting,
+        KeyCode::Down | KeyCode::Char('j') => app.profiles_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.profiles_previous(),
+        KeyCode::Enter => app.choose_profile(),
+        _ => {}
+    }
+}
+
+pub fn handle_error_popup(
+    app: &mut App,
+    key: KeyCode,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Result<()> {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q' | 'n') => dismiss_popup(app),
+        KeyCode::Left | KeyCode::Char('h') => {
+            app.modal_button = 0; // Action button
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            app.modal_button = 1; // Dismiss button
+        }
+        KeyCode::Tab => {
+            app.modal_button = 1 - app.modal_button;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            let lines = app
+                .error_details
+                .as_ref()
+                .map_or(0, |details| details.message.lines().count());
+            let last = u16::try_from(lines.saturating_sub(1)).unwrap_or(u16::MAX);
+            app.error_scroll = app.error_scroll.saturating_add(1).min(last);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.error_scroll = app.error_scroll.saturating_sub(1);
+        }
+        KeyCode::Enter | KeyCode::Char('y') => {
+            // Execute based on selected button
+            let action = app
+                .error_details
+                .as_ref()
+                .and_then(|details| details.action.clone());
+            match action {
+                Some(action) if app.modal_button == 0 => {
+                    app.dismiss_error_popup();
+                    if action.interactive {
+                        let succeeded = run_in_foreground(&action.gh_args)?;
+                        if app.auth_paused {
+                            app.finish_reauth();
+                        }
+                        match action.retry {
+                            Some(retry) if succeeded => retry_after_fix(app, retry, tx),
+                            Some(_) => app
+                                .show_message(&format!("`gh {}` failed", action.gh_args.join(" "))),
+                            None => {}
+                        }
+                    } else {
+                        std::thread::spawn(move || {
+                            let _ = tools::gh().args(&action.gh_args).status();
+                        });
+                        app.show_message("Running fix command...");
+                    }
+                }
+                _ => dismiss_popup(app),
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn dismiss_popup(app: &mut App) {
+    if app.auth_paused {
+        app.skip_after_auth();
+    }
+    app.dismiss_error_popup();
+}
+
+/// Run `gh` with the TUI suspended (for interactive commands). Returns
+/// whether it succeeded.
+fn run_in_foreground(gh_args: &[String]) -> Result<bool> {
+    let status = suspended(|| tools::gh().args(gh_args).status())?;
+    Ok(status.is_ok_and(|s| s.success()))
+}
+
+/// Leave the TUI for the terminal while `run` runs (an editor, a shell),
+/// then restore it.
+pub(super) fn suspended<T>(run: impl FnOnce() -> T) -> Result<T> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    let result = interrupt::ignored_while(run);
+
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    Ok(result)
+}
+
+/// Run the operation an error popup's command just fixed again.
+fn retry_after_fix(app: &mut App, retry: Retry, tx: &mpsc::Sender<SyncResult>) {
+    match retry {
+        Retry::Delete(names) => {
+            let targets: Vec<(usize, Fork)> = names
+                .iter()
+                .filter_map(|name| app.forks.iter().position(|f| f.full_name() == *name))
+                .map(|idx| (idx, app.forks[idx].clone()))
+                .collect();
+            for (idx, _) in &targets {
+                app.statuses[*idx] = SyncStatus::Pending;
+            }
+            app.show_message("Scope granted, retrying delete");
+            delete_targets(app, targets, tx);
+        }
+    }
+}
+
+/// Delete `targets` in the background, trashing their clones if configured.
+fn delete_targets(app: &App, targets: Vec<(usize, Fork)>, tx: &mpsc::Sender<SyncResult>) {
+    let trash_homes = app
+        .config
+        .trash_deleted_clones
+        .then(|| app.tool_homes.clone());
+    let forks = targets.into_iter().map(|(_, fork)| fork).collect();
+    delete_forks_async(forks, app.dry_run, trash_homes, tx.clone());
+}
+
+pub fn handle_confirm_modal(app: &mut App, key: KeyCode, tx: &mpsc::Sender<SyncResult>) {
+    match key {
+        KeyCode::Left | KeyCode::Char('h') => {
+            app.modal_button = 0;
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            app.modal_button = 1;
+        }
+        KeyCode::Tab => {
+            app.modal_button = 1 - app.modal_button;
+        }
+        KeyCode::Down | KeyCode::Char('j') => app.scroll_confirm_list(true),
+        KeyCode::Up | KeyCode::Char('k') => app.scroll_confirm_list(false),
+        KeyCode::Enter => {
+            if app.modal_button == 1 {
+                execute_modal_action(app, tx);
+            } else {
+                cancel_modal(app);
+            }
+        }
+        KeyCode::Char('y') => {
+            app.modal_button = 1;
+            execute_modal_action(app, tx);
+        }
+        KeyCode::Char('n') | KeyCode::Esc => cancel_modal(app),
+        KeyCode::Char('p')
+            if matches!(app.modal_action, ModalAction::Sync | ModalAction::Resume) =>
+        {
+            let forks = app.open_plan();
+            plan_sync_async(forks, app.sync_options(), tx.clone());
+        }
+        KeyCode::Char(c @ '1'..='9') if app.modal_action == ModalAction::Clone => {
+            let root = c as usize - '1' as usize;
+            if root < app.tool_homes.roots().len() {
+                app.clone_root = root;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn cancel_modal(app: &mut App) {
+    app.clear_risks();
+    if app.modal_action == ModalAction::Resume {
+        app.discard_resume();
+    }
+    app.mode = Mode::Selecting;
+}
+
+/// Start syncing the selected forks, persisting the batch for resume.
+pub fn start_batch(app: &mut App, tx: &mpsc::Sender<SyncResult>) {
+    app.mark_selected_as_pending();
+    app.mode = Mode::Syncing;
+    let forks_to_sync = app.forks_to_sync();
+    app.persist_batch(&forks_to_sync);
+    app.queue = Some(start_syncing(
+        forks_to_sync,
+        &app.sync_options(),
+        app.jobs,
+        tx,
+    ));
+}
+
+/// Take the confirmed Archive/Delete targets. A single fork runs alongside
+/// browsing; several run as a batch with progress, like syncing.
+fn start_risky_batch(app: &mut App) -> Vec<(usize, Fork)> {
+    let targets = app.take_risk_targets();
+    if targets.len() > 1 {
+        app.selected.fill(false);
+        for (idx, _) in &targets {
+            app.selected[*idx] = true;
+        }
+        app.mark_selected_as_pending();
+        app.mode = Mode::Syncing;
+    } else {
+        app.mode = Mode::Selecting;
+    }
+    targets
+}
+
+pub fn execute_modal_action(app: &mut App, tx: &mpsc::Sender<SyncResult>) {
+    if matches!(app.modal_action, ModalAction::Archive | ModalAction::Delete)
+        && !app.confirm_risky_action()
+    {
+        return;
+    }
+    let action = match app.modal_action {
+        ModalAction::Sync => "sync",
+        ModalAction::Resume => "resume",
+        ModalAction::Clone => "clone",
+        ModalAction::CloneAll => "clone all",
+        ModalAction::Archive => "archive",
+        ModalAction::Delete => "delete",
+        ModalAction::PullRequest => "pull request",
+        ModalAction::Visibility => "visibility",
+        ModalAction::Detach => "detach",
+        ModalAction::ForceSync => "force sync",
+        ModalAction::Undo => "undo",
+    };
+    app.track_action(action);
+    match app.modal_action {
+        ModalAction::Sync => start_batch(app, tx),
+        ModalAction::Resume => {
+            app.select_resume();
+            start_batch(app, tx);
+        }
+        ModalAction::Clone => {
+            if let Some(idx) = app.current_fork_index() {
+                let root = &app.tool_homes.roots()[app.clone_root];
+                let fork = &mut app.forks[idx];
+                fork.local_path = root.join(&fork.owner).join(&fork.name);
+                let fork = fork.clone();
+                app.statuses[idx] = SyncStatus::Cloning;
+                app.selected[idx] = true;
+                clone_fork_async(fork, app.sync_options(), tx.clone());
+            }
+            app.mode = Mode::Selecting;
+        }
+        ModalAction::CloneAll => {
+            let targets = app.uncloned_visible();
+            app.selected.fill(false);
+            for &idx in &targets {
+                app.selected[idx] = true;
+            }
+            app.mark_selected_as_pending();
+            app.mode = Mode::Syncing;
+            app.queue = Some(start_cloning(
+                app.forks_to_sync(),
+                &app.sync_options(),
+                app.jobs,
+                tx,
+            ));
+        }
+        ModalAction::Archive => {
+            let targets = start_risky_batch(app);
+            let forks = targets.into_iter().map(|(_, fork)| fork).collect();
+            archive_forks_async(forks, app.dry_run, tx.clone());
+        }
+        ModalAction::Delete => {
+            let targets = start_risky_batch(app);
+            delete_targets(app, targets, tx);
+        }
+        ModalAction::Visibility => {
+            if let Some(idx) = app.current_fork_index() {
+                let fork = app.forks[idx].clone();
+                if let Some(visibility) = fork.toggled_visibility() {
+                    app.statuses[idx] = SyncStatus::ChangingVisibility;
+                    set_visibility_async(fork, visibility, app.dry_run, tx.clone());
+                }
+            }
+            app.mode = Mode::Selecting;
+        }
+        ModalAction::Detach => {
+            if let Some(fork) = app.current_fork() {
+                match open_url(&settings_url(fork)) {
+                    Ok(()) => app.add_toast(Toast::info(
+                        "Use \"Leave fork network\" under Danger Zone, then refresh (R)",
+                    )),
+                    Err(e) => app.show_message(&format!("Couldn't open browser: {e}")),
+                }
+            }
+            app.mode = Mode::Selecting;
+        }
+        ModalAction::ForceSync => {
+            if let Some(idx) = app.current_fork_index() {
+                let fork = app.forks[idx].clone();
+                app.statuses[idx] = SyncStatus::Syncing;
+                force_sync_async(fork, app.dry_run, app.config.skip_dirty, tx.clone());
+            }
+            app.mode = Mode::Selecting;
+        }
+        ModalAction::Undo => {
+            app.undo_last_sync();
+            app.mode = Mode::Selecting;
+        }
+        ModalAction::PullRequest => {
+            if let Some(idx) = app.current_fork_index() {
+                let fork = app.forks[idx].clone();
+                app.statuses[idx] = SyncStatus::OpeningPr;
+                create_pr_async(fork, app.dry_run, tx.clone());
+            }
+            app.mode = Mode::Selecting;
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use super::background::{start_local_scan, start_refresh};
+use super::modals::suspended;
+use crate::app::App;
+use crate::github::{compare_url, open_url};
+use crate::launcher::bindable;
+use crate::sync::{
+    check_risks_async, fork_upstream_async, preview_incoming_async, SLOW_MODE_DELAY,
+};
+use crate::tools;
+use crate::types::{InputPurpose, ModalAction, Mode, SortColumn, SyncResult};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use std::{env, process::Stdio, sync::mpsc};
+
+/// Mouse input: a left click on a list header sorts by that column.
+pub fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    if app.mode == Mode::Selecting && mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+        app.click_header(mouse.column, mouse.row);
+    }
+}
+
+/// Paging keys shared by the selecting and syncing modes: `PageUp`/`PageDown`,
+/// `Ctrl-u`/`Ctrl-d` (half a page), `gg` (top) and `G` (bottom). Returns
+/// whether the key was used.
+pub fn handle_paging(app: &mut App, key: KeyEvent) -> bool {
+    if app.details.focused {
+        return false;
+    }
+    let pending_g = std::mem::take(&mut app.pending_g);
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::PageDown => app.page_down(),
+        KeyCode::PageUp => app.page_up(),
+        KeyCode::Char('d') if ctrl => app.half_page_down(),
+        KeyCode::Char('u') if ctrl => app.half_page_up(),
+        KeyCode::Char('g') if pending_g => app.jump_to_first(),
+        KeyCode::Char('g') => app.pending_g = true,
+        KeyCode::Char('G') | KeyCode::End => app.jump_to_last(),
+        KeyCode::Home => app.jump_to_first(),
+        _ => return false,
+    }
+    true
+}
+
+pub fn handle_selecting_mode(
+    app: &mut App,
+    key: KeyCode,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Result<Option<Result<()>>> {
+    if app.details.focused {
+        match key {
+            KeyCode::Down | KeyCode::Char('j') => app.scroll_details(true),
+            KeyCode::Up | KeyCode::Char('k') => app.scroll_details(false),
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Esc => app.unfocus_details(),
+            KeyCode::Char('I') => app.toggle_details(),
+            _ => {}
+        }
+        return Ok(None);
+    }
+    match key {
+        KeyCode::Char(c) if bindable(c) && app.config.launchers.contains_key(&c) => {
+            launch(app, c)?;
+        }
+        KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(Ok(()))),
+        KeyCode::Down | KeyCode::Char('j') => app.next(),
+        KeyCode::Up | KeyCode::Char('k') => app.previous(),
+        KeyCode::Char(' ') | KeyCode::Tab => app.toggle_selection(),
+        KeyCode::Char('a') => {
+            app.track_action("select all");
+            app.select_all();
+        }
+        KeyCode::Enter => {
+            if app.selected_count() > 0 {
+                app.modal_action = ModalAction::Sync;
+                app.mode = Mode::ConfirmModal;
+            } else if let Some(idx) = app.current_fork_index() {
+                // Nothing selected - sync current fork (works for both cloned and uncloned)
+                app.selected[idx] = true;
+                app.modal_action = ModalAction::Sync;
+                app.mode = Mode::ConfirmModal;
+            }
+        }
+        KeyCode::Char('/') => {
+            app.track_action("search");
+            app.search_query.clear();
+            app.mode = Mode::Search;
+        }
+        KeyCode::Char('d') => {
+            app.track_action("stats");
+            app.compute_stats();
+            app.mode = Mode::StatsOverlay;
+        }
+        KeyCode::Char('c') => {
+            if let Some(fork) = app.current_fork() {
+                if !fork.is_github_fork() {
+                    app.show_message("Not a GitHub fork");
+                } else if fork.is_cloned {
+                    app.show_message("Already cloned");
+                } else {
+                    app.clone_root = 0;
+                    app.modal_action = ModalAction::Clone;
+                    app.mode = Mode::ConfirmModal;
+                }
+            }
+        }
+        KeyCode::Char('\'') => {
+            app.open_prompt(InputPurpose::GoTo, "Go to (Tab: next match)", String::new());
+        }
+        KeyCode::Char('n') => app.open_prompt(
+            InputPurpose::NewFork,
+            "Fork and clone (owner/repo or URL)",
+            String::new(),
+        ),
+        KeyCode::Char('B') => {
+            if app.uncloned_visible().is_empty() {
+                app.show_message("No uncloned forks in the list");
+            } else {
+                app.modal_action = ModalAction::CloneAll;
+                app.mode = Mode::ConfirmModal;
+            }
+        }
+        KeyCode::Char('o') => {
+            if let Some(fork) = app.current_fork() {
+                if !fork.is_github_fork() {
+                    app.show_message("Not a GitHub fork");
+                    return Ok(None);
+                }
+                let repo = format!("{}/{}", fork.owner, fork.name);
+                let _ = tools::gh().args(["browse", "--repo", &repo]).spawn();
+                app.track_action("open in browser");
+                app.show_message("Opening in browser...");
+            }
+        }
+        KeyCode::Char('O') => {
+            if let Some(fork) = app.current_fork() {
+                let repo = format!("{}/{}", fork.parent_owner, fork.parent_name);
+                let _ = tools::gh().args(["browse", "--repo", &repo]).spawn();
+                app.track_action("open upstream");
+                app.show_message("Opening upstream in browser...");
+            }
+        }
+        KeyCode::Char('C') => {
+            if let Some(fork) = app.current_fork() {
+                if !fork.is_github_fork() {
+                    app.show_message("Not a GitHub fork");
+                    return Ok(None);
+                }
+                let message = match open_url(&compare_url(fork)) {
+                    Ok(()) => "Opening compare view...".to_string(),
+                    Err(e) => format!("Couldn't open browser: {e}"),
+                };
+                app.track_action("compare");
+                app.show_message(&message);
+            }
+        }
+        KeyCode::Char('e') => {
+            if let Some(fork) = app.current_fork() {
+                if fork.is_cloned {
+                    let path = fork.local_path.clone();
+                    app.track_action("open in editor");
+                    let editor = env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
+                    let _ = suspended(|| std::process::Command::new(&editor).arg(&path).status())?;
+                } else {
+                    app.show_message("Not cloned yet");
+                }
+            }
+        }
+        KeyCode::Char('s') => {
+            if let Some(fork) = app.current_fork() {
+                if fork.is_cloned {
+                    let path = fork.local_path.clone();
+                    let repo = fork.full_name();
+                    app.track_action("open shell");
+                    let shell = env::var("SHELL").unwrap_or_else(|_| "sh".to_string());
+                    let status = suspended(|| {
+                        println!("{repo} at {}. Exit the shell to return.", path.display());
+                        std::process::Command::new(&shell)
+                            .current_dir(&path)
+                            .status()
+                    })?;
+                    if let Err(e) = status {
+                        app.show_message(&format!("Couldn't start {shell}: {e}"));
+                    }
+                } else {
+                    app.show_message("Not cloned yet");
+                }
+            }
+        }
+        KeyCode::Char('P' | 'V' | 'X' | 'F')
+            if app.current_fork().is_some_and(|f| !f.is_github_fork()) =>
+        {
+            app.show_message("Not a GitHub fork");
+        }
+        KeyCode::Char(c @ ('x' | 'D')) if app.current_fork().is_some() => {
+            let targets = app.risky_action_targets();
+            if targets.is_empty() {
+                app.show_message("Not a GitHub fork");
+            } else {
+                for &i in &targets {
+                    check_risks_async(app.forks[i].clone(), tx.clone());
+                }
+                let action = if c == 'x' {
+                    ModalAction::Archive
+                } else {
+                    ModalAction::Delete
+                };
+                app.begin_risky_action(action, targets);
+            }
+        }
+        KeyCode::Char('V') => {
+            if let Some(fork) = app.current_fork() {
+                if fork.toggled_visibility().is_some() {
+                    app.modal_action = ModalAction::Visibility;
+                    app.mode = Mode::ConfirmModal;
+                } else {
+                    app.show_message("Visibility unknown - refresh (R) first");
+                }
+            }
+        }
+        KeyCode::Char('X') if app.current_fork().is_some() => {
+            app.modal_action = ModalAction::Detach;
+            app.mode = Mode::ConfirmModal;
+        }
+        KeyCode::Char('F') => {
+            if app
+                .current_fork_index()
+                .is_some_and(|i| app.statuses[i].is_diverged())
+            {
+                app.modal_action = ModalAction::ForceSync;
+                app.mode = Mode::ConfirmModal;
+            } else {
+                app.show_message("Only forks that diverged from upstream can be force-synced");
+            }
+        }
+        KeyCode::Char('P') if app.current_fork().is_some() => {
+            app.modal_action = ModalAction::PullRequest;
+            app.mode = Mode::ConfirmModal;
+        }
+        KeyCode::Char('u') => app.prompt_undo(),
+        KeyCode::Char('i') => {
+            app.track_action("ignore");
+            app.toggle_ignore_current();
+        }
+        KeyCode::Char('H') => app.toggle_show_hidden(),
+        KeyCode::Char('L') => {
+            app.track_action("reconcile");
+            app.open_reconcile();
+            start_local_scan(app.tool_homes.clone(), app.forks.clone(), tx.clone());
+        }
+        KeyCode::Char('r') => {
+            app.track_action("reminder");
+            app.prompt_reminder();
+        }
+        KeyCode::Char('p') => {
+            if app.current_fork().is_some_and(|f| !f.is_github_fork()) {
+                app.show_message("Not a GitHub fork");
+            } else {
+                app.track_action("local path");
+                app.prompt_local_path();
+            }
+        }
+        KeyCode::Char('A') => app.open_reminders(),
+        KeyCode::Char('S') => {
+            app.track_action("profiles");
+            app.open_profiles();
+        }
+        KeyCode::Char('v') => app.open_log(),
+        KeyCode::Char('f') => {
+            if let Some(fork) = app.open_preview() {
+                app.track_action("preview");
+                let template = app.sync_options().clone_url_template;
+                preview_incoming_async(fork, template, tx.clone());
+            }
+        }
+        KeyCode::Char('E') => app.show_full_error(),
+        KeyCode::Char('T') => {
+            app.track_action("audit");
+            app.open_audit();
+        }
+        KeyCode::Char('W') => {
+            app.track_action("changes");
+            app.compute_changes();
+            app.mode = Mode::ChangesOverlay;
+        }
+        KeyCode::Char('U') => {
+            app.compute_usage_stats();
+            app.mode = Mode::UsageOverlay;
+        }
+        KeyCode::Char('R') => {
+            app.track_action("refresh");
+            app.show_message("Refreshing from GitHub...");
+            start_refresh(app, true, tx);
+        }
+        KeyCode::Char('I') => app.toggle_details(),
+        KeyCode::Right | KeyCode::Char('l') => app.focus_details(),
+        KeyCode::Char(c @ '1'..='6') => {
+            app.track_action("sort");
+            let column = (c as usize) - ('1' as usize);
+            app.cycle_sort(SortColumn::ALL[column]);
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+/// Run the launcher on `key` for the highlighted fork: in the foreground with
+/// the TUI suspended, or spawned in the background.
+fn launch(app: &mut App, key: char) -> Result<()> {
+    let (Some(fork), Some(launcher)) = (app.current_fork(), app.config.launchers.get(&key)) else {
+        return Ok(());
+    };
+    if launcher.needs_clone() && !fork.is_cloned {
+        app.show_message("Not cloned yet");
+        return Ok(());
+    }
+    let mut command = std::process::Command::new("sh");
+    command.arg("-c").arg(launcher.command_line(fork));
+    if fork.is_cloned {
+        command.current_dir(&fork.local_path);
+    }
+    let name = launcher.name.clone();
+    let result = if launcher.wait {
+        suspended(|| command.status().map(|_| ()))?
+    } else {
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map(|_| ())
+    };
+    app.track_action(&format!("launch {name}"));
+    match result {
+        Ok(()) => app.show_message(&format!("Opened {name}")),
+        Err(e) => app.show_message(&format!("Couldn't run {name}: {e}")),
+    }
+    Ok(())
+}
+
+pub fn handle_search_mode(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Esc => {
+            app.search_query.clear();
+            app.update_search();
+            app.mode = Mode::Selecting;
+        }
+        KeyCode::Enter => {
+            app.mode = Mode::Selecting;
+        }
+        KeyCode::Backspace => {
+            app.search_query.pop();
+            app.update_search();
+        }
+        KeyCode::Char(c) => {
+            app.search_query.push(c);
+            app.update_search();
+        }
+        KeyCode::Down => app.next(),
+        KeyCode::Up => app.previous(),
+        _ => {}
+    }
+}
+
+/// Handle keys while a text prompt is open.
+pub fn handle_input_mode(app: &mut App, key: KeyCode, tx: &mpsc::Sender<SyncResult>) {
+    match key {
+        KeyCode::Esc => app.cancel_input(),
+        KeyCode::Enter => {
+            let Some((owner, name)) = app.submit_input() else {
+                return;
+            };
+            if app.dry_run {
+                app.show_message(&format!("Dry run: would fork and clone {owner}/{name}"));
+            } else {
+                app.show_message(&format!("Forking {owner}/{name}..."));
+                fork_upstream_async(
+                    owner,
+                    name,
+                    app.tool_homes.clone(),
+                    app.sync_options(),
+                    tx.clone(),
+                );
+            }
+        }
+        KeyCode::Backspace => {
+            if let Some(input) = &mut app.input {
+                input.buffer.pop();
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Some(input) = &mut app.input {
+                input.buffer.push(c);
+            }
+        }
+        _ => {}
+    }
+
+    // Go-to follows every keystroke; Tab moves on to the next match
+    if let Some(input) = app
+        .input
+        .as_ref()
+        .filter(|i| i.purpose == InputPurpose::GoTo)
+    {
+        let prefix = input.buffer.clone();
+        app.jump_to_prefix(&prefix, key == KeyCode::Tab);
+    }
+}
+
+/// Handle keys while a sync batch is running. Returns true to quit.
+pub fn handle_syncing_mode(app: &mut App, key: KeyCode, tx: &mpsc::Sender<SyncResult>) -> bool {
+    match key {
+        KeyCode::Char('q') => return true,
+        KeyCode::Down | KeyCode::Char('j') => app.next(),
+        KeyCode::Up | KeyCode::Char('k') => app.previous(),
+        KeyCode::Char('E') => app.show_full_error(),
+        KeyCode::Char('x') => app.skip_queued(),
+        KeyCode::Char(' ') => app.enqueue_current(tx),
+        KeyCode::Char('p') => {
+            if let Some(queue) = &app.queue {
+                let held = !queue.is_held();
+                queue.set_held(held);
+                app.show_message(if held {
+                    "Paused: forks in flight finish, the rest wait (p to resume)"
+                } else {
+                    "Resumed"
+                });
+            }
+        }
+        KeyCode::Char('s') => {
+            if let Some(queue) = &app.queue {
+                let slow = !queue.is_slow();
+                queue.set_slow(slow);
+                if slow {
+                    app.show_message(&format!(
+                        "Slow mode: one fork at a time, {}s apart",
+                        SLOW_MODE_DELAY.as_secs()
+                    ));
+                } else {
+                    app.show_message("Full speed");
+                }
+            }
+        }
+        _ => {}
+    }
+    false
+}
@@ -0,0 +1,372 @@
+use crate::events::EventSink;
+use crate::exit::{GitHubUnavailable, Interrupted, Unfinished};
+use crate::filter::ForkFilter;
+use crate::github::fetch_forks_with_fallback;
+use crate::handlers::save_full_refresh;
+use crate::interrupt;
+use crate::local::apply_path_overrides;
+use crate::metrics::{self, Tally};
+use crate::paths::ToolHomes;
+use crate::sync::{clone_snapshot, start_cloning, start_syncing, SyncOptions};
+use crate::types::{CacheStatus, Fork, ForkStore, SyncResult, SyncStatus};
+use crate::ui::short_age;
+use anyhow::{anyhow, Context, Result};
+use chrono::{Local, Utc};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+/// How often a `--no-tui` run checks for Ctrl-C while waiting on workers.
+const INTERRUPT_POLL: Duration = Duration::from_millis(100);
+
+/// Clone `forks` without the TUI (`--clone-all`), printing each result.
+/// Fails with `Unfinished` if any fork failed to clone.
+pub fn clone_all(
+    forks: &[Fork],
+    options: &SyncOptions,
+    jobs: usize,
+    mut events: Option<EventSink>,
+) -> Result<()> {
+    let stderr = events.as_ref().is_some_and(EventSink::is_stdout);
+    say(stderr, &format!("Cloning {} forks...", forks.len()));
+    let (tx, rx) = mpsc::channel();
+    start_cloning(
+        forks.iter().cloned().enumerate().collect(),
+        options,
+        jobs,
+        &tx,
+    );
+    // Workers hold the remaining senders; the loop ends once they're done
+    drop(tx);
+
+    let mut failed = 0;
+    for result in rx {
+        if let Some(events) = &mut events {
+            events.emit(&result);
+        }
+        let SyncResult::StatusUpdate(name, status) = result else {
+            continue;
+        };
+        match status {
+            SyncStatus::Synced(_) => say(stderr, &format!("  ✓ {name}")),
+            SyncStatus::Failed(err) => {
+                failed += 1;
+                say(stderr, &format!("  ✗ {name}: {err}"));
+            }
+            _ => {}
+        }
+    }
+    Unfinished {
+        failed,
+        attention: 0,
+        total: forks.len(),
+        action: "clone",
+    }
+    .check()
+}
+
+/// Sync `forks` without the TUI (`--no-tui`), printing a timestamped line
+/// whenever one changes status, for CI logs. Outcomes are recorded in `store`
+/// like in the TUI, and written to `metrics` if given. Fails with
+/// `Unfinished` if any fork failed or needs attention, `GitHubUnavailable`
+/// if `gh` wasn't logged in, or `Interrupted` after Ctrl-C.
+pub fn sync_all(
+    forks: &[Fork],
+    options: &SyncOptions,
+    jobs: usize,
+    store: Option<&dyn ForkStore>,
+    mut events: Option<EventSink>,
+    metrics: Option<&Path>,
+) -> Result<()> {
+    let stderr = events.as_ref().is_some_and(EventSink::is_stdout);
+    say(stderr, &format!("Syncing {} forks...", forks.len()));
+    // Saved like a TUI batch, so a killed run can be resumed in the TUI.
+    // Dry runs aren't persisted.
+    let persist = store.filter(|_| !options.dry_run);
+    if let Some(store) = persist {
+        let ids: Vec<String> = forks.iter().map(Fork::full_name).collect();
+        if let Err(e) = store.save_sync_queue(&ids) {
+            tracing::warn!("Failed to save sync queue: {e}");
+        }
+    }
+    let (tx, rx) = mpsc::channel();
+    let queue = start_syncing(
+        forks.iter().cloned().enumerate().collect(),
+        options,
+        jobs,
+        &tx,
+    );
+    // Workers hold the remaining senders; the loop ends once they're done
+    drop(tx);
+
+    let print = |idx: usize, text: &str| {
+        let time = Local::now().format("%H:%M:%S");
+        say(
+            stderr,
+            &format!("[{time}] {}: {text}", forks[idx].full_name()),
+        );
+    };
+    let mut statuses = vec![SyncStatus::Pending; forks.len()];
+    let mut logged_out = false;
+    let mut interrupted = false;
+    interrupt::install();
+    loop {
+        if interrupt::requested() && !interrupted {
+            // Forks in flight finish (and put back their branch and stash);
+            // the rest stay in the saved queue for the next launch
+            interrupted = true;
+            say(
+                stderr,
+                "Interrupted: finishing the forks in flight (Ctrl-C again to quit now)",
+            );
+            for (idx, _) in queue.drain() {
+                print(idx, "Skipped: interrupted");
+                statuses[idx] = SyncStatus::Skipped("interrupted".to_string());
+            }
+        }
+        let result = match rx.recv_timeout(INTERRUPT_POLL) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        if let Some(events) = &mut events {
+            events.emit(&result);
+        }
+        match result {
+            SyncResult::StatusUpdate(id, status) => {
+                let Some(idx) = forks.iter().position(|f| f.full_name() == id) else {
+                    continue;
+                };
+                if status == statuses[idx] {
+                    continue;
+                }
+                match &status {
+                    // The whole first line, not the list's truncated one
+                    SyncStatus::Failed(err) => {
+                        print(
+                            idx,
+                            &format!("Failed: {}", err.lines().next().unwrap_or(err)),
+                        );
+                    }
+                    SyncStatus::NeedsAttention(reason) => {
+                        print(idx, &format!("Needs attention: {reason}"));
+                    }
+                    _ => print(idx, &status.display()),
+                }
+                if let (Some(store), Some(_)) = (persist, status.outcome()) {
+                    let _ = store.remove_from_sync_queue(&id);
+                    let _ = store.remove_stash(&id);
+                    let _ = store.record_sync(&id, &status);
+                }
+                statuses[idx] = status;
+            }
+            SyncResult::Stashed(stash) => {
+                if let Some(store) = persist {
+                    let _ = store.record_stash(&stash);
+                }
+            }
+            SyncResult::BackedUp(backup) => {
+                if let Some(store) = persist {
+                    let _ = store.record_backup(&backup);
+                }
+            }
+            SyncResult::Log(_, lines) => {
+                for line in lines {
+                    say(stderr, &format!("    {line}"));
+                }
+            }
+            SyncResult::AuthRequired => {
+                // Nobody can log in here: skip what's left instead of pausing
+                logged_out = true;
+                say(
+                    stderr,
+                    "gh is not logged in (run `gh auth login`); skipping the remaining forks",
+                );
+                for (idx, _) in queue.drain() {
+                    print(idx, "Skipped: gh not logged in");
+                    statuses[idx] = SyncStatus::Skipped("gh not logged in".to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let count = |outcome: &str| {
+        statuses
+            .iter()
+            .filter(|s| s.outcome() == Some(outcome))
+            .count()
+    };
+    let tally = Tally {
+        synced: count("synced"),
+        skipped: count("skipped"),
+        failed: count("failed"),
+        attention: count("attention"),
+    };
+    let Tally {
+        failed, attention, ..
+    } = tally;
+    say(
+        stderr,
+        &format!(
+            "Synced {}, skipped {}, failed {failed}, needs attention {attention}",
+            tally.synced, tally.skipped,
+        ),
+    );
+    if let Some(path) = metrics {
+        metrics::write(path, forks, store, tally)?;
+    }
+    if logged_out {
+        return Err(anyhow!("gh is not logged in").context(GitHubUnavailable));
+    }
+    if interrupted {
+        return Err(Interrupted.into());
+    }
+    Unfinished {
+        failed,
+        attention,
+        total: forks.len(),
+        action: "sync",
+    }
+    .check()
+}
+
+/// Print what was found before the TUI takes over the terminal.
+pub fn print_found(
+    forks: &[Fork],
+    cache_status: &CacheStatus,
+    tool_homes: &ToolHomes,
+    profile_name: Option<&str>,
+) {
+    let cloned_count = forks.iter().filter(|f| f.is_cloned).count();
+    let uncloned_count = forks.len() - cloned_count;
+    let cache_msg = match cache_status {
+        CacheStatus::Fresh => "(cached)",
+        CacheStatus::Stale { refreshing: true } => "(refreshing...)",
+        CacheStatus::Stale { refreshing: false } => "(stale)",
+        CacheStatus::Offline => "(offline)",
+    };
+    if let Some(name) = profile_name {
+        println!("Profile: {name}");
+    }
+    println!(
+        "Found {} forks ({} cloned, {} uncloned) {} Tool home: {}",
+        forks.len(),
+        cloned_count,
+        uncloned_count,
+        cache_msg,
+        tool_homes
+            .roots()
+            .iter()
+            .map(|r| r.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+}
+
+/// Print how the TUI's batches ended, once it has left the screen.
+/// `interrupted` if the last one was stopped with Ctrl-C.
+pub fn print_summary(tally: Tally, interrupted: bool) {
+    let rows = [
+        ("Synced", tally.synced),
+        ("Skipped", tally.skipped),
+        ("Failed", tally.failed),
+        ("Needs attention", tally.attention),
+    ];
+    if rows.iter().all(|(_, n)| *n == 0) {
+        return;
+    }
+    println!("\nSummary:");
+    for (label, n) in rows.into_iter().filter(|(_, n)| *n > 0) {
+        println!("  {label}: {n}");
+    }
+    if interrupted {
+        println!("  Interrupted: forks that didn't start are offered for resume next time");
+    }
+}
+
+/// Print a line of progress: to stderr when `--events` writes to stdout, so
+/// the event stream stays parseable.
+fn say(stderr: bool, line: &str) {
+    if stderr {
+        eprintln!("{line}");
+    } else {
+        println!("{line}");
+    }
+}
+
+/// Fetch the full fork list and drop cached forks that no longer exist
+/// (`repo-syncer cache prune`), printing each one removed.
+pub fn prune_cache(cache: &dyn ForkStore, tool_homes: &ToolHomes) -> Result<()> {
+    let fetched = fetch_forks_with_fallback(tool_homes).context(GitHubUnavailable)?;
+    if fetched.truncated {
+        anyhow::bail!(
+            "GitHub listed only the first {} forks; not pruning the rest",
+            fetched.forks.len()
+        );
+    }
+    let removed = cache.prune_forks(&fetched.forks)?;
+    for id in &removed {
+        println!("  - {id}");
+    }
+    println!("Pruned {} forks no longer on GitHub", removed.len());
+    save_full_refresh(cache, &fetched);
+    Ok(())
+}
+
+/// Print a table of the non-ignored forks that pass `filter`
+/// (`repo-syncer status`): cloned or not, how far behind upstream and whether
+/// dirty the clone is, and when it last synced. Reads the cache (GitHub only
+/// when it's empty) and the clones; nothing is fetched into them or written.
+pub fn print_status(
+    cache: Option<&dyn ForkStore>,
+    tool_homes: &ToolHomes,
+    filter: &ForkFilter,
+) -> Result<()> {
+    let cache = cache.filter(|c| !c.is_empty().unwrap_or(true));
+    let mut forks = match cache {
+        Some(cache) => cache.load_forks(tool_homes)?,
+        None => {
+            fetch_forks_with_fallback(tool_homes)
+                .context(GitHubUnavailable)?
+                .forks
+        }
+    };
+    let (overrides, ignored, last_synced) = cache.map_or_else(Default::default, |c| {
+        (
+            c.path_overrides().unwrap_or_default(),
+            c.ignored_forks().unwrap_or_default(),
+            c.last_synced().unwrap_or_default(),
+        )
+    });
+    apply_path_overrides(&mut forks, &overrides);
+    forks.retain(|f| !ignored.contains(&f.full_name()) && filter.matches(f));
+    forks.sort_by_key(Fork::full_name);
+
+    let width = forks
+        .iter()
+        .map(|f| f.full_name().len())
+        .max()
+        .unwrap_or_default()
+        .max("FORK".len());
+    println!("{:width$}  CLONED  BEHIND  DIRTY  LAST SYNCED", "FORK");
+    let now = Utc::now();
+    for fork in &forks {
+        let id = fork.full_name();
+        let (behind, dirty) = if fork.is_cloned {
+            clone_snapshot(fork)
+        } else {
+            (None, false)
+        };
+        println!(
+            "{id:width$}  {:6}  {:>6}  {:5}  {}",
+            if fork.is_cloned { "yes" } else { "no" },
+            behind.map_or_else(|| "-".to_string(), |n| n.to_string()),
+            if dirty { "yes" } else { "" },
+            last_synced
+                .get(&id)
+                .map_or_else(|| "never".to_string(), |&at| short_age(now, at)),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Ctrl-C (SIGINT) and SIGTERM during a sync batch: the first one stops new
+//! forks from starting and lets the ones in flight finish, including putting
+//! back their branch and stash; a second one exits right away.
+
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::flag;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Once};
+
+/// Exit code of a run ended by a signal, like a shell's `128 + SIGINT`.
+pub const EXIT_CODE: i32 = 130;
+
+static REQUESTED: LazyLock<Arc<AtomicBool>> = LazyLock::new(Arc::default);
+/// Set once a stop has been seen (`requested`): the next signal exits.
+static ARMED: LazyLock<Arc<AtomicBool>> = LazyLock::new(Arc::default);
+static INSTALLED: Once = Once::new();
+
+/// Trap SIGINT and SIGTERM from now on (once per process). Until then they
+/// end the process as usual.
+pub fn install() {
+    INSTALLED.call_once(|| {
+        for signal in [SIGINT, SIGTERM] {
+            // Registered first, so it sees the flag from before this signal
+            let _ = flag::register_conditional_shutdown(signal, EXIT_CODE, Arc::clone(&ARMED));
+            let _ = flag::register(signal, Arc::clone(&REQUESTED));
+        }
+    });
+}
+
+/// Ask the running batch to stop, as a signal would (Ctrl-C in the TUI,
+/// where raw mode turns it into a key press).
+pub fn request() {
+    REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Whether a stop was asked for. Once it has been, another signal exits.
+pub fn requested() -> bool {
+    let requested = REQUESTED.load(Ordering::SeqCst);
+    if requested {
+        ARMED.store(true, Ordering::SeqCst);
+    }
+    requested
+}
+
+/// Run `run` (an interactive command with the TUI suspended) without
+/// letting the Ctrl-C meant for it stop the batch or exit the app.
+pub fn ignored_while<T>(run: impl FnOnce() -> T) -> T {
+    let requested = REQUESTED.load(Ordering::SeqCst);
+    let armed = ARMED.swap(false, Ordering::SeqCst);
+    let result = run();
+    REQUESTED.store(requested, Ordering::SeqCst);
+    ARMED.store(armed, Ordering::SeqCst);
+    result
+}
@@ -0,0 +1,95 @@
+use crate::types::Fork;
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Every key of the fork list's built-in actions (`handle_selecting_mode`
+/// and `handle_paging`). Add a key here when giving it an action there.
+const BUILTIN_KEYS: &str = "abcdefghijklnopqrsuvxABCDEFGHILOPRSTUVWX123456/' ";
+
+/// Built-in keys a launcher may replace: browser, editor and shell.
+const REPLACEABLE_KEYS: &str = "oes";
+
+/// An external tool opened on the highlighted fork with a key
+/// (`[launchers.<key>]`), e.g. lazygit, a tmux window or VS Code.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Launcher {
+    /// Shown in the help bar.
+    pub name: String,
+    /// Run with `sh -c` in the clone (if there is one); `{path}` and
+    /// `{repo}` (`owner/name`) are replaced, shell-quoted.
+    pub command: String,
+    /// Suspend the TUI until the command exits (for terminal tools like
+    /// lazygit); otherwise it's started in the background (`code`, `tmux
+    /// new-window`).
+    #[serde(default = "default_wait")]
+    pub wait: bool,
+}
+
+fn default_wait() -> bool {
+    true
+}
+
+impl Launcher {
+    /// Whether the command needs a local clone.
+    pub fn needs_clone(&self) -> bool {
+        self.command.contains("{path}")
+    }
+
+    /// The command line for `fork`.
+    pub fn command_line(&self, fork: &Fork) -> String {
+        self.command
+            .replace("{path}", &quote(&fork.local_path.to_string_lossy()))
+            .replace("{repo}", &quote(&fork.full_name()))
+    }
+}
+
+/// Whether a launcher may be bound to `key`: a free key or a replaceable
+/// built-in.
+pub fn bindable(key: char) -> bool {
+    !BUILTIN_KEYS.contains(key) || REPLACEABLE_KEYS.contains(key)
+}
+
+/// Reject launchers on keys the fork list already uses.
+pub fn check_keys(launchers: &BTreeMap<char, Launcher>) -> Result<()> {
+    if let Some(key) = launchers.keys().find(|k| !bindable(**k)) {
+        bail!("[launchers.{key}]: `{key}` is a built-in key (launchers can use o, e, s or a free key)");
+    }
+    Ok(())
+}
+
+/// `s` in single quotes, for `sh -c`.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_command_line_quotes_placeholders() {
+        let fork = Fork {
+            parent_owner: "serde-rs".to_string(),
+            default_branch: "master".to_string(),
+            local_path: PathBuf::from("/home/me/it's/serde"),
+            is_cloned: true,
+            ..Fork::test("me", "serde")
+        };
+        let launcher = Launcher {
+            name: "tmux".to_string(),
+            command: "tmux new-window -n {repo} -c {path}".to_string(),
+            wait: false,
+        };
+        assert_eq!(
+            launcher.command_line(&fork),
+            r"tmux new-window -n 'me/serde' -c '/home/me/it'\''s/serde'"
+        );
+    }
+}
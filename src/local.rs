@@ -0,0 +1,251 @@
+use crate::tools;
+use crate::types::{FindingKind, Fork, LocalFinding, RepoKind};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+// ============================================================
+// LOCAL REPOSITORY DISCOVERY
+// ============================================================
+
+/// List git repositories laid out as `tool_home/<owner>/<name>`.
+/// Hidden directories (e.g. `.trash`) are skipped.
+pub fn list_local_repos(tool_home: &Path) -> Vec<PathBuf> {
+    let mut repos = Vec::new();
+    for owner_dir in visible_subdirs(tool_home) {
+        for repo_dir in visible_subdirs(&owner_dir) {
+            if repo_dir.join(".git").exists() {
+                repos.push(repo_dir);
+            }
+        }
+    }
+    repos.sort();
+    repos
+}
+
+fn visible_subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.path())
+        .collect()
+}
+
+/// Total size in bytes of the files under `path` (symlinks aren't followed).
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Get the URL of a remote in a local repository.
+pub fn remote_url(path: &Path, remote: &str) -> Option<String> {
+    let output = tools::git()
+        .arg("-C")
+        .arg(path)
+        .args(["remote", "get-url", remote])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!url.is_empty()).then_some(url)
+}
+
+/// Extract `(owner, name)` from a git remote URL.
+/// Handles `git@host:owner/name.git`, `https://host/owner/name(.git)` and
+/// `ssh://git@host/owner/name.git`.
+pub fn parse_repo_slug(url: &str) -> Option<(String, String)> {
+    let path = url
+        .trim()
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':'])
+        .take(2)
+        .collect::<Vec<_>>();
+    match path.as_slice() {
+        [name, owner] if !name.is_empty() && !owner.is_empty() => {
+            Some(((*owner).to_string(), (*name).to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Determine the upstream default branch of a local repo, falling back to
+/// origin's default and then `main`.
+fn default_branch(path: &Path) -> String {
+    ["refs/remotes/upstream/HEAD", "refs/remotes/origin/HEAD"]
+        .iter()
+        .find_map(|r| {
+            let output = tools::git()
+                .arg("-C")
+                .arg(path)
+                .args(["symbolic-ref", "--short", r])
+                .output()
+                .ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            let full = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            full.split_once('/').map(|(_, branch)| branch.to_string())
+        })
+        .unwrap_or_else(|| "main".to_string())
+}
+
+/// Scan `tool_home` for non-fork repos that have an `upstream` remote.
+/// Repos whose `owner/name` is in `known` (already listed forks) are skipped.
+pub fn scan_upstream_repos(tool_home: &Path, known: &HashSet<String>) -> Vec<Fork> {
+    list_local_repos(tool_home)
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().to_string();
+            let owner = path.parent()?.file_name()?.to_string_lossy().to_string();
+            if known.contains(&format!("{owner}/{name}")) {
+                return None;
+            }
+
+            let (parent_owner, parent_name) = parse_repo_slug(&remote_url(&path, "upstream")?)?;
+
+            Some(Fork {
+                name,
+                owner,
+                parent_owner,
+                parent_name,
+                default_branch: default_branch(&path),
+                local_path: path,
+                is_cloned: true,
+                description: None,
+                primary_language: None,
+                created_at: None,
+                updated_at: None,
+                upstream_head: None,
+                upstream_pushed_at: None,
+                visibility: None,
+                kind: RepoKind::UpstreamRemote,
+            })
+        })
+        .collect()
+}
+
+/// Point forks with a path override (set with `p`) at that location instead
+/// of `tool_home/<owner>/<name>`.
+pub fn apply_path_overrides(forks: &mut [Fork], overrides: &HashMap<String, PathBuf>) {
+    for fork in forks {
+        if let Some(path) = overrides.get(&fork.full_name()) {
+            fork.relocate(path.clone());
+        }
+    }
+}
+
+// ============================================================
+// RECONCILIATION
+// ============================================================
+
+/// Compare git repos under `tool_home` with the fork list, returning orphans
+/// (repos no fork points at) and forks whose clone has a mismatched origin.
+pub fn find_local_issues(tool_home: &Path, forks: &[Fork]) -> Vec<LocalFinding> {
+    let by_path: HashMap<&Path, &Fork> =
+        forks.iter().map(|f| (f.local_path.as_path(), f)).collect();
+    let by_id: HashMap<String, &Fork> = forks
+        .iter()
+        .filter(|f| f.is_github_fork())
+        .map(|f| (f.full_name().to_lowercase(), f))
+        .collect();
+
+    let mut findings = Vec::new();
+    for path in list_local_repos(tool_home) {
+        let origin = remote_url(&path, "origin");
+        let slug = origin.as_deref().and_then(parse_repo_slug);
+        let suggested_path = slug
+            .as_ref()
+            .map(|(owner, name)| tool_home.join(owner).join(name))
+            .filter(|p| p != &path && !p.exists());
+
+        let kind = match by_path.get(path.as_path()) {
+            // Upstream-remote repos have arbitrary origins
+            Some(fork) if !fork.is_github_fork() => continue,
+            Some(fork) => {
+                let matches = slug.as_ref().is_some_and(|(o, n)| {
+                    format!("{o}/{n}").eq_ignore_ascii_case(&fork.full_name())
+                });
+                if matches {
+                    continue;
+                }
+                FindingKind::OriginMismatch {
+                    fork: fork.full_name(),
+                }
+            }
+            None => FindingKind::Orphan {
+                adopt_as: slug
+                    .as_ref()
+                    .and_then(|(o, n)| by_id.get(&format!("{o}/{n}").to_lowercase()))
+                    .filter(|f| !f.local_path.exists())
+                    .map(|f| f.full_name()),
+            },
+        };
+
+        findings.push(LocalFinding {
+            path,
+            origin,
+            kind,
+            suggested_path,
+        });
+    }
+    findings
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_repo_slug() {
+        let expected = Some(("rust-lang".to_string(), "cargo".to_string()));
+        assert_eq!(
+            parse_repo_slug("git@github.com:rust-lang/cargo.git"),
+            expected
+        );
+        assert_eq!(
+            parse_repo_slug("https://github.com/rust-lang/cargo"),
+            expected
+        );
+        assert_eq!(
+            parse_repo_slug("https://github.com/rust-lang/cargo.git/"),
+            expected
+        );
+        assert_eq!(
+            parse_repo_slug("ssh://git@github.com/rust-lang/cargo.git"),
+            expected
+        );
+        assert_eq!(parse_repo_slug("cargo"), None);
+    }
+
+    #[test]
+    fn test_dir_size() {
+        let dir = std::env::temp_dir().join(format!("repo-syncer-size-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("a.txt"), [0u8; 100]).unwrap();
+        std::fs::write(dir.join("nested/b.txt"), [0u8; 50]).unwrap();
+
+        assert_eq!(dir_size(&dir), 150);
+        assert_eq!(dir_size(&dir.join("missing")), 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
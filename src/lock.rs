@@ -0,0 +1,146 @@
+use crate::tools;
+use anyhow::{bail, Context, Result};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// An exclusive lock on a file, held until dropped. The OS releases it when
+/// the process exits, so a crashed run never leaves a stale lock behind.
+pub struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    /// Lock `path` (created if missing) and write our PID into it, for the
+    /// message other processes show. `Ok(None)` if another process holds it.
+    pub fn try_acquire(path: &Path) -> io::Result<Option<Self>> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok(None),
+            Err(TryLockError::Error(e)) => return Err(e),
+        }
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+        Ok(Some(Self { _file: file }))
+    }
+
+    /// Lock `path` (created if missing), waiting for another holder to
+    /// release it.
+    pub fn acquire(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        file.lock()?;
+        Ok(Self { _file: file })
+    }
+
+    /// "locked by another process", with its PID if it wrote one.
+    pub fn held_by(path: &Path) -> String {
+        match std::fs::read_to_string(path)
+            .ok()
+            .and_then(|pid| pid.trim().parse::<u32>().ok())
+        {
+            Some(pid) => format!("locked by another process (pid {pid})"),
+            None => "locked by another process".to_string(),
+        }
+    }
+}
+
+/// Where a local repo's lock lives: in its git directory (`git rev-parse
+/// --git-common-dir`), which a linked worktree or submodule points to with a
+/// `.git` file and a bare clone is itself. Falls back to the repo itself if
+/// git can't tell.
+pub fn repo_lock_path(repo: &Path) -> PathBuf {
+    let common_dir = tools::git()
+        .arg("-C")
+        .arg(repo)
+        .args(["rev-parse", "--git-common-dir"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|dir| !dir.is_empty());
+    // Relative to `repo` unless git printed an absolute path
+    common_dir
+        .map_or_else(|| repo.to_path_buf(), |dir| repo.join(dir))
+        .join("repo-syncer.lock")
+}
+
+/// Take the instance lock (`--single-instance`) for the session, failing if
+/// another repo-syncer holds it.
+pub fn lock_instance(path: &Path) -> Result<FileLock> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    match FileLock::try_acquire(path)
+        .with_context(|| format!("Failed to lock {}", path.display()))?
+    {
+        Some(lock) => Ok(lock),
+        None => bail!(
+            "Another repo-syncer is running: {} is {}",
+            path.display(),
+            FileLock::held_by(path)
+        ),
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_lock() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("repo-syncer.lock");
+
+        let lock = FileLock::try_acquire(&path).unwrap();
+        assert!(lock.is_some());
+        assert!(FileLock::try_acquire(&path).unwrap().is_none());
+        assert_eq!(
+            FileLock::held_by(&path),
+            format!("locked by another process (pid {})", std::process::id())
+        );
+        assert!(lock_instance(&path).is_err());
+
+        drop(lock);
+        assert!(FileLock::try_acquire(&path).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_repo_lock_path_with_git_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        let git_dir = dir.path().join("repo.git");
+        let status = tools::git()
+            .args(["init", "-q", "--separate-git-dir"])
+            .arg(&git_dir)
+            .arg(&repo)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert!(repo.join(".git").is_file());
+
+        let lock = repo_lock_path(&repo);
+        assert_eq!(
+            lock.parent().unwrap().canonicalize().unwrap(),
+            git_dir.canonicalize().unwrap()
+        );
+        assert_eq!(
+            repo_lock_path(dir.path()),
+            dir.path().join("repo-syncer.lock")
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use crate::types::{AuditEntry, ForkStore};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::cell::RefCell;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::sync::Mutex;
+use tracing::level_filters::LevelFilter;
+
+/// Send `tracing` events to `path` (appended to), never to the terminal:
+/// the TUI owns the alternate screen, and output from background threads
+/// would corrupt it.
+pub fn init(path: &Path, level: LevelFilter) -> Result<()> {
+    if level == LevelFilter::OFF {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create log directory")?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))?;
+    tracing_subscriber::fmt()
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .with_max_level(level)
+        .init();
+    Ok(())
+}
+
+/// Told about each command a thread runs (see `on_command`).
+type CommandCallback = Box<dyn Fn(&str)>;
+
+thread_local! {
+    static ON_COMMAND: RefCell<Option<CommandCallback>> = const { RefCell::new(None) };
+}
+
+/// Pass the command line of every `logged_output` call on this thread to
+/// `callback`, until the returned guard is dropped. Sync workers use it to
+/// show what each fork is running.
+pub fn on_command(callback: impl Fn(&str) + 'static) -> CommandWatch {
+    ON_COMMAND.with(|c| *c.borrow_mut() = Some(Box::new(callback)));
+    CommandWatch
+}
+
+/// Stops `on_command` reporting when dropped.
+pub struct CommandWatch;
+
+impl Drop for CommandWatch {
+    fn drop(&mut self) {
+        ON_COMMAND.with(|c| c.borrow_mut().take());
+    }
+}
+
+/// Bytes of a command's output kept in its audit entry.
+const AUDIT_OUTPUT_LIMIT: usize = 4000;
+
+/// Commands run (on any thread) since the last `take_audit`, once
+/// `start_audit` has been called.
+static AUDIT: Mutex<Option<Vec<AuditEntry>>> = Mutex::new(None);
+
+/// Start collecting an `AuditEntry` for every `logged_output` call, for
+/// `flush_audit` to write to the store.
+pub fn start_audit() {
+    if let Ok(mut audit) = AUDIT.lock() {
+        audit.get_or_insert_with(Vec::new);
+    }
+}
+
+/// The commands collected since the last call, oldest first.
+fn take_audit() -> Vec<AuditEntry> {
+    AUDIT
+        .lock()
+        .ok()
+        .and_then(|mut audit| audit.as_mut().map(std::mem::take))
+        .unwrap_or_default()
+}
+
+/// Write the commands collected since the last call to `store`'s audit log.
+pub fn flush_audit(store: Option<&dyn ForkStore>) {
+    let Some(store) = store else {
+        return;
+    };
+    let entries = take_audit();
+    if entries.is_empty() {
+        return;
+    }
+    if let Err(e) = store.record_commands(&entries) {
+        tracing::warn!("Failed to write the audit log: {e}");
+    }
+}
+
+/// `output` cut to `AUDIT_OUTPUT_LIMIT` bytes (on a char boundary).
+fn truncate_output(mut output: String) -> String {
+    if output.len() > AUDIT_OUTPUT_LIMIT {
+        let mut end = AUDIT_OUTPUT_LIMIT;
+        while !output.is_char_boundary(end) {
+            end -= 1;
+        }
+        output.truncate(end);
+        output.push_str("\n[truncated]");
+    }
+    output
+}
+
+fn audit(command: &Command, line: String, started_at: DateTime<Utc>, output: &io::Result<Output>) {
+    let Ok(mut audit) = AUDIT.lock() else {
+        return;
+    };
+    let Some(entries) = audit.as_mut() else {
+        return;
+    };
+    let cwd = command
+        .get_current_dir()
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok())
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    let (exit_code, text) = match output {
+        Ok(out) => {
+            let mut text = String::from_utf8_lossy(&out.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&out.stderr));
+            (out.status.code(), text)
+        }
+        Err(e) => (None, format!("failed to start: {e}")),
+    };
+    let elapsed = Utc::now() - started_at;
+    entries.push(AuditEntry {
+        started_at,
+        command: line,
+        cwd,
+        exit_code,
+        duration_ms: elapsed.num_milliseconds().unsigned_abs(),
+        output: truncate_output(text),
+    });
+}
+
+/// `Command::output` that also logs the command line, its exit status and
+/// (on failure) its stderr, and adds it to the audit log if one is kept.
+pub trait LoggedOutput {
+    fn logged_output(&mut self) -> io::Result<Output>;
+
+    /// `logged_output` with `input` written to the command's stdin, which
+    /// (unlike its arguments) stays out of the logs and the audit log.
+    fn logged_output_with_stdin(&mut self, input: &[u8]) -> io::Result<Output>;
+}
+
+impl LoggedOutput for Command {
+    fn logged_output(&mut self) -> io::Result<Output> {
+        run_logged(self, Command::output)
+    }
+
+    fn logged_output_with_stdin(&mut self, input: &[u8]) -> io::Result<Output> {
+        run_logged(self, |command| {
+            let mut child = command
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()?;
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(input)?;
+            }
+            child.wait_with_output()
+        })
+    }
+}
+
+/// Run `command` with `run`, logging and auditing it (see `LoggedOutput`).
+fn run_logged(
+    command: &mut Command,
+    run: impl FnOnce(&mut Command) -> io::Result<Output>,
+) -> io::Result<Output> {
+    let line = std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
+    ON_COMMAND.with(|c| {
+        if let Some(callback) = &*c.borrow() {
+            callback(&line);
+        }
+    });
+    let started_at = Utc::now();
+    let output = run(command);
+    match &output {
+        Ok(out) if out.status.success() => tracing::debug!(status = %out.status, "{line}"),
+        Ok(out) => tracing::info!(
+            status = %out.status,
+            stderr = %String::from_utf8_lossy(&out.stderr).trim(),
+            "{line}"
+        ),
+        Err(e) => tracing::warn!(error = %e, "{line}"),
+    }
+    audit(command, line, started_at, &output);
+    output
+}
@@ -1,62 +1,225 @@
+mod api_cache;
 mod app;
 mod cache;
 mod cli;
+mod config;
+mod doctor;
+mod events;
+mod exit;
+mod filter;
 mod github;
 mod handlers;
+mod headless;
+mod interrupt;
+mod launcher;
+mod local;
+mod lock;
+mod logging;
+mod metrics;
+mod paths;
+mod profile;
 mod sync;
+mod token_api;
+mod tools;
+mod trash;
 mod types;
 mod ui;
 
 use anyhow::{Context, Result};
-use chrono::Utc;
 use clap::Parser;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
-use std::{env, io, sync::mpsc, time::Duration};
+use std::{
+    collections::HashSet,
+    io,
+    process::ExitCode,
+    sync::mpsc,
+    time::{Duration, Instant},
+};
 
 use app::App;
-use cache::SqliteStore;
-use cli::Args;
-use github::fetch_forks_graphql;
+use cache::open_store;
+use cli::{Args, CacheCommand, Command};
+use config::Config;
+use events::EventSink;
+use exit::{GitHubUnavailable, Interrupted, Unfinished};
 use handlers::{
-    handle_confirm_modal, handle_error_popup, handle_search_mode, handle_selecting_mode,
+    handle_audit_overlay, handle_confirm_modal, handle_error_popup, handle_input_mode,
+    handle_mouse, handle_paging, handle_plan_overlay, handle_preview_overlay,
+    handle_profiles_overlay, handle_reconcile_overlay, handle_reminders_overlay,
+    handle_search_mode, handle_selecting_mode, handle_stashes_overlay, handle_syncing_mode,
+    load_forks_with_cache, start_batch, start_behind_lookup, start_disk_usage_scan, start_lookups,
+    start_refresh,
 };
-use sync::start_syncing;
-use types::{CacheStatus, Fork, ForkStore, Mode, SyncResult};
+use paths::{Paths, ToolHomes};
+use profile::GhEnv;
+use sync::SyncOptions;
+use types::{CacheStatus, Fork, Mode, ProfileSwitch, SyncResult, Toast};
+
+fn main() -> ExitCode {
+    let mut args = Args::parse();
+    match run_sessions(&mut args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            exit::code(&err)
+        }
+    }
+}
+
+fn run_sessions(args: &mut Args) -> Result<()> {
+    let gh_env = GhEnv::capture();
+    let log_file = match &args.log_file {
+        Some(path) => path.clone(),
+        None => Paths::resolve(args, None)?.log_file(),
+    };
+    logging::init(&log_file, args.log_level)?;
+
+    // Picking a profile with `S` ends the session; start over with it
+    while let Some(switch) = run(args, &gh_env)? {
+        args.profile = switch.into_name();
+        args.yes = false;
+    }
+    Ok(())
+}
+
+/// Load the forks of `args.profile` and run the TUI (or a headless command).
+/// Returns the profile picked with `S`, if any.
+fn run(args: &Args, gh_env: &GhEnv) -> Result<Option<ProfileSwitch>> {
+    let profile_name = args.profile.as_deref();
+    let paths = Paths::resolve(args, profile_name)?;
+    let config = Config::load(&paths.config_file)?
+        .with_profile(profile_name)?
+        .merge_args(args);
+    tools::configure(&config.tools);
+    let profile = config.profile(profile_name)?.cloned().unwrap_or_default();
+    gh_env
+        .activate(profile_name.map(|_| &profile))
+        .context(GitHubUnavailable)?;
+    let tool_homes = ToolHomes::resolve(&args.tool_home, &config.tool_homes)?;
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    let tool_home = get_tool_home(args.tool_home.clone())?;
+    if let Some(id) = &args.restore {
+        let path = trash::restore(&tool_homes, id)?;
+        println!("Restored {id} to {}", path.display());
+        return Ok(None);
+    }
+
+    if let Some(Command::Cache {
+        command: CacheCommand::Prune,
+    }) = &args.command
+    {
+        let cache = open_store(config.store, &paths.db_file)?;
+        headless::prune_cache(cache.as_ref(), &tool_homes)?;
+        return Ok(None);
+    }
+
+    if let Some(Command::Status) = &args.command {
+        let cache = open_store(config.store, &paths.db_file).ok();
+        headless::print_status(cache.as_deref(), &tool_homes, &args.filter)?;
+        return Ok(None);
+    }
+
+    if let Some(Command::Doctor) = &args.command {
+        let checks = doctor::all_checks(&tool_homes);
+        let failed = doctor::print_report(&checks);
+        if failed > 0 {
+            anyhow::bail!("{failed} of {} checks failed", checks.len());
+        }
+        return Ok(None);
+    }
+    doctor::warn_on_startup(&doctor::quick_checks(&tool_homes, args.offline));
+    let _instance = if args.single_instance {
+        Some(lock::lock_instance(&paths.lock_file())?)
+    } else {
+        None
+    };
 
     // Try to load from cache first
-    let cache = SqliteStore::open().ok();
-    let (forks, cache_status) = load_forks_with_cache(cache.as_ref(), &tool_home, args.refresh)?;
+    let cache = open_store(config.store, &paths.db_file).ok();
+    if let Some(cache) = &cache {
+        logging::start_audit();
+        api_cache::load(cache.as_ref());
+    }
+    let (fetched, cache_status) = load_forks_with_cache(
+        cache.as_deref(),
+        &tool_homes,
+        args.refresh,
+        args.offline,
+        config.cache_ttl_hours,
+    )?;
+    let mut forks = fetched.forks;
+
+    // Forks cloned outside the tool home layout (set with `p`)
+    let overrides = cache
+        .as_ref()
+        .and_then(|c| c.path_overrides().ok())
+        .unwrap_or_default();
+    local::apply_path_overrides(&mut forks, &overrides);
+
+    // Add local non-fork repos that track an upstream remote
+    if args.scan_upstreams {
+        let mut known: HashSet<String> = forks.iter().map(Fork::full_name).collect();
+        for root in tool_homes.roots() {
+            let found = local::scan_upstream_repos(root, &known);
+            known.extend(found.iter().map(Fork::full_name));
+            forks.extend(found);
+        }
+    }
 
     if forks.is_empty() {
         println!("No forks found.");
-        return Ok(());
+        return Ok(None);
     }
 
-    let cloned_count = forks.iter().filter(|f| f.is_cloned).count();
-    let uncloned_count = forks.len() - cloned_count;
-    let cache_msg = match cache_status {
-        CacheStatus::Fresh => "(cached)",
-        CacheStatus::Stale { refreshing: true } => "(refreshing...)",
-        CacheStatus::Stale { refreshing: false } => "(stale)",
-        CacheStatus::Offline => "(offline)",
-    };
-    println!(
-        "Found {} forks ({} cloned, {} uncloned) {} Tool home: {}",
-        forks.len(),
-        cloned_count,
-        uncloned_count,
-        cache_msg,
-        tool_home.display()
-    );
+    let events = args
+        .events
+        .map(|_| EventSink::open(args.events_file.as_deref()))
+        .transpose()?;
+    let headless = args.clone_all || args.no_tui;
+    if !headless && events.as_ref().is_some_and(EventSink::is_stdout) {
+        anyhow::bail!("--events needs --events-file with the TUI, which owns stdout");
+    }
+    if headless {
+        let mut ignored = cache
+            .as_ref()
+            .and_then(|c| c.ignored_forks().ok())
+            .unwrap_or_default();
+        ignored.extend(profile.ignore.iter().cloned());
+        let targets = forks
+            .into_iter()
+            .filter(|f| !ignored.contains(&f.full_name()) && args.filter.matches(f));
+        let options = SyncOptions {
+            local_only: cache_status == CacheStatus::Offline,
+            ..config.sync_options(args.dry_run)
+        };
+        let result = if args.clone_all {
+            let uncloned: Vec<Fork> = targets
+                .filter(|f| f.is_github_fork() && !f.is_cloned)
+                .collect();
+            headless::clone_all(&uncloned, &options, args.jobs, events)
+        } else {
+            let cloned: Vec<Fork> = targets.filter(|f| f.is_cloned).collect();
+            headless::sync_all(
+                &cloned,
+                &options,
+                args.jobs,
+                cache.as_deref(),
+                events,
+                args.metrics_file.as_deref(),
+            )
+        };
+        logging::flush_audit(cache.as_deref());
+        api_cache::flush(cache.as_deref());
+        return result.map(|()| None);
+    }
+
+    headless::print_found(&forks, &cache_status, &tool_homes, profile_name);
     println!("Launching TUI...");
 
     enable_raw_mode()?;
@@ -65,22 +228,37 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(forks, args.dry_run, tool_home.clone(), cache_status);
+    let mut app = App::new(forks, args.dry_run, tool_homes, cache_status, cache);
+    app.jobs = args.jobs;
+    app.forks_truncated = fetched.truncated;
+    app.usage_enabled = !args.no_usage_stats;
+    app.config = config;
+    app.db_file = paths.db_file;
+    app.apply_profile(profile_name, &profile);
+    app.refreshed_at = app
+        .store
+        .as_ref()
+        .and_then(|s| s.last_full_sync().ok().flatten());
+    app.announce_due_reminders();
 
-    // Skip to syncing if --yes flag is set (only sync cloned forks)
+    // Skip to syncing if --yes flag is set (only sync cloned, non-ignored forks)
     if args.yes {
-        for (i, fork) in app.forks.iter().enumerate() {
-            if fork.is_cloned {
+        for i in 0..app.forks.len() {
+            if app.forks[i].is_cloned && !app.is_ignored(i) && args.filter.matches(&app.forks[i]) {
                 app.selected[i] = true;
             }
         }
         if app.selected_count() > 0 {
-            app.mark_selected_as_pending();
             app.mode = Mode::Syncing;
         }
+    } else {
+        app.offer_resume();
+        app.offer_stash_recovery();
     }
 
-    let res = run_app(&mut terminal, &mut app);
+    let res = run_app(&mut terminal, &mut app, events);
+    logging::flush_audit(app.store.as_deref());
+    api_cache::flush(app.store.as_deref());
 
     disable_raw_mode()?;
     execute!(
@@ -94,160 +272,181 @@ fn main() -> Result<()> {
         eprintln!("Error: {err:?}");
     }
 
-    // Print summary
-    let (synced, skipped, failed) = app.summary();
-    if synced > 0 || skipped > 0 || failed > 0 {
-        println!("\nSummary:");
-        if synced > 0 {
-            println!("  Synced: {synced}");
-        }
-        if skipped > 0 {
-            println!("  Skipped: {skipped}");
-        }
-        if failed > 0 {
-            println!("  Failed: {failed}");
+    app.save_usage();
+
+    let (synced, skipped, failed, attention) = app.summary();
+    let tally = metrics::Tally {
+        synced,
+        skipped,
+        failed,
+        attention,
+    };
+    headless::print_summary(tally, app.stopping);
+    if let Some(path) = &args.metrics_file {
+        if let Err(e) = metrics::write(path, &app.forks, app.store.as_deref(), tally) {
+            eprintln!("Warning: {e:#}");
         }
     }
-
-    Ok(())
-}
-
-fn get_tool_home(args_tool_home: Option<std::path::PathBuf>) -> Result<std::path::PathBuf> {
-    if let Some(path) = args_tool_home {
-        return Ok(path);
+    if app.stopping {
+        return Err(Interrupted.into());
     }
-    let home = env::var("HOME").context("HOME environment variable not set")?;
-    Ok(std::path::PathBuf::from(home).join("dev/github.com"))
-}
-
-/// Load forks with cache support.
-/// Returns (forks, `cache_status`) tuple.
-fn load_forks_with_cache(
-    cache: Option<&SqliteStore>,
-    tool_home: &std::path::Path,
-    force_refresh: bool,
-) -> Result<(Vec<Fork>, CacheStatus)> {
-    // If no cache available, fetch directly
-    let Some(cache) = cache else {
-        let forks = fetch_forks_graphql(tool_home)?;
-        return Ok((forks, CacheStatus::Fresh));
-    };
-
-    // Check if we should use cache or refresh
-    let cache_empty = cache.is_empty().unwrap_or(true);
-
-    if force_refresh || cache_empty {
-        // Fetch fresh data from GitHub
-        match fetch_forks_graphql(tool_home) {
-            Ok(forks) => {
-                // Save to cache
-                if let Err(e) = cache.save_forks(&forks) {
-                    eprintln!("Warning: Failed to save to cache: {e}");
-                }
-                if let Err(e) = cache.set_last_full_sync(Utc::now()) {
-                    eprintln!("Warning: Failed to update last sync time: {e}");
-                }
-                Ok((forks, CacheStatus::Fresh))
-            }
-            Err(e) => {
-                // If fetch failed but we have cache, use it
-                if cache_empty {
-                    Err(e)
-                } else {
-                    eprintln!("Warning: GitHub fetch failed, using cache: {e}");
-                    let forks = cache.load_forks(tool_home)?;
-                    Ok((forks, CacheStatus::Offline))
-                }
-            }
+    let switch = app.switch_profile.take();
+    if switch.is_none() {
+        Unfinished {
+            failed,
+            attention,
+            total: synced + skipped + failed + attention,
+            action: "sync",
         }
-    } else {
-        // Load from cache
-        let forks = cache.load_forks(tool_home)?;
-
-        // Check if cache is stale (older than 24 hours)
-        let is_stale = cache
-            .last_full_sync()
-            .ok()
-            .flatten()
-            .is_none_or(|last_sync| {
-                let age = Utc::now() - last_sync;
-                age.num_hours() >= 24
-            });
-
-        let cache_status = if is_stale {
-            CacheStatus::Stale { refreshing: false }
-        } else {
-            CacheStatus::Fresh
-        };
-
-        Ok((forks, cache_status))
+        .check()?;
     }
+    Ok(switch)
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    mut events: Option<EventSink>,
+) -> Result<()> {
     let (tx, rx) = mpsc::channel::<SyncResult>();
 
+    start_disk_usage_scan(app.forks.clone(), tx.clone());
+    start_behind_lookup(app, &tx);
+
+    // A cache older than the TTL is shown right away and refreshed behind it
+    if app.cache_status == (CacheStatus::Stale { refreshing: false }) {
+        start_refresh(app, false, &tx);
+    }
+
     // Start syncing if mode is already Syncing (from --yes flag)
     if app.mode == Mode::Syncing {
-        let forks_to_sync = app.forks_to_sync();
-        start_syncing(forks_to_sync, app.dry_run, tx.clone());
+        start_batch(app, &tx);
     }
 
+    interrupt::install();
     loop {
         app.tick_spinner();
+        logging::flush_audit(app.store.as_deref());
+        api_cache::flush(app.store.as_deref());
+        if interrupt::requested() && !app.stopping {
+            if app.mode != Mode::Syncing {
+                return Ok(());
+            }
+            app.stop_batch();
+        }
 
         // Check for sync results
         while let Ok(result) = rx.try_recv() {
+            if let Some(events) = &mut events {
+                events.emit(&result);
+            }
             match result {
-                SyncResult::StatusUpdate(idx, status) => {
-                    if idx < app.statuses.len() {
-                        app.statuses[idx] = status;
+                SyncResult::StatusUpdate(id, status) => {
+                    if let Some(idx) = app.fork_index(&id) {
+                        app.set_status(idx, status);
                     }
                 }
-                SyncResult::ForkCloned(idx) => {
-                    if idx < app.forks.len() {
+                SyncResult::ForkCloned(id) => {
+                    if let Some(idx) = app.fork_index(&id) {
                         app.forks[idx].is_cloned = true;
                     }
                 }
-                SyncResult::ForkArchived(idx) => {
-                    app.remove_fork(idx);
+                SyncResult::ForkArchived(id) => {
+                    app.remove_fork_when_idle(id);
                     app.show_message("Fork archived!");
                 }
-                SyncResult::ForkDeleted(idx) => {
-                    app.remove_fork(idx);
+                SyncResult::ForkCreated(fork, clone_error) => app.add_new_fork(fork, clone_error),
+                SyncResult::ForkDeleted(id) => {
+                    app.remove_fork_when_idle(id);
                     app.show_message("Fork deleted!");
                 }
-                SyncResult::ForksRefreshed(new_forks) => {
-                    // Update forks list from background refresh
-                    let len = new_forks.len();
-                    app.forks = new_forks;
-                    app.statuses = vec![types::SyncStatus::Pending; len];
-                    app.selected = vec![false; len];
-                    app.update_search();
-                    app.cache_status = CacheStatus::Fresh;
-                    app.show_message("Forks refreshed!");
+                SyncResult::ForksRefreshed(fetched) => {
+                    app.forks_truncated = fetched.truncated;
+                    app.apply_refresh(fetched.forks);
+                    start_behind_lookup(app, &tx);
                 }
+                SyncResult::ForksUpdated(delta) => app.apply_delta(delta),
                 SyncResult::RefreshFailed(err) => {
+                    app.cache_status = CacheStatus::Offline;
                     app.show_message(&format!("Refresh failed: {err}"));
                 }
                 SyncResult::ActionableError(details) => {
                     app.show_error_popup(details);
                 }
+                SyncResult::LocalScanned(findings) => app.set_reconcile_findings(findings),
+                SyncResult::AuthRequired => app.show_auth_popup(),
+                SyncResult::Log(id, lines) => app.append_log(&id, lines),
+                SyncResult::ForceSynced(id, discarded) => app.report_force_sync(&id, discarded),
+                SyncResult::PullRequestOpened(id, url) => {
+                    app.append_log(&id, vec![format!("Pull request: {url}")]);
+                    app.add_toast(Toast::success(format!("PR opened: {url}")));
+                }
+                SyncResult::RisksChecked(id, result) => app.set_risks(&id, result),
+                SyncResult::UpstreamCommits(id, result) => app.set_upstream_commits(id, result),
+                SyncResult::GitStatus(id, result) => app.set_git_status(&id, result),
+                SyncResult::Preview(id, result) => app.set_preview(&id, result),
+                SyncResult::Plan(plans) => app.set_plan(plans),
+                SyncResult::Dirty(id, dirty) => app.set_dirty(id, dirty),
+                SyncResult::LocalWork(id, busy) => app.set_local_work(id, busy),
+                SyncResult::Stashed(stash) => app.record_stash(&stash),
+                SyncResult::BackedUp(backup) => app.record_backup(&backup),
+                SyncResult::Command(id, line) => app.set_command(&id, line),
+                SyncResult::VisibilityChanged(id, visibility) => {
+                    app.set_visibility(&id, visibility);
+                }
+                SyncResult::DiskUsage(id, size) => {
+                    app.disk_usage.insert(id, size);
+                }
+                SyncResult::Behind(counts) => app.behind = counts,
+            }
+        }
+
+        // Checked every tick: a batch can also end without a final message
+        // (e.g. when the remaining forks are skipped after an auth failure)
+        if app.is_all_done() && app.mode == Mode::Syncing {
+            if app.stopping {
+                return Ok(());
             }
-            if app.is_all_done() && app.mode == Mode::Syncing {
-                // Automatically reset and return to selecting mode
-                app.reset_for_next_round();
-                app.mode = Mode::Selecting;
+            // Automatically reset and return to selecting mode
+            app.reset_for_next_round();
+            app.mode = Mode::Selecting;
+            app.apply_pending_removals();
+            if let Some(new_forks) = app.pending_refresh.take() {
+                app.apply_refresh(new_forks);
             }
         }
 
+        // Long idle sessions shouldn't quietly work on hours-old data
+        if app.should_auto_refresh() {
+            app.last_activity = Instant::now();
+            start_refresh(app, false, &tx);
+        }
+
         terminal.draw(|f| ui::render(f, app))?;
 
+        start_lookups(app, &tx);
+
         if event::poll(Duration::from_millis(50))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                handle_mouse(app, mouse);
+            }
+            if let Event::Key(key) = event {
                 if key.kind != KeyEventKind::Press {
                     continue;
                 }
+                app.last_activity = Instant::now();
+                app.track_key(key.code);
+                if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                    if app.stopping {
+                        return Ok(());
+                    }
+                    interrupt::request();
+                    continue;
+                }
+                if matches!(app.mode, Mode::Selecting | Mode::Syncing) && handle_paging(app, key) {
+                    continue;
+                }
 
                 match &app.mode {
                     Mode::Selecting => {
@@ -261,14 +460,39 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()>
                             app.mode = Mode::Selecting;
                         }
                     }
-                    Mode::ErrorPopup => handle_error_popup(app, key.code),
+                    Mode::UsageOverlay => {
+                        if matches!(key.code, KeyCode::Char('U' | 'q') | KeyCode::Esc) {
+                            app.mode = Mode::Selecting;
+                        }
+                    }
+                    Mode::ChangesOverlay => {
+                        if matches!(key.code, KeyCode::Char('W' | 'q') | KeyCode::Esc) {
+                            app.mode = Mode::Selecting;
+                        }
+                    }
+                    Mode::LogOverlay => {
+                        if matches!(key.code, KeyCode::Char('v' | 'q') | KeyCode::Esc) {
+                            app.mode = Mode::Selecting;
+                        }
+                    }
+                    Mode::PreviewOverlay => handle_preview_overlay(app, key.code),
+                    Mode::AuditOverlay => handle_audit_overlay(app, key.code),
+                    Mode::PlanOverlay => handle_plan_overlay(app, key.code, &tx),
+                    Mode::ReconcileOverlay => handle_reconcile_overlay(app, key.code, &tx),
+                    Mode::RemindersOverlay => handle_reminders_overlay(app, key.code),
+                    Mode::StashesOverlay => handle_stashes_overlay(app, key.code),
+                    Mode::Input => handle_input_mode(app, key.code, &tx),
+                    Mode::ErrorPopup => handle_error_popup(app, key.code, &tx)?,
                     Mode::ConfirmModal => handle_confirm_modal(app, key.code, &tx),
-                    Mode::Syncing => match key.code {
-                        KeyCode::Char('q') => return Ok(()),
-                        KeyCode::Down | KeyCode::Char('j') => app.next(),
-                        KeyCode::Up | KeyCode::Char('k') => app.previous(),
-                        _ => {}
-                    },
+                    Mode::ProfilesOverlay => handle_profiles_overlay(app, key.code),
+                    Mode::Syncing => {
+                        if handle_syncing_mode(app, key.code, &tx) {
+                            return Ok(());
+                        }
+                    }
+                }
+                if app.switch_profile.is_some() {
+                    return Ok(());
                 }
             }
         }
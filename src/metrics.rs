@@ -0,0 +1,146 @@
+use crate::sync::clone_snapshot;
+use crate::types::{Fork, ForkStore};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// How the forks of a run ended.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Tally {
+    pub synced: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub attention: usize,
+}
+
+/// Write Prometheus metrics for `forks` and the run that just ended to `path`
+/// (`--metrics-file`), for `node_exporter`'s textfile collector. Behind counts
+/// come from each clone's refs as of its last fetch.
+pub fn write(path: &Path, forks: &[Fork], store: Option<&dyn ForkStore>, run: Tally) -> Result<()> {
+    let behind = forks
+        .iter()
+        .filter(|f| f.is_cloned)
+        .filter_map(|f| Some((f.full_name(), clone_snapshot(f).0?)))
+        .collect();
+    let last_synced = store.and_then(|s| s.last_synced().ok()).unwrap_or_default();
+    let text = render(forks, &behind, &last_synced, run, Utc::now());
+
+    // Write and rename, so the collector never reads a half-written file
+    let tmp = path.with_extension("prom.tmp");
+    std::fs::write(&tmp, text)
+        .and_then(|()| std::fs::rename(&tmp, path))
+        .with_context(|| format!("Failed to write metrics to {}", path.display()))
+}
+
+fn render(
+    forks: &[Fork],
+    behind: &HashMap<String, u32>,
+    last_synced: &HashMap<String, DateTime<Utc>>,
+    run: Tally,
+    now: DateTime<Utc>,
+) -> String {
+    let mut out = String::new();
+    let mut gauge = |name: &str, help: &str, samples: Vec<(String, String)>| {
+        let _ = writeln!(out, "# HELP repo_syncer_{name} {help}");
+        let _ = writeln!(out, "# TYPE repo_syncer_{name} gauge");
+        for (labels, value) in samples {
+            let _ = writeln!(out, "repo_syncer_{name}{labels} {value}");
+        }
+    };
+    let total = |value: usize| vec![(String::new(), value.to_string())];
+    let per_fork = |values: Vec<(&String, String)>| {
+        let mut samples: Vec<_> = values
+            .into_iter()
+            .map(|(id, value)| (format!("{{fork=\"{id}\"}}"), value))
+            .collect();
+        samples.sort();
+        samples
+    };
+
+    gauge(
+        "forks_total",
+        "Forks known to repo-syncer.",
+        total(forks.len()),
+    );
+    gauge(
+        "forks_cloned",
+        "Forks with a local clone.",
+        total(forks.iter().filter(|f| f.is_cloned).count()),
+    );
+    gauge(
+        "forks_behind",
+        "Clones behind upstream as of their last fetch.",
+        total(behind.values().filter(|&&n| n > 0).count()),
+    );
+    gauge(
+        "fork_behind_commits",
+        "Commits a clone's default branch is behind upstream.",
+        per_fork(behind.iter().map(|(id, n)| (id, n.to_string())).collect()),
+    );
+    gauge(
+        "syncs_succeeded",
+        "Forks synced in the last run.",
+        total(run.synced),
+    );
+    gauge(
+        "syncs_failed",
+        "Forks that failed in the last run.",
+        total(run.failed),
+    );
+    gauge(
+        "syncs_needs_attention",
+        "Forks that need attention after the last run.",
+        total(run.attention),
+    );
+    gauge(
+        "last_sync_timestamp_seconds",
+        "When each fork last synced successfully.",
+        per_fork(
+            last_synced
+                .iter()
+                .map(|(id, at)| (id, at.timestamp().to_string()))
+                .collect(),
+        ),
+    );
+    gauge(
+        "last_run_timestamp_seconds",
+        "When repo-syncer last wrote these metrics.",
+        vec![(String::new(), now.timestamp().to_string())],
+    );
+    out
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let behind = HashMap::from([("me/cargo".to_string(), 5), ("me/serde".to_string(), 0)]);
+        let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let last_synced = HashMap::from([("me/serde".to_string(), at)]);
+        let run = Tally {
+            synced: 1,
+            failed: 1,
+            ..Tally::default()
+        };
+        let text = render(&[], &behind, &last_synced, run, at);
+
+        assert!(
+            text.contains("# TYPE repo_syncer_forks_behind gauge\nrepo_syncer_forks_behind 1\n")
+        );
+        assert!(text.contains(
+            "repo_syncer_fork_behind_commits{fork=\"me/cargo\"} 5\n\
+             repo_syncer_fork_behind_commits{fork=\"me/serde\"} 0\n"
+        ));
+        assert!(text.contains("repo_syncer_syncs_failed 1\n"));
+        assert!(text
+            .contains("repo_syncer_last_sync_timestamp_seconds{fork=\"me/serde\"} 1700000000\n"));
+    }
+}
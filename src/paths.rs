@@ -0,0 +1,153 @@
+use crate::cli::Args;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Where the config file, cache database and log file live.
+///
+/// By default these follow the platform conventions (e.g.
+/// `~/.config/repo-syncer/config.toml` and `~/.cache/repo-syncer/forks.db`).
+/// `--data-dir` puts both in one directory, and `--portable` uses the
+/// directory containing the binary (for USB sticks and locked-down machines).
+/// Each profile gets its own database, `forks-<profile>.db`.
+#[derive(Debug, Clone)]
+pub struct Paths {
+    pub config_file: PathBuf,
+    pub db_file: PathBuf,
+}
+
+impl Paths {
+    pub fn resolve(args: &Args, profile: Option<&str>) -> Result<Self> {
+        let db_name = profile.map_or_else(|| "forks.db".to_string(), |p| format!("forks-{p}.db"));
+        if let Some(dir) = &args.data_dir {
+            return Ok(Self::in_dir(dir, &db_name));
+        }
+        if args.portable {
+            let exe = std::env::current_exe().context("Could not locate the executable")?;
+            let dir = exe.parent().context("Executable has no parent directory")?;
+            return Ok(Self::in_dir(dir, &db_name));
+        }
+
+        let config_dir = dirs::config_dir().context("Could not determine config directory")?;
+        let cache_dir = dirs::cache_dir().context("Could not determine cache directory")?;
+        Ok(Self {
+            config_file: config_dir.join("repo-syncer").join("config.toml"),
+            db_file: cache_dir.join("repo-syncer").join(db_name),
+        })
+    }
+
+    /// The default log file, `repo-syncer.log` next to the database.
+    pub fn log_file(&self) -> PathBuf {
+        self.db_file.with_file_name("repo-syncer.log")
+    }
+
+    /// The instance lock (`--single-instance`), next to the database.
+    pub fn lock_file(&self) -> PathBuf {
+        self.db_file.with_file_name("repo-syncer.lock")
+    }
+
+    /// Keep config and cache side by side in `dir`.
+    fn in_dir(dir: &Path, db_name: &str) -> Self {
+        Self {
+            config_file: dir.join("config.toml"),
+            db_file: dir.join(db_name),
+        }
+    }
+}
+
+/// The directories clones live in, laid out as `<root>/<owner>/<name>`.
+///
+/// The first root is the primary one: new clones go there unless the clone
+/// modal picks another, and forks that aren't cloned anywhere point there.
+#[derive(Debug, Clone)]
+pub struct ToolHomes {
+    roots: Vec<PathBuf>,
+}
+
+impl ToolHomes {
+    /// Roots from `--tool-home` (repeatable), else `tool_homes` in the config,
+    /// else `$HOME/dev/github.com`.
+    pub fn resolve(args: &[PathBuf], config: &[PathBuf]) -> Result<Self> {
+        let roots = if !args.is_empty() {
+            args.to_vec()
+        } else if !config.is_empty() {
+            config.iter().map(|p| expand_home(p)).collect()
+        } else {
+            let home = std::env::var("HOME").context("HOME environment variable not set")?;
+            vec![PathBuf::from(home).join("dev/github.com")]
+        };
+        Ok(Self::new(roots))
+    }
+
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        assert!(!roots.is_empty(), "at least one tool home is required");
+        Self { roots }
+    }
+
+    pub fn primary(&self) -> &Path {
+        &self.roots[0]
+    }
+
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Where `owner/name` is cloned: the first root that has it, else the
+    /// primary root. The flag says whether a clone exists.
+    pub fn locate(&self, owner: &str, name: &str) -> (PathBuf, bool) {
+        self.roots
+            .iter()
+            .map(|root| root.join(owner).join(name))
+            .find(|path| path.exists())
+            .map_or_else(
+                || (self.primary().join(owner).join(name), false),
+                |path| (path, true),
+            )
+    }
+
+    /// The root a clone path lives under.
+    pub fn root_of(&self, path: &Path) -> Option<&Path> {
+        self.roots
+            .iter()
+            .find(|root| path.starts_with(root))
+            .map(PathBuf::as_path)
+    }
+
+    /// A path relative to its root (for display), or the full path.
+    pub fn display(&self, path: &Path) -> String {
+        self.root_of(path)
+            .and_then(|root| path.strip_prefix(root).ok())
+            .map_or_else(|| path.display().to_string(), |p| p.display().to_string())
+    }
+}
+
+/// Expand a leading `~` to `$HOME`.
+pub fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_across_roots() {
+        let base = std::env::temp_dir().join(format!("repo-syncer-roots-{}", std::process::id()));
+        let (dev, work) = (base.join("dev"), base.join("work"));
+        std::fs::create_dir_all(work.join("me/cargo")).unwrap();
+
+        let homes = ToolHomes::new(vec![dev.clone(), work.clone()]);
+        assert_eq!(homes.locate("me", "cargo"), (work.join("me/cargo"), true));
+        assert_eq!(homes.locate("me", "serde"), (dev.join("me/serde"), false));
+        assert_eq!(homes.root_of(&work.join("me/cargo")), Some(work.as_path()));
+        assert_eq!(homes.display(&work.join("me/cargo")), "me/cargo");
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
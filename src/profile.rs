@@ -0,0 +1,61 @@
+use crate::config::Profile;
+use crate::github::host;
+use crate::tools;
+use anyhow::{bail, Context, Result};
+use std::ffi::OsString;
+
+/// Environment variables a profile sets for `gh` (and so every GitHub call).
+const GH_VARS: [&str; 3] = ["GH_HOST", "GH_TOKEN", "GH_ENTERPRISE_TOKEN"];
+
+/// The `gh` environment from before any profile was applied, so switching
+/// profiles starts from a clean slate.
+pub struct GhEnv(Vec<(&'static str, Option<OsString>)>);
+
+impl GhEnv {
+    pub fn capture() -> Self {
+        Self(
+            GH_VARS
+                .iter()
+                .map(|&var| (var, std::env::var_os(var)))
+                .collect(),
+        )
+    }
+
+    /// Point `gh` at the profile's host and account (or back at the defaults).
+    pub fn activate(&self, profile: Option<&Profile>) -> Result<()> {
+        for (var, value) in &self.0 {
+            match value {
+                Some(value) => std::env::set_var(var, value),
+                None => std::env::remove_var(var),
+            }
+        }
+        let Some(profile) = profile else {
+            return Ok(());
+        };
+
+        if let Some(host) = &profile.host {
+            std::env::set_var("GH_HOST", host);
+        }
+        if let Some(account) = &profile.account {
+            let host = host();
+            let output = tools::gh()
+                .args(["auth", "token", "--hostname", &host, "--user", account])
+                .output()
+                .context("Failed to run gh CLI. Is it installed?")?;
+            if !output.status.success() {
+                bail!(
+                    "No gh login for {account} on {host} (run `gh auth login -h {host}`): {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let var = if host == "github.com" {
+                "GH_TOKEN"
+            } else {
+                "GH_ENTERPRISE_TOKEN"
+            };
+            std::env::set_var(var, token);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use super::worktree::{git_ok, git_stdout};
+use crate::types::{BackupRecord, Fork, SyncResult};
+use chrono::Utc;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+/// Namespace of the refs keeping a branch's commits reachable before a sync
+/// resets or rebases it.
+pub(super) const BACKUP_REFS: &str = "refs/repo-syncer/backup";
+
+/// Before the branch checked out in `path` is reset or rebased onto
+/// `target`, back it up and report the backup so the cache can offer to
+/// undo the sync.
+pub(super) fn back_up_head(
+    path: &str,
+    target: &str,
+    fork: &Fork,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Result<(), String> {
+    if let Some(backup) = back_up(path, "HEAD", target, fork)? {
+        let _ = tx.send(SyncResult::BackedUp(backup));
+    }
+    Ok(())
+}
+
+/// Point a new backup ref at `branch` (a local branch, or `HEAD`) before
+/// it's reset or rebased onto `target`. Nothing is written when the branch
+/// is already contained in `target` (the update only fast-forwards it).
+pub(super) fn back_up(
+    path: &str,
+    branch: &str,
+    target: &str,
+    fork: &Fork,
+) -> Result<Option<BackupRecord>, String> {
+    let rev = if branch == "HEAD" {
+        branch.to_string()
+    } else {
+        format!("refs/heads/{branch}")
+    };
+    if git_ok(path, &["merge-base", "--is-ancestor", &rev, target]) {
+        return Ok(None);
+    }
+    let failed = || "couldn't write a backup ref".to_string();
+    let oid = git_stdout(path, &["rev-parse", "--verify", &rev]).ok_or_else(failed)?;
+    let branch = git_stdout(path, &["rev-parse", "--abbrev-ref", &rev]).ok_or_else(failed)?;
+    let created_at = Utc::now();
+    let ref_name = format!(
+        "{BACKUP_REFS}/{branch}/{}",
+        created_at.format("%Y%m%dT%H%M%S%.3fZ")
+    );
+    if !git_ok(path, &["update-ref", &ref_name, &oid]) {
+        return Err(failed());
+    }
+    Ok(Some(BackupRecord {
+        id: fork.full_name(),
+        path: PathBuf::from(path),
+        branch,
+        ref_name,
+        oid,
+        created_at,
+    }))
+}
+
+/// Undo a sync: put the backed-up branch back where it was. A checked-out
+/// branch is moved with `reset --keep`, which refuses to touch uncommitted
+/// changes. The backup ref itself is kept.
+pub fn restore_backup(backup: &BackupRecord) -> Result<(), String> {
+    let path = backup.path.to_string_lossy();
+    let target = git_stdout(
+        &path,
+        &["rev-parse", "--verify", "--quiet", &backup.ref_name],
+    )
+    .ok_or_else(|| format!("{} is gone", backup.ref_name))?;
+    let current = git_stdout(&path, &["rev-parse", "--abbrev-ref", "HEAD"]);
+    if current.as_deref() == Some(backup.branch.as_str()) {
+        if !git_ok(&path, &["reset", "--keep", &target]) {
+            return Err("uncommitted changes in the way; commit or stash them first".to_string());
+        }
+    } else if !git_ok(&path, &["branch", "--force", &backup.branch, &target]) {
+        return Err(format!(
+            "couldn't move {} (checked out elsewhere?)",
+            backup.branch
+        ));
+    }
+    Ok(())
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{commit, local_options, Fixture};
+    use super::super::fork::sync_single_fork;
+    use super::*;
+    use crate::config::{LocalUpdate, RepoConfig};
+
+    #[test]
+    fn test_undo_reset_with_backup_ref() {
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        commit(&fixture.clone, "NOTES.md", "local only");
+        let local_head = Fixture::rev(&fixture.clone, "HEAD");
+
+        let mut options = local_options();
+        let repo = RepoConfig {
+            update: Some(LocalUpdate::Reset),
+            ..RepoConfig::default()
+        };
+        options.repos.insert(fixture.fork().full_name(), repo);
+        let (tx, rx) = mpsc::channel();
+        sync_single_fork(&fixture.fork(), &options, &tx);
+        let results: Vec<_> = rx.try_iter().collect();
+        let backup = results
+            .iter()
+            .find_map(|result| match result {
+                SyncResult::BackedUp(backup) => Some(backup.clone()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(backup.oid, local_head);
+        assert_eq!(backup.branch, "main");
+        assert!(backup.ref_name.starts_with(BACKUP_REFS));
+
+        // The reset dropped the local commit; undoing brings it back
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "HEAD"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+        restore_backup(&backup).unwrap();
+        assert_eq!(Fixture::rev(&fixture.clone, "HEAD"), local_head);
+
+        // From another branch, the default branch is moved without checking it out
+        fixture.switch_clone_to("feature");
+        assert!(git_ok(
+            &fixture.clone.to_string_lossy(),
+            &["branch", "--force", "main", "HEAD~1"]
+        ));
+        restore_backup(&backup).unwrap();
+        assert_eq!(fixture.clone_branch(), "feature");
+        assert_eq!(Fixture::rev(&fixture.clone, "main"), local_head);
+    }
+}
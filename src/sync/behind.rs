@@ -0,0 +1,178 @@
+use super::remote::get_commits_behind;
+use crate::logging::LoggedOutput;
+use crate::token_api;
+use crate::tools;
+use crate::types::{Fork, SyncResult};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{mpsc, OnceLock};
+use std::thread;
+
+/// Forks compared per GraphQL query; GitHub caps how many nodes one query
+/// may touch.
+const FORKS_PER_QUERY: usize = 50;
+
+#[derive(Deserialize)]
+struct Response {
+    data: Option<HashMap<String, Option<Repository>>>,
+}
+
+#[derive(Deserialize)]
+struct Repository {
+    #[serde(rename = "ref")]
+    branch: Option<Branch>,
+}
+
+#[derive(Deserialize)]
+struct Branch {
+    compare: Option<Comparison>,
+}
+
+/// The fork's default branch (base) against upstream's (head): `aheadBy`
+/// is what upstream has that the fork doesn't.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Comparison {
+    ahead_by: u32,
+}
+
+/// How far behind upstream each of a sync batch's forks is, looked up for
+/// all of them with `commits_behind_batch` the first time a worker asks, so
+/// a batch costs a few GraphQL queries instead of a REST call per fork.
+#[derive(Debug)]
+pub struct BehindLookup {
+    forks: Vec<Fork>,
+    counts: OnceLock<HashMap<String, u32>>,
+}
+
+impl BehindLookup {
+    pub fn new(forks: Vec<Fork>) -> Self {
+        Self {
+            forks,
+            counts: OnceLock::new(),
+        }
+    }
+
+    /// How many commits `fork` is behind, from the batched lookup or, for
+    /// forks it missed (added to the batch later, or not comparable in
+    /// GraphQL), a REST call of their own.
+    pub fn get(&self, fork: &Fork) -> Option<u32> {
+        self.counts
+            .get_or_init(|| commits_behind_batch(&self.forks))
+            .get(&fork.full_name())
+            .copied()
+            .or_else(|| get_commits_behind(fork))
+    }
+}
+
+/// Look up how far behind upstream `forks` are in the background and send
+/// the counts back as one `SyncResult::Behind`, for the Behind column.
+pub fn fetch_behind_async(forks: Vec<Fork>, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        let _ = tx.send(SyncResult::Behind(commits_behind_batch(&forks)));
+    });
+}
+
+/// How many commits each GitHub fork in `forks` is behind its upstream, by
+/// `owner/name`. Forks whose query failed are left out.
+pub(super) fn commits_behind_batch(forks: &[Fork]) -> HashMap<String, u32> {
+    let forks: Vec<&Fork> = forks.iter().filter(|f| f.is_github_fork()).collect();
+    let mut counts = HashMap::new();
+    for chunk in forks.chunks(FORKS_PER_QUERY) {
+        match query_chunk(chunk) {
+            Ok(chunk_counts) => counts.extend(chunk_counts),
+            Err(e) => tracing::warn!("Batched behind-by lookup failed: {e}"),
+        }
+    }
+    counts
+}
+
+fn query_chunk(forks: &[&Fork]) -> Result<HashMap<String, u32>> {
+    let query = behind_query(forks);
+    let output = if token_api::enabled() {
+        token_api::graphql(&query, &serde_json::json!({}))
+            .context("Failed to run curl for GraphQL query")?
+    } else {
+        tools::gh()
+            .args(["api", "graphql", "-f", &format!("query={query}")])
+            .logged_output()
+            .context("Failed to run gh CLI for GraphQL query")?
+    };
+    // Forks that can't be compared come back as errors next to the rest
+    // of the data, so only a response without any is a failure
+    let response: Response =
+        serde_json::from_slice(&output.stdout).context("Failed to parse GraphQL response")?;
+    let data = response.data.with_context(|| {
+        format!(
+            "gh graphql failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    })?;
+    Ok(forks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, fork)| {
+            let comparison = data
+                .get(&format!("f{i}"))?
+                .as_ref()?
+                .branch
+                .as_ref()?
+                .compare
+                .as_ref()?;
+            Some((fork.full_name(), comparison.ahead_by))
+        })
+        .collect())
+}
+
+/// One query comparing each fork's default branch with its upstream's,
+/// aliased `f0`, `f1`, ... in the order of `forks`.
+fn behind_query(forks: &[&Fork]) -> String {
+    let fields: Vec<String> = forks
+        .iter()
+        .enumerate()
+        .map(|(i, fork)| {
+            format!(
+                "f{i}: repository(owner: {}, name: {}) {{ ref(qualifiedName: {}) {{ compare(headRef: {}) {{ aheadBy }} }} }}",
+                quote(&fork.owner),
+                quote(&fork.name),
+                quote(&format!("refs/heads/{}", fork.default_branch)),
+                quote(&format!("{}:{}", fork.parent_owner, fork.default_branch)),
+            )
+        })
+        .collect();
+    format!("query {{\n  {}\n}}", fields.join("\n  "))
+}
+
+/// A GraphQL string literal (the same escaping as JSON).
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fork(name: &str, branch: &str) -> Fork {
+        Fork {
+            default_branch: branch.to_string(),
+            ..Fork::test("me", name)
+        }
+    }
+
+    #[test]
+    fn test_behind_query_aliases_each_fork() {
+        let query = behind_query(&[&fork("serde", "master"), &fork("say \"hi\"", "main")]);
+        assert_eq!(
+            query,
+            r#"query {
+  f0: repository(owner: "me", name: "serde") { ref(qualifiedName: "refs/heads/master") { compare(headRef: "them:master") { aheadBy } } }
+  f1: repository(owner: "me", name: "say \"hi\"") { ref(qualifiedName: "refs/heads/main") { compare(headRef: "them:main") { aheadBy } } }
+}"#
+        );
+    }
+}
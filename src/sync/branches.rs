@@ -0,0 +1,154 @@
+use super::backup::back_up;
+use super::worktree::{git_ok, git_stdout};
+use super::SyncOptions;
+use crate::types::{Fork, SyncResult, SyncStatus};
+use std::sync::mpsc;
+
+/// Rebase the local branches that track the default branch (locally or on
+/// `origin`/`upstream`) onto its new tip, if enabled. Run with the default
+/// branch checked out and a clean tree; leaves it checked out again.
+///
+/// Each branch is backed up first (the refs are listed in the log; `u` only
+/// undoes the default branch). A conflicting rebase is aborted and the
+/// branch left as it was; those come back as a `NeedsAttention` status for
+/// the end of the sync.
+pub(super) fn rebase_tracking_branches(
+    fork: &Fork,
+    path: &str,
+    options: &SyncOptions,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Option<SyncStatus> {
+    if !options.rebase_branches {
+        return None;
+    }
+    let branch = &fork.default_branch;
+    let branches = branches_tracking(path, branch)?;
+    if branches.is_empty() {
+        return None;
+    }
+
+    let _ = tx.send(SyncResult::StatusUpdate(
+        fork.full_name(),
+        SyncStatus::Rebasing,
+    ));
+    let mut log = Vec::new();
+    let mut conflicts = Vec::new();
+    for feature in &branches {
+        match back_up(path, feature, branch, fork) {
+            Ok(Some(backup)) => log.push(format!("Backed up {feature} to {}", backup.ref_name)),
+            Ok(None) => {}
+            Err(reason) => {
+                log.push(format!("Not rebasing {feature}: {reason}"));
+                continue;
+            }
+        }
+        if git_ok(path, &["rebase", "--autostash", branch, feature]) {
+            log.push(format!("Rebased {feature} onto {branch}"));
+        } else {
+            let _ = git_ok(path, &["rebase", "--abort"]);
+            log.push(format!(
+                "Rebasing {feature} onto {branch} conflicted; left as it was"
+            ));
+            conflicts.push(feature.as_str());
+        }
+    }
+    let _ = git_ok(path, &["checkout", "-q", branch]);
+    let _ = tx.send(SyncResult::Log(fork.full_name(), log));
+
+    (!conflicts.is_empty()).then(|| {
+        SyncStatus::NeedsAttention(format!(
+            "Synced, but rebasing {} onto {branch} conflicted, so the rebase was aborted. \
+             Rebase by hand: git -C {path} rebase {branch} <branch>",
+            conflicts.join(", ")
+        ))
+    })
+}
+
+/// Local branches of the clone at `path` that `rebase_tracking_branches`
+/// rebases onto `branch`.
+pub(super) fn branches_tracking(path: &str, branch: &str) -> Option<Vec<String>> {
+    let listing = git_stdout(
+        path,
+        &[
+            "for-each-ref",
+            "--format=%(refname:short)\t%(upstream:short)\t%(worktreepath)",
+            "refs/heads",
+        ],
+    )?;
+    Some(tracking_branches(&listing, branch))
+}
+
+/// Branches in `git for-each-ref` output (name, upstream, worktree path)
+/// whose upstream is `branch` or a remote's `branch`. Branches checked out
+/// in a worktree are left alone.
+fn tracking_branches(listing: &str, branch: &str) -> Vec<String> {
+    let tracked = [
+        branch.to_string(),
+        format!("origin/{branch}"),
+        format!("upstream/{branch}"),
+    ];
+    listing
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let (name, upstream, worktree) = (fields.next()?, fields.next()?, fields.next());
+            let tracks = name != branch && tracked.iter().any(|t| t == upstream);
+            (tracks && worktree.unwrap_or_default().is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{commit, final_status, local_options, Fixture};
+    use super::super::fork::sync_single_fork;
+    use super::*;
+
+    #[test]
+    fn test_tracking_branches() {
+        let listing = "main\torigin/main\t/src/repo\n\
+                       feature\tmain\t\n\
+                       fix\torigin/main\t\n\
+                       other\torigin/other\t\n\
+                       wip\t\t\n\
+                       linked\tmain\t/src/repo-linked";
+        assert_eq!(tracking_branches(listing, "main"), vec!["feature", "fix"]);
+    }
+
+    #[test]
+    fn test_sync_rebases_tracking_branches() {
+        let fixture = Fixture::new();
+        let clone = fixture.clone.to_string_lossy().to_string();
+        fixture.switch_clone_to("feature");
+        assert!(git_ok(&clone, &["branch", "--set-upstream-to=main"]));
+        commit(&fixture.clone, "FEATURE.md", "feature work");
+        let feature_head = Fixture::rev(&fixture.clone, "feature");
+        assert!(git_ok(&clone, &["checkout", "-q", "main"]));
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+
+        let mut options = local_options();
+        options.rebase_branches = true;
+        let (tx, rx) = mpsc::channel();
+        sync_single_fork(&fixture.fork(), &options, &tx);
+
+        assert_eq!(final_status(&rx), SyncStatus::Synced(Some(1)));
+        assert_eq!(fixture.clone_branch(), "main");
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "feature~1"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+        let backups = git_stdout(
+            &clone,
+            &[
+                "for-each-ref",
+                "--format=%(objectname)",
+                "refs/repo-syncer/backup/feature",
+            ],
+        );
+        assert_eq!(backups, Some(feature_head));
+    }
+}
@@ -0,0 +1,43 @@
+use super::risks::gh_api_lines;
+use crate::types::{Fork, SyncResult, UpstreamCommits};
+use std::sync::mpsc;
+use std::thread;
+
+/// Commits listed in the details pane.
+const RECENT_COMMITS: usize = 10;
+
+/// Fetch in the background what syncing the fork would bring in: the
+/// upstream default branch compared with the fork's.
+pub fn fetch_upstream_commits_async(fork: Fork, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        let result = upstream_commits(&fork);
+        let _ = tx.send(SyncResult::UpstreamCommits(fork.full_name(), result));
+    });
+}
+
+fn upstream_commits(fork: &Fork) -> Result<UpstreamCommits, String> {
+    // First line: how far behind; then the newest commits, newest first
+    let lines = gh_api_lines(&[
+        &format!(
+            "repos/{}/{}/compare/{}:{branch}...{branch}",
+            fork.parent_owner,
+            fork.parent_name,
+            fork.owner,
+            branch = fork.default_branch
+        ),
+        "--jq",
+        &format!(
+            r#".ahead_by, (.commits | reverse | .[:{RECENT_COMMITS}][] | "\(.sha[0:7]) \(.commit.message | split("\n")[0])")"#
+        ),
+    ])?;
+    let (total, recent) = lines
+        .split_first()
+        .ok_or_else(|| "Empty response from GitHub".to_string())?;
+    Ok(UpstreamCommits {
+        total: total
+            .trim()
+            .parse()
+            .map_err(|_| format!("Unexpected count: {total}"))?,
+        recent: recent.to_vec(),
+    })
+}
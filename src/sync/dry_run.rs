@@ -0,0 +1,163 @@
+use super::preview::peek_incoming_changes;
+use super::route::{route, Route};
+use super::SyncOptions;
+use crate::config::LocalUpdate;
+use crate::types::{Fork, SyncResult, SyncStatus};
+use std::sync::mpsc;
+
+/// Dry run of a fork's sync: the same `route` a real sync takes (opt-out,
+/// uncommitted changes, checked-out branch, unpushed commits) plus how far
+/// behind, reported as what it would do, e.g. `Skipped("would stash, check
+/// out main, pull 3 commits")`. Nothing was synced, so it never reports
+/// `Synced`. Clones fetch upstream to count incoming commits, which only
+/// moves remote-tracking refs (or `FETCH_HEAD`, for a clone that doesn't
+/// have its `upstream` remote yet: adding it is reported, not done).
+pub(super) fn dry_run_sync(
+    fork: &Fork,
+    options: &SyncOptions,
+    tx: &mpsc::Sender<SyncResult>,
+) -> SyncStatus {
+    let sync = match route(fork, options) {
+        Route::Skip(status) => return status,
+        Route::Remote => return would(github_step(options.commits_behind(fork)).as_slice()),
+        Route::Mirror { on_github } => {
+            let mut plan: Vec<String> = on_github
+                .then(|| github_step(options.commits_behind(fork)))
+                .flatten()
+                .into_iter()
+                .collect();
+            plan.push("fetch the mirror".to_string());
+            return would(&plan);
+        }
+        Route::Clone(sync) => sync,
+    };
+
+    let mut plan = Vec::new();
+    if sync.add_upstream.is_some() {
+        plan.push("add upstream remote".to_string());
+    }
+    if sync.stash {
+        plan.push("stash".to_string());
+    }
+    if sync.switch {
+        plan.push(format!("check out {}", fork.default_branch));
+    }
+
+    let _ = tx.send(SyncResult::StatusUpdate(
+        fork.full_name(),
+        SyncStatus::Fetching,
+    ));
+    let incoming = match peek_incoming_changes(fork, sync.add_upstream.as_deref()) {
+        Ok(incoming) => incoming,
+        Err(e) => return SyncStatus::Failed(e),
+    };
+    if !incoming.commits.is_empty() {
+        let count = incoming.commits.len();
+        let how = match sync.update {
+            Some(LocalUpdate::Reset) => " (reset)",
+            Some(LocalUpdate::Rebase) => " (rebase)",
+            _ => "",
+        };
+        let step = format!(
+            "pull {count} commit{}{how}",
+            if count == 1 { "" } else { "s" }
+        );
+        let mut log = vec![format!("Dry run: would {step}")];
+        log.extend(incoming.commits);
+        let _ = tx.send(SyncResult::Log(fork.full_name(), log));
+        plan.push(step);
+    }
+    would(&plan)
+}
+
+/// What `gh repo sync` would bring into the fork, given how far behind
+/// GitHub says it is (`None` if that's unknown). Nothing when up to date.
+fn github_step(behind: Option<u32>) -> Option<String> {
+    match behind {
+        Some(0) => None,
+        Some(n) => Some(format!(
+            "sync {n} commit{} on GitHub",
+            if n == 1 { "" } else { "s" }
+        )),
+        None => Some("sync on GitHub".to_string()),
+    }
+}
+
+/// A dry run's outcome: the steps a sync would take, or "up to date".
+fn would(plan: &[String]) -> SyncStatus {
+    SyncStatus::Skipped(if plan.is_empty() {
+        "up to date".to_string()
+    } else {
+        format!("would {}", plan.join(", "))
+    })
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{local_options, Fixture};
+    use super::super::worktree::git_ok;
+    use super::*;
+    use crate::types::DIRTY;
+
+    #[test]
+    fn test_dry_run_reports_plan() {
+        let fixture = Fixture::new();
+        let options = SyncOptions {
+            dry_run: true,
+            ..local_options()
+        };
+        let (tx, _rx) = mpsc::channel();
+        let plan = |fixture: &Fixture| dry_run_sync(&fixture.fork(), &options, &tx);
+        assert_eq!(
+            plan(&fixture),
+            SyncStatus::Skipped("up to date".to_string())
+        );
+
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        fixture.upstream_commit("CHANGELOG.md", "1.2");
+        fixture.switch_clone_to("feature");
+        std::fs::write(fixture.clone.join("README.md"), "work in progress").unwrap();
+        let head = Fixture::rev(&fixture.clone, "HEAD");
+        assert_eq!(
+            plan(&fixture),
+            SyncStatus::Skipped("would stash, check out main, pull 2 commits".to_string())
+        );
+        // Read-only: still on the branch, with the changes in place
+        assert_eq!(fixture.clone_branch(), "feature");
+        assert_eq!(Fixture::rev(&fixture.clone, "HEAD"), head);
+        assert!(!fixture.clone_has_stash());
+
+        let options = SyncOptions {
+            skip_dirty: true,
+            ..options
+        };
+        assert_eq!(
+            dry_run_sync(&fixture.fork(), &options, &tx),
+            SyncStatus::Skipped(DIRTY.to_string())
+        );
+    }
+
+    #[test]
+    fn test_dry_run_leaves_upstream_remote_unadded() {
+        let fixture = Fixture::new();
+        let clone = fixture.clone.to_string_lossy().to_string();
+        assert!(git_ok(&clone, &["remote", "remove", "upstream"]));
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+
+        let options = SyncOptions {
+            dry_run: true,
+            clone_url_template: Some(fixture.upstream.to_string_lossy().to_string()),
+            ..local_options()
+        };
+        let (tx, _rx) = mpsc::channel();
+        assert_eq!(
+            dry_run_sync(&fixture.fork(), &options, &tx),
+            SyncStatus::Skipped("would add upstream remote, pull 1 commit".to_string())
+        );
+        assert!(!git_ok(&clone, &["remote", "get-url", "upstream"]));
+    }
+}
@@ -0,0 +1,222 @@
+//! Throwaway git repositories for sync tests: a bare `upstream`, a bare
+//! `fork` cloned from it, and a local clone of the fork with an `upstream`
+//! remote, all under one temporary directory. `stub_gh` stands in for `gh`
+//! against them.
+
+use super::worktree::{git_ok, git_stdout};
+use super::SyncOptions;
+use crate::config::Hooks;
+use crate::types::{Fork, SyncResult, SyncStatus};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use tempfile::TempDir;
+
+pub(super) struct Fixture {
+    _dir: TempDir,
+    /// Working copy used to push new commits to the upstream.
+    seed: PathBuf,
+    pub upstream: PathBuf,
+    /// The fork on "GitHub" (bare).
+    origin: PathBuf,
+    pub clone: PathBuf,
+}
+
+/// Run git in `path`, panicking with its stderr on failure.
+fn run_git(path: &Path, args: &[&str]) {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .output()
+        .expect("git is installed");
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Commit `file` with `contents` in the working copy at `path`.
+pub(super) fn commit(path: &Path, file: &str, contents: &str) {
+    std::fs::write(path.join(file), contents).unwrap();
+    run_git(path, &["add", file]);
+    run_git(path, &["commit", "-q", "-m", &format!("Update {file}")]);
+}
+
+/// Configure a committer so commits and stashes work without a global config.
+fn set_identity(path: &Path) {
+    run_git(path, &["config", "user.name", "Fixture"]);
+    run_git(path, &["config", "user.email", "fixture@example.com"]);
+}
+
+impl Fixture {
+    /// Upstream and fork with one commit on `main`, and a clean clone of the
+    /// fork on `main`.
+    pub fn new() -> Self {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().to_path_buf();
+        let (seed, upstream, fork, clone) = (
+            root.join("seed"),
+            root.join("upstream.git"),
+            root.join("fork.git"),
+            root.join("clone"),
+        );
+
+        run_git(&root, &["init", "-q", "-b", "main", seed.to_str().unwrap()]);
+        set_identity(&seed);
+        let fixture = Self {
+            _dir: dir,
+            seed,
+            upstream,
+            origin: fork.clone(),
+            clone,
+        };
+        commit(&fixture.seed, "README.md", "initial");
+
+        run_git(
+            &root,
+            &[
+                "clone",
+                "-q",
+                "--bare",
+                fixture.seed.to_str().unwrap(),
+                "upstream.git",
+            ],
+        );
+        run_git(
+            &fixture.seed,
+            &["remote", "add", "origin", "../upstream.git"],
+        );
+        run_git(
+            &root,
+            &["clone", "-q", "--bare", "upstream.git", "fork.git"],
+        );
+        run_git(&root, &["clone", "-q", fork.to_str().unwrap(), "clone"]);
+        set_identity(&fixture.clone);
+        run_git(
+            &fixture.clone,
+            &[
+                "remote",
+                "add",
+                "upstream",
+                fixture.upstream.to_str().unwrap(),
+            ],
+        );
+        fixture
+    }
+
+    /// Land a new commit on the upstream's `main`.
+    pub fn upstream_commit(&self, file: &str, contents: &str) {
+        commit(&self.seed, file, contents);
+        run_git(&self.seed, &["push", "-q", "origin", "main"]);
+    }
+
+    /// Tag the upstream's `main` with an annotated tag `name`.
+    pub fn upstream_tag(&self, name: &str) {
+        run_git(&self.seed, &["tag", "-a", name, "-m", name]);
+        run_git(&self.seed, &["push", "-q", "origin", name]);
+    }
+
+    /// The commit `rev` points at in `repo`.
+    pub fn rev(repo: &Path, rev: &str) -> String {
+        git_stdout(&repo.to_string_lossy(), &["rev-parse", rev]).unwrap()
+    }
+
+    /// The branch checked out in the clone.
+    pub fn clone_branch(&self) -> String {
+        git_stdout(
+            &self.clone.to_string_lossy(),
+            &["rev-parse", "--abbrev-ref", "HEAD"],
+        )
+        .unwrap()
+    }
+
+    /// Whether the clone has stash entries left behind.
+    pub fn clone_has_stash(&self) -> bool {
+        !git_stdout(&self.clone.to_string_lossy(), &["stash", "list"])
+            .unwrap()
+            .is_empty()
+    }
+
+    /// Check out a new `branch` in the clone.
+    pub fn switch_clone_to(&self, branch: &str) {
+        assert!(git_ok(
+            &self.clone.to_string_lossy(),
+            &["checkout", "-q", "-b", branch]
+        ));
+    }
+
+    /// Write a `gh` stand-in next to the repositories and return its path:
+    /// `gh repo sync` fast-forwards the fork's `main` to upstream's, and
+    /// anything else fails (so lookups like `gh api` come back empty).
+    pub fn stub_gh(&self) -> PathBuf {
+        self.write_gh(&format!(
+            "if [ \"$1 $2\" = \"repo sync\" ]; then\n  exec git -C '{}' fetch -q '{}' main:main\nfi\necho \"unexpected gh $*\" >&2\nexit 1\n",
+            self.origin.display(),
+            self.upstream.display(),
+        ))
+    }
+
+    /// Write a `gh` stand-in that prints `stderr` and fails.
+    pub fn failing_gh(&self, stderr: &str) -> PathBuf {
+        self.write_gh(&format!("echo '{stderr}' >&2\nexit 1\n"))
+    }
+
+    fn write_gh(&self, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = self.seed.with_file_name("gh");
+        // `--version` works, so gh counts as installed (see `token_api`)
+        let version = "[ \"$1\" = --version ] && { echo 'gh version 0.0.0 (stub)'; exit 0; }";
+        std::fs::write(&path, format!("#!/bin/sh\n{version}\n{body}")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    pub fn fork(&self) -> Fork {
+        Fork {
+            local_path: self.clone.clone(),
+            is_cloned: true,
+            ..Fork::test("me", "project")
+        }
+    }
+}
+
+/// Options for an offline sync, which only uses git against the fixtures.
+pub(super) fn local_options() -> SyncOptions {
+    SyncOptions {
+        dry_run: false,
+        local_only: true,
+        auto_force: false,
+        clone_url_template: None,
+        owner_limits: HashMap::new(),
+        update_submodules: false,
+        mirror_tags: false,
+        rebase_branches: false,
+        skip_dirty: false,
+        reset_fallback: false,
+        hooks: Hooks::default(),
+        repos: HashMap::new(),
+        behind: None,
+    }
+}
+
+/// Options for a sync that goes through `gh` (with `tools` pointing `gh` at
+/// a stand-in like `Fixture::stub_gh`).
+pub(super) fn gh_options() -> SyncOptions {
+    SyncOptions {
+        local_only: false,
+        ..local_options()
+    }
+}
+
+/// The last status sent on `rx`.
+pub(super) fn final_status(rx: &mpsc::Receiver<SyncResult>) -> SyncStatus {
+    rx.try_iter()
+        .filter_map(|result| match result {
+            SyncResult::StatusUpdate(_, status) => Some(status),
+            _ => None,
+        })
+        .last()
+        .expect("sync reported a status")
+}
@@ -0,0 +1,203 @@
+use super::backup::back_up_head;
+use super::fork::lock_clone;
+use super::opt_out::opt_out_reason;
+use super::remote::{get_commits_behind, gh_repo_sync};
+use super::worktree::{git_ok, git_stdout, Worktree};
+use crate::types::{Fork, SyncResult, SyncStatus, DIRTY};
+use std::sync::mpsc;
+use std::thread;
+
+/// Whether `gh repo sync` failed because the fork has diverged from upstream
+/// (i.e. it would need `--force`).
+pub(super) fn is_divergence_error(stderr: &str) -> bool {
+    stderr.contains("diverg") || stderr.contains("--force")
+}
+
+/// Check whether force-syncing `branch` would lose nothing: every commit the
+/// fork has beyond upstream is already contained upstream. Uses the local
+/// clone at `path` and its `origin`/`upstream` remotes.
+///
+/// Two cases count as contained:
+/// - each fork commit has a patch-equivalent commit upstream (rebase merges),
+/// - merging the fork into upstream leaves upstream's tree unchanged
+///   (squash merges).
+pub(super) fn fork_changes_in_upstream(path: &str, branch: &str) -> bool {
+    if !git_ok(path, &["fetch", "origin", branch]) || !git_ok(path, &["fetch", "upstream", branch])
+    {
+        return false;
+    }
+    let fork_ref = format!("origin/{branch}");
+    let upstream_ref = format!("upstream/{branch}");
+
+    if let Some(cherry) = git_stdout(path, &["cherry", &upstream_ref, &fork_ref]) {
+        if cherry.lines().all(|line| line.starts_with('-')) {
+            return true;
+        }
+    }
+
+    let merged_tree = git_stdout(
+        path,
+        &["merge-tree", "--write-tree", &upstream_ref, &fork_ref],
+    );
+    let upstream_tree = git_stdout(path, &["rev-parse", &format!("{upstream_ref}^{{tree}}")]);
+    match (merged_tree, upstream_tree) {
+        (Some(merged), Some(upstream)) => merged.lines().next() == Some(upstream.as_str()),
+        _ => false,
+    }
+}
+
+/// Force-sync a diverged fork once the user has confirmed it: `gh repo sync
+/// --force`, then reset the clone's default branch to the new fork head.
+/// With `skip_dirty`, a clone with uncommitted changes is left alone.
+pub fn force_sync_async(fork: Fork, dry_run: bool, skip_dirty: bool, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        let _span = tracing::info_span!("force sync", fork = %fork.full_name()).entered();
+        let send = |status: SyncStatus| {
+            let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+        };
+
+        if let Some(reason) = opt_out_reason(&fork.local_path) {
+            send(SyncStatus::Skipped(reason));
+            return;
+        }
+        if skip_dirty
+            && fork.local_path.exists()
+            && Worktree::inspect(fork.local_path.to_string_lossy().to_string())
+                .is_ok_and(|w| w.is_dirty)
+        {
+            send(SyncStatus::Skipped(DIRTY.to_string()));
+            return;
+        }
+        send(SyncStatus::Syncing);
+        if dry_run {
+            send(dry_run_force_sync(&fork));
+            return;
+        }
+
+        let _lock = match lock_clone(&fork) {
+            Ok(lock) => lock,
+            Err(status) => {
+                send(status);
+                return;
+            }
+        };
+
+        let commits_behind = get_commits_behind(&fork);
+        match gh_repo_sync(&fork, true) {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => {
+                send(SyncStatus::failed(&String::from_utf8_lossy(&output.stderr)));
+                return;
+            }
+            Err(e) => {
+                send(SyncStatus::failed(&e.to_string()));
+                return;
+            }
+        }
+
+        let discarded = if fork.local_path.exists() {
+            match reset_clone(&fork, &send, &tx) {
+                Ok(discarded) => discarded,
+                Err(status) => {
+                    send(status);
+                    return;
+                }
+            }
+        } else {
+            0
+        };
+        send(SyncStatus::Synced(commits_behind));
+        let _ = tx.send(SyncResult::ForceSynced(fork.full_name(), discarded));
+    });
+}
+
+/// What a confirmed force-sync would do, counting the local commits its
+/// reset would discard (as of the last fetch).
+fn dry_run_force_sync(fork: &Fork) -> SyncStatus {
+    let branch = &fork.default_branch;
+    let discarded = fork
+        .local_path
+        .exists()
+        .then(|| {
+            git_stdout(
+                &fork.local_path.to_string_lossy(),
+                &["rev-list", "--count", &format!("origin/{branch}..{branch}")],
+            )
+        })
+        .flatten()
+        .filter(|n| n != "0");
+    SyncStatus::Skipped(match discarded {
+        Some(n) => format!("would force-sync, discard {n} local commit(s)"),
+        None => "would force-sync".to_string(),
+    })
+}
+
+/// Reset the clone's default branch to the force-synced `origin`, keeping
+/// uncommitted changes and the checked-out branch. Returns how many local
+/// commits were discarded.
+fn reset_clone(
+    fork: &Fork,
+    send: &impl Fn(SyncStatus),
+    tx: &mpsc::Sender<SyncResult>,
+) -> Result<usize, SyncStatus> {
+    let branch = &fork.default_branch;
+    let mut worktree = Worktree::for_branch(fork.local_path.to_string_lossy().to_string(), branch)?;
+    worktree.stash_and_checkout(branch, false, send)?;
+    if let Some(stash) = worktree.stash_record(fork) {
+        let _ = tx.send(SyncResult::Stashed(stash));
+    }
+    let path = worktree.path.clone();
+
+    send(SyncStatus::Fetching);
+    let origin_ref = format!("origin/{branch}");
+    if !git_ok(&path, &["fetch", "origin"]) {
+        worktree.abort();
+        return Err(SyncStatus::Failed("fetch origin failed".to_string()));
+    }
+    let discarded = git_stdout(
+        &path,
+        &["rev-list", "--count", &format!("{origin_ref}..HEAD")],
+    )
+    .and_then(|n| n.parse().ok())
+    .unwrap_or(0);
+    if let Err(reason) = back_up_head(&path, &origin_ref, fork, tx) {
+        worktree.abort();
+        return Err(SyncStatus::Failed(reason));
+    }
+    if !git_ok(&path, &["reset", "--hard", &origin_ref]) {
+        worktree.abort();
+        return Err(SyncStatus::Failed("reset failed".to_string()));
+    }
+
+    worktree.restore(send)?;
+    Ok(discarded)
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{commit, Fixture};
+    use super::*;
+
+    #[test]
+    fn test_reset_clone_reports_discarded_commits() {
+        let fixture = Fixture::new();
+        commit(&fixture.clone, "NOTES.md", "local only");
+        commit(&fixture.clone, "TODO.md", "local only");
+        std::fs::write(fixture.clone.join("README.md"), "work in progress").unwrap();
+
+        assert_eq!(
+            reset_clone(&fixture.fork(), &|_| {}, &mpsc::channel().0),
+            Ok(2)
+        );
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "HEAD"),
+            Fixture::rev(&fixture.clone, "origin/main")
+        );
+        let readme = std::fs::read_to_string(fixture.clone.join("README.md")).unwrap();
+        assert_eq!(readme, "work in progress");
+    }
+}
@@ -0,0 +1,308 @@
+use super::dry_run::dry_run_sync;
+use super::local::sync_clone;
+use super::mirror::sync_mirror;
+use super::remote::sync_fork_remote;
+use super::route::{route, Route};
+use super::worktree::git;
+use super::{SyncOptions, SyncOutcome};
+use crate::lock::{repo_lock_path, FileLock};
+use crate::types::{Fork, SyncResult, SyncStatus};
+use std::sync::mpsc;
+
+/// Lock `fork`'s clone for the rest of the sync, so concurrent repo-syncer
+/// processes don't race on it. `Ok(None)` without a clone, or if the lock
+/// file can't be written (the sync goes ahead unlocked); `Skipped` if another
+/// process holds it.
+pub(super) fn lock_clone(fork: &Fork) -> Result<Option<FileLock>, SyncStatus> {
+    if !fork.local_path.exists() {
+        return Ok(None);
+    }
+    let path = repo_lock_path(&fork.local_path);
+    match FileLock::try_acquire(&path) {
+        Ok(Some(lock)) => Ok(Some(lock)),
+        Ok(None) => Err(SyncStatus::Skipped(FileLock::held_by(&path))),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), "Failed to lock clone: {e}");
+            Ok(None)
+        }
+    }
+}
+
+/// Whether the clone's default branch has commits the fork on GitHub
+/// (`origin`'s default branch) doesn't. Work on other branches doesn't
+/// count: the sync only moves the default branch.
+pub(super) fn has_unpushed_commits(path: &str, fork: &Fork) -> bool {
+    let branch = &fork.default_branch;
+    git(
+        path,
+        &[
+            "log",
+            &format!("origin/{branch}..refs/heads/{branch}"),
+            "--oneline",
+        ],
+    )
+    .is_ok_and(|output| !output.stdout.is_empty())
+}
+
+/// Sync a single fork with its upstream (runs in caller's thread context),
+/// the way `route` decides. Works for both cloned and uncloned forks:
+/// - Uncloned: syncs the GitHub fork remotely via `gh repo sync`
+/// - Cloned: syncs GitHub fork AND updates local clone
+///
+/// Returns `AuthRequired` (with the fork reset to Pending) if `gh` isn't
+/// logged in, so the queue can pause and retry it later.
+pub fn sync_single_fork(
+    fork: &Fork,
+    options: &SyncOptions,
+    tx: &mpsc::Sender<SyncResult>,
+) -> SyncOutcome {
+    let _span = tracing::info_span!("sync", fork = %fork.full_name()).entered();
+    let send = |status: SyncStatus| {
+        let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+    };
+
+    send(SyncStatus::Checking);
+
+    // Dry run: run the read-only checks and report what would happen
+    if options.dry_run {
+        send(dry_run_sync(fork, options, tx));
+        return SyncOutcome::Done;
+    }
+
+    // Another repo-syncer may be working in this clone
+    let _lock = match lock_clone(fork) {
+        Ok(lock) => lock,
+        Err(status) => {
+            send(status);
+            return SyncOutcome::Done;
+        }
+    };
+
+    match route(fork, options) {
+        Route::Skip(status) => {
+            send(status);
+            SyncOutcome::Done
+        }
+        Route::Mirror { .. } => sync_mirror(fork, options, tx),
+        Route::Remote => sync_fork_remote(fork, options, tx),
+        Route::Clone(sync) => sync_clone(fork, *sync, options, tx),
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{commit, final_status, gh_options, local_options, Fixture};
+    use super::*;
+    use crate::config::{LocalUpdate, RepoConfig};
+    use crate::tools::{self, Tools};
+    use crate::types::DIRTY;
+    use std::path::PathBuf;
+
+    fn sync(fixture: &Fixture) -> SyncStatus {
+        let (tx, rx) = mpsc::channel();
+        let outcome = sync_single_fork(&fixture.fork(), &local_options(), &tx);
+        assert_eq!(outcome, SyncOutcome::Done);
+        final_status(&rx)
+    }
+
+    #[test]
+    fn test_sync_clean_clone() {
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+
+        assert_eq!(sync(&fixture), SyncStatus::Synced(Some(1)));
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "HEAD"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+    }
+
+    #[test]
+    fn test_sync_dirty_clone_keeps_changes() {
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        std::fs::write(fixture.clone.join("README.md"), "work in progress").unwrap();
+
+        assert_eq!(sync(&fixture), SyncStatus::Synced(Some(1)));
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "HEAD"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+        let readme = std::fs::read_to_string(fixture.clone.join("README.md")).unwrap();
+        assert_eq!(readme, "work in progress");
+        assert!(!fixture.clone_has_stash());
+    }
+
+    #[test]
+    fn test_sync_dirty_clone_conflicting_with_upstream() {
+        let fixture = Fixture::new();
+        fixture.upstream_commit("README.md", "upstream edit");
+        std::fs::write(fixture.clone.join("README.md"), "work in progress").unwrap();
+
+        // git applies the autostash after the fast-forward, conflicts, and
+        // keeps the changes in the stash list
+        assert!(matches!(sync(&fixture), SyncStatus::NeedsAttention(_)));
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "HEAD"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+        assert!(fixture.clone_has_stash());
+    }
+
+    #[test]
+    fn test_skip_dirty_leaves_clone_alone() {
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        std::fs::write(fixture.clone.join("README.md"), "work in progress").unwrap();
+        let head = Fixture::rev(&fixture.clone, "HEAD");
+
+        let options = SyncOptions {
+            skip_dirty: true,
+            ..local_options()
+        };
+        let (tx, rx) = mpsc::channel();
+        sync_single_fork(&fixture.fork(), &options, &tx);
+        assert_eq!(final_status(&rx), SyncStatus::Skipped(DIRTY.to_string()));
+        assert_eq!(Fixture::rev(&fixture.clone, "HEAD"), head);
+        assert!(!fixture.clone_has_stash());
+    }
+
+    #[test]
+    fn test_sync_from_other_branch_returns_to_it() {
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        fixture.switch_clone_to("feature");
+
+        assert_eq!(sync(&fixture), SyncStatus::Synced(Some(1)));
+        assert_eq!(fixture.clone_branch(), "feature");
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "main"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+    }
+
+    #[test]
+    fn test_sync_cloned_fork_through_gh() {
+        // One test, since `tools` is global and each fixture has its own gh
+        let sync_with_gh = |fixture: &Fixture, gh: PathBuf| {
+            tools::configure(&Tools {
+                gh: Some(gh),
+                ..Tools::default()
+            });
+            let (tx, rx) = mpsc::channel();
+            let outcome = sync_single_fork(&fixture.fork(), &gh_options(), &tx);
+            tools::configure(&Tools::default());
+            assert_eq!(outcome, SyncOutcome::Done);
+            final_status(&rx)
+        };
+
+        // gh updates the fork, then the clone fast-forwards to it
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        assert!(matches!(
+            sync_with_gh(&fixture, fixture.stub_gh()),
+            SyncStatus::Synced(_)
+        ));
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "HEAD"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+
+        // Unpushed commits are left alone
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        commit(&fixture.clone, "NOTES.md", "local only");
+        let local_head = Fixture::rev(&fixture.clone, "HEAD");
+        assert_eq!(
+            sync_with_gh(&fixture, fixture.stub_gh()),
+            SyncStatus::Skipped("unpushed commits".to_string())
+        );
+        assert_eq!(Fixture::rev(&fixture.clone, "HEAD"), local_head);
+        assert_eq!(fixture.clone_branch(), "main");
+
+        // Commits on a feature branch don't hold the default branch back
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        fixture.switch_clone_to("feature");
+        commit(&fixture.clone, "NOTES.md", "feature work");
+        let feature_head = Fixture::rev(&fixture.clone, "HEAD");
+        assert!(matches!(
+            sync_with_gh(&fixture, fixture.stub_gh()),
+            SyncStatus::Synced(_)
+        ));
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "main"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+        assert_eq!(fixture.clone_branch(), "feature");
+        assert_eq!(Fixture::rev(&fixture.clone, "HEAD"), feature_head);
+
+        // A failed gh repo sync is reported with its stderr
+        let fixture = Fixture::new();
+        let gh = fixture.failing_gh("HTTP 422: Validation Failed");
+        assert_eq!(
+            sync_with_gh(&fixture, gh),
+            SyncStatus::Failed("HTTP 422: Validation Failed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_offline_sync_with_unpushed_commits_fails() {
+        // Offline there's no fork to compare with: the fast-forward refuses
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        commit(&fixture.clone, "NOTES.md", "local only");
+        let local_head = Fixture::rev(&fixture.clone, "HEAD");
+
+        assert_eq!(
+            sync(&fixture),
+            SyncStatus::Failed("not a fast-forward".to_string())
+        );
+        assert_eq!(Fixture::rev(&fixture.clone, "HEAD"), local_head);
+    }
+
+    #[test]
+    fn test_sync_strategy_per_repo() {
+        let sync_with = |fixture: &Fixture, update: LocalUpdate| {
+            let mut options = local_options();
+            let repo = RepoConfig {
+                update: Some(update),
+                ..RepoConfig::default()
+            };
+            options.repos.insert(fixture.fork().full_name(), repo);
+            let (tx, rx) = mpsc::channel();
+            sync_single_fork(&fixture.fork(), &options, &tx);
+            final_status(&rx)
+        };
+
+        // Rebase keeps the local commit on top of upstream
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        commit(&fixture.clone, "NOTES.md", "local only");
+        assert_eq!(
+            sync_with(&fixture, LocalUpdate::Rebase),
+            SyncStatus::Synced(Some(1))
+        );
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "HEAD~1"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+
+        // Reset drops it
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        commit(&fixture.clone, "NOTES.md", "local only");
+        assert_eq!(
+            sync_with(&fixture, LocalUpdate::Reset),
+            SyncStatus::Synced(Some(1))
+        );
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "HEAD"),
+            Fixture::rev(&fixture.upstream, "main")
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use super::SyncOptions;
+use crate::logging::LoggedOutput;
+use crate::types::{Fork, SyncResult, SyncStatus};
+use std::process::Command;
+use std::sync::mpsc;
+
+/// Run a hook command with `sh -c` in `path`, appending the command, its
+/// output and exit status to the fork's log. Returns the exit code on
+/// failure (`None` if the command couldn't be started).
+fn run_hook(
+    fork: &Fork,
+    path: &str,
+    label: &str,
+    command: &str,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Result<(), Option<i32>> {
+    let mut lines = vec![format!("$ {command}  ({label})")];
+    let outcome = match Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(path)
+        .logged_output()
+    {
+        Ok(output) => {
+            for stream in [&output.stdout, &output.stderr] {
+                lines.extend(String::from_utf8_lossy(stream).lines().map(str::to_string));
+            }
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(output.status.code())
+            }
+        }
+        Err(e) => {
+            lines.push(format!("failed to start: {e}"));
+            Err(None)
+        }
+    };
+    lines.push(match outcome {
+        Ok(()) => "exit 0".to_string(),
+        Err(Some(code)) => format!("exit {code}"),
+        Err(None) => "not run".to_string(),
+    });
+    let _ = tx.send(SyncResult::Log(fork.full_name(), lines));
+    outcome
+}
+
+/// Run the fork's pre-sync hook (if configured) in the clone, before
+/// anything is touched. A nonzero exit vetoes the sync: the fork is Skipped.
+pub(super) fn run_pre_sync_hook(
+    fork: &Fork,
+    path: &str,
+    options: &SyncOptions,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Result<(), SyncStatus> {
+    let Some(command) = options.hooks_for(fork).pre_sync else {
+        return Ok(());
+    };
+    let _ = tx.send(SyncResult::StatusUpdate(
+        fork.full_name(),
+        SyncStatus::RunningHook,
+    ));
+    run_hook(fork, path, "pre-sync", &command, tx).map_err(|code| match code {
+        Some(code) => SyncStatus::Skipped(format!("pre-sync hook vetoed (exit {code})")),
+        None => SyncStatus::Skipped("pre-sync hook couldn't start".to_string()),
+    })
+}
+
+/// Run the fork's post-sync hook (if configured) in the synced clone.
+/// The sync itself already succeeded, so a failing hook is reported as
+/// `NeedsAttention` rather than `Failed`.
+pub(super) fn run_post_sync_hook(
+    fork: &Fork,
+    path: &str,
+    options: &SyncOptions,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Result<(), SyncStatus> {
+    let Some(command) = options.hooks_for(fork).post_sync else {
+        return Ok(());
+    };
+    let _ = tx.send(SyncResult::StatusUpdate(
+        fork.full_name(),
+        SyncStatus::RunningHook,
+    ));
+    run_hook(fork, path, "post-sync", &command, tx).map_err(|code| {
+        let exit = code.map_or_else(|| "couldn't start".to_string(), |c| format!("exited {c}"));
+        SyncStatus::NeedsAttention(format!(
+            "Synced, but the post-sync hook `{command}` {exit}. Press v for its output."
+        ))
+    })
+}
@@ -0,0 +1,196 @@
+use super::backup::back_up_head;
+use super::branches::rebase_tracking_branches;
+use super::force::{fork_changes_in_upstream, is_divergence_error};
+use super::hooks::{run_post_sync_hook, run_pre_sync_hook};
+use super::remote::{add_upstream_remote, gh_repo_sync, sync_result};
+use super::route::CloneSync;
+use super::tags::mirror_upstream_tags;
+use super::worktree::{git_ok, git_stdout};
+use super::{SyncOptions, SyncOutcome};
+use crate::config::{ForceSync, LocalUpdate};
+use crate::github::is_auth_error;
+use crate::types::{Fork, SyncResult, SyncStatus, DIVERGED, NEEDS_PULL};
+use std::sync::mpsc;
+
+/// Update a clone the way `route` decided. Through the GitHub fork:
+/// `gh repo sync` (forced if it diverged and `force` allows), then `git
+/// fetch origin`. Otherwise (non-fork repos, and every clone in offline
+/// mode) straight from the `upstream` remote with plain git. Then the
+/// default branch is updated to it, and the clone put back as it was.
+///
+/// Returns `AuthRequired` (with the fork reset to Pending) if `gh` isn't
+/// logged in, so the queue can pause and retry it later.
+pub(super) fn sync_clone(
+    fork: &Fork,
+    sync: CloneSync,
+    options: &SyncOptions,
+    tx: &mpsc::Sender<SyncResult>,
+) -> SyncOutcome {
+    let send = |status: SyncStatus| {
+        let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+    };
+    let branch = &fork.default_branch;
+    let target = sync.target(branch);
+    let mut worktree = sync.worktree;
+    let path = worktree.path.clone();
+    // How far behind GitHub says the fork is, before it's synced
+    let mut commits_behind = if sync.on_github {
+        options.commits_behind(fork)
+    } else {
+        None
+    };
+
+    if let Err(status) = run_pre_sync_hook(fork, &path, options, tx) {
+        send(status);
+        return SyncOutcome::Done;
+    }
+    if let Some(url) = &sync.add_upstream {
+        add_upstream_remote(fork, url);
+    }
+
+    if let Err(status) = worktree.stash_and_checkout(branch, sync.autostash, &send) {
+        send(status);
+        return SyncOutcome::Done;
+    }
+    if let Some(stash) = worktree.stash_record(fork) {
+        let _ = tx.send(SyncResult::Stashed(stash));
+    }
+
+    if sync.on_github {
+        send(SyncStatus::Syncing);
+        let mut synced = sync_result(gh_repo_sync(fork, false));
+        if synced.as_ref().is_err_and(|err| is_auth_error(err)) {
+            // Put things back; the queue retries this fork after re-auth
+            worktree.abort();
+            send(SyncStatus::Pending);
+            return SyncOutcome::AuthRequired;
+        }
+        // A diverged fork whose extra commits are already upstream can be
+        // force-synced without losing anything
+        let diverged = synced.as_ref().is_err_and(|err| is_divergence_error(err));
+        let force = diverged
+            && match sync.force {
+                ForceSync::Never => false,
+                ForceSync::Auto => fork_changes_in_upstream(&path, branch),
+                ForceSync::Always => true,
+            };
+        if force {
+            synced = sync_result(gh_repo_sync(fork, true));
+        }
+        if let Err(err) = synced {
+            worktree.abort();
+            send(if is_divergence_error(&err) {
+                SyncStatus::Failed(DIVERGED.to_string())
+            } else {
+                SyncStatus::failed(&err)
+            });
+            return SyncOutcome::Done;
+        }
+        send(SyncStatus::Fetching);
+        let _ = git_ok(&path, &["fetch", "origin"]);
+    } else {
+        send(SyncStatus::Fetching);
+        if !git_ok(&path, &["fetch", "upstream"]) {
+            worktree.abort();
+            send(SyncStatus::Failed("fetch upstream failed".to_string()));
+            return SyncOutcome::Done;
+        }
+        commits_behind = git_stdout(&path, &["rev-list", "--count", &format!("HEAD..{target}")])
+            .and_then(|n| n.parse().ok());
+        send(SyncStatus::Syncing);
+    }
+
+    let update = sync.update.unwrap_or(LocalUpdate::FfOnly);
+    let updated =
+        update_branch(&path, &target, update, worktree.autostash(), fork, tx).or_else(|reason| {
+            match sync.fallback_reset {
+                None => Err(reason),
+                Some(false) => Err(NEEDS_PULL.to_string()),
+                // Catch up with a force-synced fork (a hard reset throws away
+                // local merge state)
+                Some(true) => {
+                    worktree
+                        .stash_instead_of_autostash(&send)
+                        .map_err(|_| "stash failed".to_string())?;
+                    if let Some(stash) = worktree.stash_record(fork) {
+                        let _ = tx.send(SyncResult::Stashed(stash));
+                    }
+                    update_branch(&path, &target, LocalUpdate::Reset, false, fork, tx)
+                }
+            }
+        });
+    if let Err(reason) = updated {
+        worktree.abort();
+        send(SyncStatus::Failed(reason));
+        return SyncOutcome::Done;
+    }
+    let attention = rebase_tracking_branches(fork, &path, options, tx);
+
+    // Restore the original branch and pop the stash if we changed them
+    if let Err(status) = worktree.restore(&send) {
+        send(status);
+        return SyncOutcome::Done;
+    }
+
+    if options.update_submodules {
+        if let Err(status) = worktree.update_submodules(&send) {
+            send(status);
+            return SyncOutcome::Done;
+        }
+    }
+
+    if let Err(status) = mirror_upstream_tags(fork, &path, options, tx) {
+        send(status);
+        return SyncOutcome::Done;
+    }
+
+    if let Err(status) = run_post_sync_hook(fork, &path, options, tx) {
+        send(status);
+        return SyncOutcome::Done;
+    }
+
+    send(attention.unwrap_or(SyncStatus::Synced(commits_behind)));
+    SyncOutcome::Done
+}
+
+/// Bring the checked-out branch up to `target` (e.g. `upstream/main`) the
+/// way the fork is configured to. A failed rebase is aborted.
+/// With `autostash`, uncommitted changes are carried across the merge or
+/// rebase (a reset can't carry them; stash those by hand). A reset or
+/// rebase first writes a backup ref, so the sync can be undone.
+pub(super) fn update_branch(
+    path: &str,
+    target: &str,
+    update: LocalUpdate,
+    autostash: bool,
+    fork: &Fork,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Result<(), String> {
+    if update != LocalUpdate::FfOnly {
+        back_up_head(path, target, fork, tx)?;
+    }
+    if git_ok(path, &update_args(target, update, autostash)) {
+        return Ok(());
+    }
+    Err(match update {
+        LocalUpdate::FfOnly => "not a fast-forward".to_string(),
+        LocalUpdate::Reset => "reset failed".to_string(),
+        LocalUpdate::Rebase => {
+            let _ = git_ok(path, &["rebase", "--abort"]);
+            "rebase conflict".to_string()
+        }
+    })
+}
+
+/// Arguments of the git command `update_branch` runs.
+pub(super) fn update_args(target: &str, update: LocalUpdate, autostash: bool) -> Vec<&str> {
+    let mut args = match update {
+        LocalUpdate::FfOnly => vec!["merge", "--ff-only", target],
+        LocalUpdate::Reset => vec!["reset", "--hard", target],
+        LocalUpdate::Rebase => vec!["rebase", target],
+    };
+    if autostash && update != LocalUpdate::Reset {
+        args.insert(1, "--autostash");
+    }
+    args
+}
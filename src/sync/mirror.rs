@@ -0,0 +1,108 @@
+use super::force::is_divergence_error;
+use super::remote::gh_repo_sync;
+use super::worktree::{git_ok, git_stdout};
+use super::{SyncOptions, SyncOutcome};
+use crate::github::is_auth_error;
+use crate::types::{Fork, SyncResult, SyncStatus, DIVERGED};
+use std::sync::mpsc;
+
+/// Sync a bare `--mirror` clone: update the GitHub fork (unless offline),
+/// then `git remote update --prune`. There's no working tree, so none of the
+/// stash/checkout steps (or hooks) apply.
+pub(super) fn sync_mirror(
+    fork: &Fork,
+    options: &SyncOptions,
+    tx: &mpsc::Sender<SyncResult>,
+) -> SyncOutcome {
+    let send = |status: SyncStatus| {
+        let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+    };
+    let path = fork.local_path.to_string_lossy();
+    let head = format!("refs/heads/{}", fork.default_branch);
+    let before = git_stdout(&path, &["rev-parse", &head]);
+
+    if fork.is_github_fork() && !options.local_only {
+        send(SyncStatus::Syncing);
+        match gh_repo_sync(fork, false) {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => {
+                let err = String::from_utf8_lossy(&output.stderr);
+                if is_auth_error(&err) {
+                    send(SyncStatus::Pending);
+                    return SyncOutcome::AuthRequired;
+                }
+                send(if is_divergence_error(&err) {
+                    SyncStatus::Failed(DIVERGED.to_string())
+                } else {
+                    SyncStatus::failed(&err)
+                });
+                return SyncOutcome::Done;
+            }
+            Err(e) => {
+                send(SyncStatus::failed(&e.to_string()));
+                return SyncOutcome::Done;
+            }
+        }
+    }
+
+    send(SyncStatus::Fetching);
+    if !git_ok(&path, &["remote", "update", "--prune"]) {
+        send(SyncStatus::Failed("remote update failed".to_string()));
+        return SyncOutcome::Done;
+    }
+    let pulled = before.and_then(|before| {
+        git_stdout(
+            &path,
+            &["rev-list", "--count", &format!("{before}..{head}")],
+        )?
+        .parse()
+        .ok()
+    });
+    send(SyncStatus::Synced(pulled));
+    SyncOutcome::Done
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{commit, final_status, local_options, Fixture};
+    use super::super::fork::sync_single_fork;
+    use super::*;
+    use crate::config::RepoConfig;
+
+    #[test]
+    fn test_sync_mirror_clone() {
+        let fixture = Fixture::new();
+        let clone = fixture.clone.to_string_lossy().to_string();
+        let origin = git_stdout(&clone, &["remote", "get-url", "origin"]).unwrap();
+        let mirror = fixture.clone.with_file_name("mirror.git");
+        assert!(git_ok(
+            &clone,
+            &["clone", "-q", "--mirror", &origin, mirror.to_str().unwrap()]
+        ));
+
+        // The fork moves on; the mirror catches up without a working tree
+        commit(&fixture.clone, "NOTES.md", "pushed to the fork");
+        assert!(git_ok(&clone, &["push", "-q", "origin", "main"]));
+
+        let mut fork = fixture.fork();
+        fork.local_path = mirror.clone();
+        let mut options = local_options();
+        let repo = RepoConfig {
+            mirror: true,
+            ..RepoConfig::default()
+        };
+        options.repos.insert(fork.full_name(), repo);
+        let (tx, rx) = mpsc::channel();
+        sync_single_fork(&fork, &options, &tx);
+
+        assert_eq!(final_status(&rx), SyncStatus::Synced(Some(1)));
+        assert_eq!(
+            Fixture::rev(&mirror, "main"),
+            Fixture::rev(&fixture.clone, "HEAD")
+        );
+    }
+}
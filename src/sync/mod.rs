@@ -0,0 +1,135 @@
+mod backup;
+mod behind;
+mod branches;
+mod commits;
+mod dry_run;
+#[cfg(test)]
+mod fixtures;
+mod force;
+mod fork;
+mod hooks;
+mod local;
+mod mirror;
+mod new_fork;
+mod ops;
+mod opt_out;
+mod plan;
+mod preview;
+mod pull_request;
+mod queue;
+mod remote;
+mod risks;
+mod route;
+mod stashes;
+mod status;
+mod tags;
+mod worktree;
+
+use crate::config::{ForceSync, Hooks, LocalUpdate, RepoConfig};
+use crate::types::Fork;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub use backup::restore_backup;
+pub use behind::{fetch_behind_async, BehindLookup};
+pub use commits::fetch_upstream_commits_async;
+pub use force::force_sync_async;
+pub use new_fork::fork_upstream_async;
+pub use ops::{archive_forks_async, clone_fork_async, delete_forks_async, set_visibility_async};
+pub use plan::plan_sync_async;
+pub use preview::preview_incoming_async;
+pub use pull_request::create_pr_async;
+pub use queue::{start_cloning, start_syncing, SyncQueue, SLOW_MODE_DELAY};
+pub use risks::check_risks_async;
+pub use stashes::{find_stash, restore_stash};
+pub use status::{
+    check_dirty_async, check_local_work_async, clone_snapshot, git_status_async, unsaved_work,
+};
+
+/// How a sync attempt ended, from the worker's point of view.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum SyncOutcome {
+    /// Finished (its final status has been sent)
+    Done,
+    /// `gh` isn't logged in; the fork should be retried after re-auth
+    AuthRequired,
+}
+
+/// Options that control how a sync batch behaves.
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)] // Independent on/off switches
+pub struct SyncOptions {
+    pub dry_run: bool,
+    /// Don't talk to GitHub: sync local clones from their `upstream` remote
+    /// with plain git and skip forks that aren't cloned (offline mode).
+    pub local_only: bool,
+    /// Force-sync diverged forks when their extra commits are already upstream
+    /// (unless a repo sets `force`).
+    pub auto_force: bool,
+    /// Clone URL template (`{owner}`/`{name}`); `None` uses `gh repo clone`.
+    pub clone_url_template: Option<String>,
+    /// Max concurrent syncs per upstream owner (e.g. `kubernetes` → 2).
+    pub owner_limits: HashMap<String, usize>,
+    /// Run `git submodule update --init --recursive` after a local sync.
+    pub update_submodules: bool,
+    /// Fetch upstream tags into clones and push the missing ones to the fork.
+    pub mirror_tags: bool,
+    /// Rebase local branches that track the default branch onto its new tip.
+    pub rebase_branches: bool,
+    /// Skip clones with uncommitted changes instead of stashing them.
+    pub skip_dirty: bool,
+    /// Hard-reset clones that can't be fast-forwarded (without a per-repo
+    /// `update`), instead of failing them with `NEEDS_PULL`.
+    pub reset_fallback: bool,
+    /// Global hooks, and per-repo hook overrides and strategy (by `owner/name`).
+    pub hooks: Hooks,
+    pub repos: HashMap<String, RepoConfig>,
+    /// How far behind the batch's forks are, looked up together (set by
+    /// `start_syncing`); without it each fork makes its own REST call.
+    pub behind: Option<Arc<BehindLookup>>,
+}
+
+impl SyncOptions {
+    /// How many commits `fork` is behind upstream, before syncing it.
+    pub(crate) fn commits_behind(&self, fork: &Fork) -> Option<u32> {
+        match &self.behind {
+            Some(lookup) => lookup.get(fork),
+            None => remote::get_commits_behind(fork),
+        }
+    }
+
+    /// The hooks that apply to `fork`.
+    pub fn hooks_for(&self, fork: &Fork) -> Hooks {
+        self.hooks.resolve(&self.repos, &fork.full_name())
+    }
+
+    /// How `fork`'s clone is updated, if configured.
+    pub fn update_for(&self, fork: &Fork) -> Option<LocalUpdate> {
+        self.repos.get(&fork.full_name()).and_then(|r| r.update)
+    }
+
+    /// Whether uncommitted changes on `fork`'s checked-out default branch
+    /// can be left for `--autostash`: merges and rebases carry them, a reset
+    /// or rebasing other branches needs them stashed by hand.
+    pub fn autostash_for(&self, fork: &Fork) -> bool {
+        self.update_for(fork) != Some(LocalUpdate::Reset) && !self.rebase_branches
+    }
+
+    /// Whether `fork` is kept as a bare `--mirror` clone.
+    pub fn is_mirror(&self, fork: &Fork) -> bool {
+        self.repos.get(&fork.full_name()).is_some_and(|r| r.mirror)
+    }
+
+    /// When `fork` may be force-synced.
+    pub fn force_for(&self, fork: &Fork) -> ForceSync {
+        let default = if self.auto_force {
+            ForceSync::Auto
+        } else {
+            ForceSync::Never
+        };
+        self.repos
+            .get(&fork.full_name())
+            .and_then(|r| r.force)
+            .unwrap_or(default)
+    }
+}
@@ -0,0 +1,124 @@
+use super::ops::clone_repo;
+use super::SyncOptions;
+use crate::logging::LoggedOutput;
+use crate::paths::ToolHomes;
+use crate::tools;
+use crate::types::{ErrorDetails, Fork, RepoKind, SyncResult};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Clone attempts right after forking; GitHub creates forks asynchronously,
+/// so the first clone can race it.
+const CLONE_ATTEMPTS: u32 = 5;
+const CLONE_RETRY_DELAY: Duration = Duration::from_secs(3);
+
+/// The parts of the `POST /repos/{owner}/{repo}/forks` response we use.
+#[derive(Debug, Deserialize)]
+struct CreatedFork {
+    name: String,
+    owner: CreatedOwner,
+    default_branch: String,
+    description: Option<String>,
+    language: Option<String>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    visibility: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedOwner {
+    login: String,
+}
+
+/// Fork `parent_owner/parent_name` on GitHub and clone it into the primary
+/// tool home (unless it's already cloned under one of the roots),
+/// in the background. Sends `ForkCreated` (even if the clone failed), or an
+/// error popup if the fork couldn't be created.
+pub fn fork_upstream_async(
+    parent_owner: String,
+    parent_name: String,
+    tool_homes: ToolHomes,
+    options: SyncOptions,
+    tx: mpsc::Sender<SyncResult>,
+) {
+    thread::spawn(move || {
+        let mut fork = match create_fork(&parent_owner, &parent_name, &tool_homes) {
+            Ok(fork) => fork,
+            Err(err) => {
+                let _ = tx.send(SyncResult::ActionableError(ErrorDetails {
+                    title: "Fork Failed".to_string(),
+                    message: format!("Couldn't fork {parent_owner}/{parent_name}:\n\n{err}"),
+                    action: None,
+                }));
+                return;
+            }
+        };
+
+        let mut clone_error = None;
+        if !fork.is_cloned {
+            for attempt in 1..=CLONE_ATTEMPTS {
+                match clone_repo(&fork, &options) {
+                    Ok(()) => {
+                        fork.is_cloned = true;
+                        clone_error = None;
+                        break;
+                    }
+                    Err(e) => clone_error = Some(e),
+                }
+                if attempt < CLONE_ATTEMPTS {
+                    thread::sleep(CLONE_RETRY_DELAY);
+                }
+            }
+        }
+        let _ = tx.send(SyncResult::ForkCreated(fork, clone_error));
+    });
+}
+
+/// Create (or, if it already exists, look up) the user's fork of a repo.
+fn create_fork(
+    parent_owner: &str,
+    parent_name: &str,
+    tool_homes: &ToolHomes,
+) -> Result<Fork, String> {
+    let output = tools::gh()
+        .args([
+            "api",
+            "--method",
+            "POST",
+            &format!("repos/{parent_owner}/{parent_name}/forks"),
+        ])
+        .logged_output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let created: CreatedFork = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Unexpected response from GitHub: {e}"))?;
+
+    let (local_path, is_cloned) = tool_homes.locate(&created.owner.login, &created.name);
+    Ok(Fork {
+        is_cloned,
+        local_path,
+        name: created.name,
+        owner: created.owner.login,
+        parent_owner: parent_owner.to_string(),
+        parent_name: parent_name.to_string(),
+        default_branch: created.default_branch,
+        description: created.description,
+        primary_language: created.language,
+        created_at: parse_time(created.created_at.as_deref()),
+        updated_at: parse_time(created.updated_at.as_deref()),
+        upstream_head: None,
+        upstream_pushed_at: None,
+        visibility: created.visibility,
+        kind: RepoKind::Fork,
+    })
+}
+
+fn parse_time(s: Option<&str>) -> Option<DateTime<Utc>> {
+    s.and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
@@ -0,0 +1,294 @@
+use super::remote::ensure_upstream_remote;
+use super::SyncOptions;
+use crate::config::expand_clone_url;
+use crate::github::host;
+use crate::logging::LoggedOutput;
+use crate::paths::ToolHomes;
+use crate::tools;
+use crate::trash::delete_clone;
+use crate::types::{ErrorAction, ErrorDetails, Fork, Retry, SyncResult, SyncStatus};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Clone a single fork in the background.
+pub fn clone_fork_async(fork: Fork, options: SyncOptions, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        clone_single_fork(&fork, &options, &tx);
+    });
+}
+
+/// Delete forks one after another in the background (removes each local clone
+/// and deletes the fork from GitHub). With `trash_homes`, clones are moved to
+/// the trash directory of the root they live under instead of removed.
+pub fn delete_forks_async(
+    forks: Vec<Fork>,
+    dry_run: bool,
+    trash_homes: Option<ToolHomes>,
+    tx: mpsc::Sender<SyncResult>,
+) {
+    thread::spawn(move || {
+        let mut forks = forks.into_iter();
+        let Some(failed) = forks
+            .by_ref()
+            .find(|fork| !delete_fork(fork, dry_run, trash_homes.as_ref(), &tx))
+        else {
+            return;
+        };
+        // Without the delete_repo scope the rest would fail the same way
+        let mut names = vec![failed.full_name()];
+        for fork in forks {
+            names.push(fork.full_name());
+            let _ = tx.send(SyncResult::StatusUpdate(
+                fork.full_name(),
+                SyncStatus::Skipped("missing delete_repo scope".to_string()),
+            ));
+        }
+        let _ = tx.send(SyncResult::ActionableError(missing_delete_scope(names)));
+    });
+}
+
+/// Popup offering to grant the `delete_repo` scope in place, then retry the
+/// deletion of `names`.
+fn missing_delete_scope(names: Vec<String>) -> ErrorDetails {
+    let what = match names.as_slice() {
+        [name] => name.clone(),
+        _ => format!("{} forks", names.len()),
+    };
+    ErrorDetails {
+        title: "Missing GitHub Scope".to_string(),
+        message: format!(
+            "Cannot delete {what}.\n\n\
+            The 'delete_repo' scope is required.\n\n\
+            Grant it with gh auth refresh (the TUI is\n\
+            suspended meanwhile), and the delete is retried."
+        ),
+        action: Some(ErrorAction {
+            label: "Grant scope".to_string(),
+            gh_args: ["auth", "refresh", "-h", &host(), "-s", "delete_repo"]
+                .map(String::from)
+                .to_vec(),
+            interactive: true,
+            retry: Some(Retry::Delete(names)),
+        }),
+    }
+}
+
+/// Delete a single fork. Returns false if the `delete_repo` scope is missing.
+fn delete_fork(
+    fork: &Fork,
+    dry_run: bool,
+    trash_homes: Option<&ToolHomes>,
+    tx: &mpsc::Sender<SyncResult>,
+) -> bool {
+    let send = |status: SyncStatus| {
+        let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+    };
+
+    let _span = tracing::info_span!("delete", fork = %fork.full_name()).entered();
+    send(SyncStatus::Deleting);
+
+    if dry_run {
+        thread::sleep(Duration::from_millis(500));
+        send(SyncStatus::Synced(None));
+        let _ = tx.send(SyncResult::ForkDeleted(fork.full_name()));
+        return true;
+    }
+
+    // Step 1: Trash or delete local directory if it exists
+    if fork.local_path.exists() {
+        if let Err(e) = delete_clone(trash_homes, &fork.owner, &fork.name, &fork.local_path) {
+            send(SyncStatus::failed(&format!("remove local: {e:#}")));
+            return true;
+        }
+    }
+
+    // Step 2: Delete the fork from GitHub
+    let repo = format!("{}/{}", fork.owner, fork.name);
+    let result = tools::gh()
+        .args(["repo", "delete", &repo, "--yes"])
+        .logged_output();
+
+    match result {
+        Ok(output) if output.status.success() => {
+            send(SyncStatus::Synced(None));
+            let _ = tx.send(SyncResult::ForkDeleted(fork.full_name()));
+        }
+        Ok(output) => {
+            let err = String::from_utf8_lossy(&output.stderr).to_string();
+
+            // A scope error: the caller offers to fix it and retry
+            if err.contains("delete_repo") && err.contains("scope") {
+                send(SyncStatus::Failed("missing delete_repo scope".to_string()));
+                return false;
+            }
+            send(SyncStatus::failed(&err));
+        }
+        Err(e) => {
+            send(SyncStatus::failed(&e.to_string()));
+        }
+    }
+    true
+}
+
+/// Archive forks one after another in the background (async, non-blocking).
+pub fn archive_forks_async(forks: Vec<Fork>, dry_run: bool, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        for fork in forks {
+            archive_fork(&fork, dry_run, &tx);
+        }
+    });
+}
+
+/// Archive a single fork.
+fn archive_fork(fork: &Fork, dry_run: bool, tx: &mpsc::Sender<SyncResult>) {
+    let send = |status: SyncStatus| {
+        let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+    };
+
+    let _span = tracing::info_span!("archive", fork = %fork.full_name()).entered();
+    send(SyncStatus::Archiving);
+
+    if dry_run {
+        thread::sleep(Duration::from_millis(500));
+        send(SyncStatus::Synced(None));
+        let _ = tx.send(SyncResult::ForkArchived(fork.full_name()));
+        return;
+    }
+
+    let repo = format!("{}/{}", fork.owner, fork.name);
+    let result = tools::gh()
+        .args(["repo", "archive", &repo, "--yes"])
+        .logged_output();
+
+    match result {
+        Ok(output) if output.status.success() => {
+            send(SyncStatus::Synced(None));
+            let _ = tx.send(SyncResult::ForkArchived(fork.full_name()));
+        }
+        Ok(output) => {
+            let err = String::from_utf8_lossy(&output.stderr);
+            send(SyncStatus::failed(&err));
+        }
+        Err(e) => {
+            send(SyncStatus::failed(&e.to_string()));
+        }
+    }
+}
+
+/// Change a fork's visibility (`public` or `private`) in the background.
+pub fn set_visibility_async(
+    fork: Fork,
+    visibility: &'static str,
+    dry_run: bool,
+    tx: mpsc::Sender<SyncResult>,
+) {
+    thread::spawn(move || {
+        let send = |status: SyncStatus| {
+            let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+        };
+
+        send(SyncStatus::ChangingVisibility);
+
+        if dry_run {
+            thread::sleep(Duration::from_millis(500));
+            send(SyncStatus::Pending);
+            return;
+        }
+
+        let result = tools::gh()
+            .args([
+                "repo",
+                "edit",
+                &fork.full_name(),
+                "--visibility",
+                visibility,
+                "--accept-visibility-change-consequences",
+            ])
+            .logged_output();
+
+        match result {
+            Ok(output) if output.status.success() => {
+                send(SyncStatus::Pending);
+                let _ = tx.send(SyncResult::VisibilityChanged(
+                    fork.full_name(),
+                    visibility.to_string(),
+                ));
+            }
+            Ok(output) => {
+                let err = String::from_utf8_lossy(&output.stderr);
+                send(SyncStatus::failed(&err));
+            }
+            Err(e) => {
+                send(SyncStatus::failed(&e.to_string()));
+            }
+        }
+    });
+}
+
+/// Clone a single fork (runs in caller's thread context).
+pub fn clone_single_fork(fork: &Fork, options: &SyncOptions, tx: &mpsc::Sender<SyncResult>) {
+    let send = |status: SyncStatus| {
+        let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+    };
+
+    let _span = tracing::info_span!("clone", fork = %fork.full_name()).entered();
+    send(SyncStatus::Cloning);
+
+    if options.dry_run {
+        send(SyncStatus::Skipped(format!(
+            "would clone into {}",
+            fork.local_path.display()
+        )));
+        return;
+    }
+
+    match clone_repo(fork, options) {
+        Ok(()) => {
+            send(SyncStatus::Synced(None));
+            let _ = tx.send(SyncResult::ForkCloned(fork.full_name()));
+        }
+        Err(e) => send(SyncStatus::failed(&e)),
+    }
+}
+
+/// Clone a fork to its `local_path` and add its `upstream` remote, or make a
+/// bare mirror of it if configured.
+pub(super) fn clone_repo(fork: &Fork, options: &SyncOptions) -> Result<(), String> {
+    // Ensure parent directory exists
+    if let Some(parent) = fork.local_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("mkdir: {e}"))?;
+    }
+
+    // Clone with an explicit URL if a template is configured, else let gh decide
+    // Mirrors are bare and only track the fork (see `sync_mirror`)
+    let path = fork.local_path.to_string_lossy();
+    let mirror = options.is_mirror(fork);
+    let output = if let Some(template) = &options.clone_url_template {
+        let url = expand_clone_url(template, &fork.owner, &fork.name);
+        tools::git()
+            .arg("clone")
+            .args(mirror.then_some("--mirror"))
+            .args([&url, path.as_ref()])
+            .logged_output()
+    } else {
+        tools::gh()
+            .args([
+                "repo",
+                "clone",
+                &format!("{}/{}", fork.owner, fork.name),
+                path.as_ref(),
+            ])
+            .args(mirror.then_some(["--", "--mirror"]).into_iter().flatten())
+            .logged_output()
+    }
+    .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    if !mirror {
+        ensure_upstream_remote(fork, options.clone_url_template.as_deref());
+    }
+    Ok(())
+}
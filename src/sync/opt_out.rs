@@ -0,0 +1,92 @@
+use super::worktree::git_stdout;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Marker file a clone can carry to keep repo-syncer away from it.
+pub const MARKER_FILE: &str = ".repo-syncer.toml";
+
+/// Contents of `.repo-syncer.toml`:
+///
+/// ```toml
+/// skip = true
+/// reason = "vendored, pinned to an old release"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Marker {
+    skip: bool,
+    reason: Option<String>,
+}
+
+/// Why the clone at `path` must never be synced, stashed or reset, if it
+/// opted out: `skip = true` in its `.repo-syncer.toml`, or the git config
+/// key `repo-syncer.skip=true` (with an optional `repo-syncer.reason`).
+/// A marker file that can't be parsed counts as opting out.
+pub fn opt_out_reason(path: &Path) -> Option<String> {
+    if let Ok(contents) = std::fs::read_to_string(path.join(MARKER_FILE)) {
+        match toml::from_str::<Marker>(&contents) {
+            Ok(marker) if marker.skip => {
+                let reason = marker.reason;
+                return Some(reason.unwrap_or_else(|| format!("opted out ({MARKER_FILE})")));
+            }
+            Ok(_) => {}
+            Err(_) => return Some(format!("invalid {MARKER_FILE}")),
+        }
+    }
+    let git_path = path.to_string_lossy();
+    let skip = git_stdout(
+        &git_path,
+        &["config", "--type=bool", "--get", "repo-syncer.skip"],
+    );
+    (skip.as_deref() == Some("true")).then(|| {
+        git_stdout(&git_path, &["config", "--get", "repo-syncer.reason"])
+            .filter(|reason| !reason.is_empty())
+            .unwrap_or_else(|| "opted out (repo-syncer.skip)".to_string())
+    })
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{final_status, local_options, Fixture};
+    use super::super::fork::sync_single_fork;
+    use super::super::worktree::git_ok;
+    use super::*;
+    use crate::types::SyncStatus;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_opted_out_clone_is_skipped() {
+        let fixture = Fixture::new();
+        fixture.upstream_commit("CHANGELOG.md", "1.1");
+        let head = Fixture::rev(&fixture.clone, "HEAD");
+        assert_eq!(opt_out_reason(&fixture.clone), None);
+
+        let path = fixture.clone.to_string_lossy();
+        assert!(git_ok(&path, &["config", "repo-syncer.skip", "yes"]));
+        assert_eq!(
+            opt_out_reason(&fixture.clone).as_deref(),
+            Some("opted out (repo-syncer.skip)")
+        );
+
+        assert!(git_ok(&path, &["config", "--unset", "repo-syncer.skip"]));
+        let marker = fixture.clone.join(MARKER_FILE);
+        std::fs::write(&marker, "skip = false\n").unwrap();
+        assert_eq!(opt_out_reason(&fixture.clone), None);
+        std::fs::write(&marker, "skip = true\nreason = \"pinned\"\n").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        sync_single_fork(&fixture.fork(), &local_options(), &tx);
+        assert_eq!(final_status(&rx), SyncStatus::Skipped("pinned".to_string()));
+        assert_eq!(Fixture::rev(&fixture.clone, "HEAD"), head);
+
+        std::fs::write(&marker, "skip = [").unwrap();
+        assert_eq!(
+            opt_out_reason(&fixture.clone).as_deref(),
+            Some("invalid .repo-syncer.toml")
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use super::backup::BACKUP_REFS;
+use super::branches::branches_tracking;
+use super::local::update_args;
+use super::remote::gh_repo_sync_args;
+use super::route::{route, Route};
+use super::worktree::STASH_PUSH;
+use super::SyncOptions;
+use crate::config::{ForceSync, LocalUpdate};
+use crate::types::{Fork, SyncPlan, SyncResult};
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+
+/// Work out the sync plans of `forks` in the background and send them back
+/// as one `SyncResult::Plan`, for the plan overlay.
+pub fn plan_sync_async(forks: Vec<Fork>, options: SyncOptions, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        let plans = forks.iter().map(|fork| plan_sync(fork, &options)).collect();
+        let _ = tx.send(SyncResult::Plan(plans));
+    });
+}
+
+/// The commands a sync of `fork` would run, in order, following the same
+/// `route` as `sync_single_fork` (opt-out, checked-out branch, uncommitted
+/// changes, unpushed commits). Nothing is fetched, so steps that depend on
+/// what upstream has (a backup ref that turns out unneeded, a forced or
+/// fallback sync, the tags to push) are shown as they would run in the
+/// worst case, or as `#` notes.
+pub(super) fn plan_sync(fork: &Fork, options: &SyncOptions) -> SyncPlan {
+    let mut plan = SyncPlan {
+        id: fork.full_name(),
+        commands: Vec::new(),
+        skip: None,
+    };
+    let gh_repo_sync = |force| command("gh", &gh_repo_sync_args(fork, force));
+    let sync = match route(fork, options) {
+        Route::Skip(status) => {
+            plan.skip = Some(status.display());
+            return plan;
+        }
+        Route::Mirror { on_github } => {
+            if on_github {
+                plan.commands.push(gh_repo_sync(false));
+            }
+            plan.commands.push(git(&["remote", "update", "--prune"]));
+            return plan;
+        }
+        Route::Remote => {
+            plan.commands.push(gh_repo_sync(false));
+            return plan;
+        }
+        Route::Clone(sync) => sync,
+    };
+
+    let worktree = &sync.worktree;
+    let branch = &fork.default_branch;
+    if Path::new(&worktree.path) != fork.local_path {
+        plan.commands.push(command("cd", &[&worktree.path]));
+    }
+    let hooks = options.hooks_for(fork);
+    plan.commands
+        .extend(hooks.pre_sync.map(|hook| command("sh", &["-c", &hook])));
+    if let Some(url) = &sync.add_upstream {
+        plan.commands.push(git(&["remote", "add", "upstream", url]));
+    }
+    if sync.stash {
+        plan.commands.push(git(&STASH_PUSH));
+    }
+    if sync.switch {
+        plan.commands.push(git(&["checkout", branch]));
+    }
+
+    if sync.on_github {
+        plan.commands.push(gh_repo_sync(false));
+        let when = match sync.force {
+            ForceSync::Never => None,
+            ForceSync::Auto => Some("if the fork diverged and its commits are already upstream"),
+            ForceSync::Always => Some("if the fork diverged"),
+        };
+        if let Some(when) = when {
+            plan.commands
+                .push(format!("# {when}: {}", gh_repo_sync(true)));
+        }
+        plan.commands.push(git(&["fetch", "origin"]));
+    } else {
+        plan.commands.push(git(&["fetch", "upstream"]));
+    }
+    let target = sync.target(branch);
+    let how = sync.update.unwrap_or(LocalUpdate::FfOnly);
+    if how != LocalUpdate::FfOnly {
+        plan.commands.push(git(&[
+            "update-ref",
+            &format!("{BACKUP_REFS}/<branch>/<time>"),
+            "HEAD",
+        ]));
+    }
+    let autostash = worktree.will_autostash(branch, sync.autostash);
+    plan.commands
+        .push(git(&update_args(&target, how, autostash)));
+    if sync.fallback_reset == Some(true) {
+        plan.commands.push(format!(
+            "# if that isn't a fast-forward: {}",
+            git(&update_args(&target, LocalUpdate::Reset, false))
+        ));
+    }
+
+    if options.rebase_branches {
+        let feature_branches = branches_tracking(&worktree.path, branch).unwrap_or_default();
+        for feature in &feature_branches {
+            plan.commands
+                .push(git(&["rebase", "--autostash", branch, feature]));
+        }
+        if !feature_branches.is_empty() {
+            plan.commands.push(git(&["checkout", "-q", branch]));
+        }
+    }
+    if sync.switch {
+        plan.commands
+            .push(git(&["checkout", &worktree.original_branch]));
+    }
+    if sync.stash {
+        plan.commands.push(git(&["stash", "pop"]));
+    }
+
+    if options.update_submodules && Path::new(&worktree.path).join(".gitmodules").exists() {
+        plan.commands
+            .push(git(&["submodule", "update", "--init", "--recursive"]));
+    }
+    if options.mirror_tags {
+        plan.commands.push(git(&["fetch", "upstream", "--tags"]));
+        if sync.on_github {
+            plan.commands
+                .push("git push origin <upstream tags missing on origin>".to_string());
+        }
+    }
+    plan.commands
+        .extend(hooks.post_sync.map(|hook| command("sh", &["-c", &hook])));
+    plan
+}
+
+fn git(args: &[&str]) -> String {
+    command("git", args)
+}
+
+/// A command line as it could be pasted into a shell, quoting arguments
+/// with spaces or quotes in them.
+fn command(program: &str, args: &[impl AsRef<str>]) -> String {
+    let mut words = vec![program.to_string()];
+    words.extend(args.iter().map(|arg| {
+        let arg = arg.as_ref();
+        if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || "'\"$`\\".contains(c)) {
+            format!("'{}'", arg.replace('\'', r"'\''"))
+        } else {
+            arg.to_string()
+        }
+    }));
+    words.join(" ")
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{gh_options, local_options, Fixture};
+    use super::*;
+    use crate::types::DIRTY;
+
+    #[test]
+    fn test_plan_dirty_clone_on_feature_branch() {
+        let fixture = Fixture::new();
+        assert_eq!(
+            plan_sync(&fixture.fork(), &local_options()).commands,
+            ["git fetch upstream", "git merge --ff-only upstream/main"]
+        );
+
+        fixture.switch_clone_to("feature");
+        std::fs::write(fixture.clone.join("README.md"), "work in progress").unwrap();
+        let plan = plan_sync(&fixture.fork(), &local_options());
+        assert_eq!(plan.skip, None);
+        assert_eq!(
+            plan.commands,
+            [
+                "git stash push -m 'repo-syncer auto-stash'",
+                "git checkout main",
+                "git fetch upstream",
+                "git merge --ff-only upstream/main",
+                "git checkout feature",
+                "git stash pop",
+            ]
+        );
+        // Planning touches nothing
+        assert_eq!(fixture.clone_branch(), "feature");
+        assert!(!fixture.clone_has_stash());
+
+        let options = SyncOptions {
+            skip_dirty: true,
+            ..local_options()
+        };
+        assert_eq!(
+            plan_sync(&fixture.fork(), &options).skip.as_deref(),
+            Some(DIRTY)
+        );
+    }
+
+    #[test]
+    fn test_plan_shows_forced_sync() {
+        let fixture = Fixture::new();
+        let options = SyncOptions {
+            auto_force: true,
+            reset_fallback: true,
+            ..gh_options()
+        };
+        let gh_sync = "gh repo sync me/project --source them/project --branch main";
+        assert_eq!(
+            plan_sync(&fixture.fork(), &options).commands,
+            [
+                gh_sync.to_string(),
+                format!(
+                    "# if the fork diverged and its commits are already upstream: {gh_sync} --force"
+                ),
+                "git fetch origin".to_string(),
+                "git merge --ff-only origin/main".to_string(),
+                "# if that isn't a fast-forward: git reset --hard origin/main".to_string(),
+            ]
+        );
+    }
+}
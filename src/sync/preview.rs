@@ -0,0 +1,98 @@
+use super::remote::ensure_upstream_remote;
+use super::worktree::{git_ok, git_stdout};
+use crate::types::{Fork, IncomingChanges, SyncResult};
+use std::sync::mpsc;
+use std::thread;
+
+/// Fetch the upstream into a clone in the background and report what a
+/// sync would bring in, for the preview overlay.
+pub fn preview_incoming_async(
+    fork: Fork,
+    clone_url_template: Option<String>,
+    tx: mpsc::Sender<SyncResult>,
+) {
+    thread::spawn(move || {
+        let result = incoming_changes(&fork, clone_url_template.as_deref());
+        let _ = tx.send(SyncResult::Preview(fork.full_name(), result));
+    });
+}
+
+/// `git fetch upstream`, then list the commits and files on the upstream
+/// default branch that the clone's default branch doesn't have. Only
+/// remote-tracking refs change; the working tree is left alone.
+pub(super) fn incoming_changes(
+    fork: &Fork,
+    clone_url_template: Option<&str>,
+) -> Result<IncomingChanges, String> {
+    ensure_upstream_remote(fork, clone_url_template);
+    fetch_incoming(fork, None)
+}
+
+/// Like `incoming_changes`, but a clone without an `upstream` remote keeps
+/// its config: `upstream_url` (its missing remote's URL) is fetched into
+/// `FETCH_HEAD` only. For `--dry-run`, which mustn't change the clone.
+pub(super) fn peek_incoming_changes(
+    fork: &Fork,
+    upstream_url: Option<&str>,
+) -> Result<IncomingChanges, String> {
+    fetch_incoming(fork, upstream_url)
+}
+
+/// Fetch the upstream default branch (from the `upstream` remote, or `url`)
+/// and list what it has that the clone's doesn't.
+fn fetch_incoming(fork: &Fork, url: Option<&str>) -> Result<IncomingChanges, String> {
+    let path = fork.local_path.to_string_lossy();
+    let branch = &fork.default_branch;
+    let (fetched, theirs) = match url {
+        Some(url) => (
+            git_ok(&path, &["fetch", url, branch]),
+            "FETCH_HEAD".to_string(),
+        ),
+        None => (
+            git_ok(&path, &["fetch", "upstream"]),
+            format!("upstream/{branch}"),
+        ),
+    };
+    if !fetched {
+        return Err("fetch upstream failed".to_string());
+    }
+
+    let lines = |args: &[&str]| {
+        git_stdout(&path, args)
+            .map(|out| out.lines().map(str::to_string).collect::<Vec<_>>())
+            .ok_or_else(|| format!("no {branch} branch to compare with upstream/{branch}"))
+    };
+    Ok(IncomingChanges {
+        commits: lines(&["log", "--format=%h %s", &format!("{branch}..{theirs}")])?,
+        files: lines(&["diff", "--name-status", &format!("{branch}...{theirs}")])?,
+    })
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::Fixture;
+    use super::*;
+
+    #[test]
+    fn test_incoming_changes() {
+        let fixture = Fixture::new();
+        let fork = fixture.fork();
+        let incoming = incoming_changes(&fork, None).unwrap();
+        assert!(incoming.commits.is_empty() && incoming.files.is_empty());
+
+        fixture.upstream_commit("src.txt", "new");
+        let incoming = incoming_changes(&fork, None).unwrap();
+        assert_eq!(incoming.commits.len(), 1);
+        assert!(incoming.commits[0].ends_with(" Update src.txt"));
+        assert_eq!(incoming.files, vec!["A\tsrc.txt".to_string()]);
+        // The clone itself hasn't moved
+        assert_eq!(
+            Fixture::rev(&fixture.clone, "HEAD"),
+            Fixture::rev(&fixture.clone, "origin/main")
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::logging::LoggedOutput;
+use crate::tools;
+use crate::types::{Fork, SyncResult, SyncStatus};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Open a pull request from the fork's default branch to the same branch
+/// upstream, in the background. Forks that aren't ahead are Skipped.
+pub fn create_pr_async(fork: Fork, dry_run: bool, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        let send = |status: SyncStatus| {
+            let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+        };
+
+        send(SyncStatus::OpeningPr);
+
+        let subjects = match ahead_commits(&fork) {
+            Ok(subjects) if subjects.is_empty() => {
+                send(SyncStatus::Skipped("not ahead of upstream".to_string()));
+                return;
+            }
+            Ok(subjects) => subjects,
+            Err(err) => {
+                send(SyncStatus::failed(&err));
+                return;
+            }
+        };
+
+        if dry_run {
+            thread::sleep(Duration::from_millis(500));
+            send(SyncStatus::Pending);
+            return;
+        }
+
+        let (title, body) = pr_text(&fork, &subjects);
+        let result = tools::gh()
+            .args([
+                "pr",
+                "create",
+                "--repo",
+                &format!("{}/{}", fork.parent_owner, fork.parent_name),
+                "--head",
+                &format!("{}:{}", fork.owner, fork.default_branch),
+                "--base",
+                &fork.default_branch,
+                "--title",
+                &title,
+                "--body",
+                &body,
+            ])
+            .logged_output();
+
+        match result {
+            Ok(output) if output.status.success() => {
+                let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                send(SyncStatus::Pending);
+                let _ = tx.send(SyncResult::PullRequestOpened(fork.full_name(), url));
+            }
+            Ok(output) => {
+                let err = String::from_utf8_lossy(&output.stderr);
+                // gh refuses a second PR for the same head, but tells us its URL
+                if let Some(url) = err
+                    .contains("already exists")
+                    .then(|| find_url(&err))
+                    .flatten()
+                {
+                    send(SyncStatus::Pending);
+                    let _ = tx.send(SyncResult::PullRequestOpened(fork.full_name(), url));
+                } else {
+                    send(SyncStatus::failed(&err));
+                }
+            }
+            Err(e) => send(SyncStatus::failed(&e.to_string())),
+        }
+    });
+}
+
+/// Subjects of the fork's commits that upstream doesn't have (oldest first).
+fn ahead_commits(fork: &Fork) -> Result<Vec<String>, String> {
+    let output = tools::gh()
+        .args([
+            "api",
+            &format!(
+                "repos/{}/{}/compare/{branch}...{}:{branch}",
+                fork.parent_owner,
+                fork.parent_name,
+                fork.owner,
+                branch = fork.default_branch
+            ),
+            "--jq",
+            r#".commits[].commit.message | split("\n")[0]"#,
+        ])
+        .logged_output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// PR title and body: a single commit's subject, or a summary plus the list.
+fn pr_text(fork: &Fork, subjects: &[String]) -> (String, String) {
+    let list = subjects
+        .iter()
+        .map(|s| format!("- {s}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    match subjects {
+        [only] => (only.clone(), String::new()),
+        _ => (
+            format!(
+                "Changes from {}/{} ({} commits)",
+                fork.owner,
+                fork.name,
+                subjects.len()
+            ),
+            list,
+        ),
+    }
+}
+
+fn find_url(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .find(|word| word.starts_with("https://"))
+        .map(str::to_string)
+}
@@ -0,0 +1,355 @@
+use super::behind::BehindLookup;
+use super::fork::sync_single_fork;
+use super::ops::clone_single_fork;
+use super::{SyncOptions, SyncOutcome};
+use crate::logging;
+use crate::types::{Fork, SyncResult};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{mpsc, Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+/// Pause between forks when running at full speed.
+const FULL_SPEED_DELAY: Duration = Duration::from_millis(100);
+
+/// Pause between forks in slow mode.
+pub const SLOW_MODE_DELAY: Duration = Duration::from_secs(5);
+
+/// Shared work queue for a sync batch.
+///
+/// Workers pull forks from the queue until it is empty. The queue can be
+/// switched into slow mode at any time: only the lowest-numbered live worker
+/// keeps taking new forks (one at a time, with `SLOW_MODE_DELAY` between
+/// them) while the others park. It's never one that already exited, so a
+/// fork put back after an auth error still gets picked up.
+/// Forks already in flight are never interrupted.
+///
+/// Independently of the worker count, at most `owner_limits[owner]` forks of
+/// the same upstream owner run at once; workers skip ahead to forks of other
+/// owners while one is at its limit.
+///
+/// If `gh` turns out not to be logged in, the queue pauses: the affected fork
+/// goes back to the front and no new forks start until `resume` is called.
+/// The user can also hold the queue (`set_held`), which likewise lets forks
+/// in flight finish and starts no new ones until it's released.
+///
+/// Forks can be added while the batch runs (`push`); workers that already
+/// exited because the queue ran dry are started again.
+pub struct SyncQueue {
+    state: Mutex<QueueState>,
+    wakeup: Condvar,
+    job: JobFn,
+}
+
+/// What a worker runs for each fork.
+type JobFn = fn(&Fork, &SyncOptions, &mpsc::Sender<SyncResult>) -> SyncOutcome;
+
+struct QueueState {
+    pending: VecDeque<(usize, Fork)>,
+    slow: bool,
+    /// Waiting for the user to re-authenticate `gh`
+    paused: bool,
+    /// Held by the user (`p` while syncing)
+    held: bool,
+    /// In-flight forks per upstream owner (lowercased)
+    active: HashMap<String, usize>,
+    /// Max concurrent forks per upstream owner (lowercased)
+    owner_limits: HashMap<String, usize>,
+    /// Size of the worker pool
+    jobs: usize,
+    /// Workers that haven't exited yet
+    running: HashSet<usize>,
+}
+
+impl QueueState {
+    /// Position of the first pending fork whose upstream owner is below its limit.
+    fn next_allowed(&self) -> Option<usize> {
+        self.pending.iter().position(|(_, fork)| {
+            let owner = fork.parent_owner.to_lowercase();
+            self.owner_limits
+                .get(&owner)
+                .is_none_or(|&limit| self.active.get(&owner).copied().unwrap_or(0) < limit)
+        })
+    }
+}
+
+impl SyncQueue {
+    fn new(
+        forks: Vec<(usize, Fork)>,
+        owner_limits: &HashMap<String, usize>,
+        jobs: usize,
+        job: JobFn,
+    ) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                pending: forks.into(),
+                slow: false,
+                paused: false,
+                held: false,
+                active: HashMap::new(),
+                owner_limits: owner_limits
+                    .iter()
+                    .map(|(owner, &limit)| (owner.to_lowercase(), limit.max(1)))
+                    .collect(),
+                jobs,
+                running: (0..jobs).collect(),
+            }),
+            wakeup: Condvar::new(),
+            job,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Switch between full speed and slow mode.
+    pub fn set_slow(&self, slow: bool) {
+        self.lock().slow = slow;
+        self.wakeup.notify_all();
+    }
+
+    /// Whether the queue is currently in slow mode.
+    pub fn is_slow(&self) -> bool {
+        self.lock().slow
+    }
+
+    /// Hold the queue (no new forks start) or release it.
+    pub fn set_held(&self, held: bool) {
+        self.lock().held = held;
+        self.wakeup.notify_all();
+    }
+
+    /// Whether the user is holding the queue.
+    pub fn is_held(&self) -> bool {
+        self.lock().held
+    }
+
+    /// Take the next fork for `worker`, blocking while the queue is paused
+    /// or held, slow mode parks it, or every pending fork's owner is at its
+    /// limit.
+    /// Returns None once the queue is drained.
+    fn next_job(&self, worker: usize) -> Option<(usize, Fork)> {
+        let mut state = self.lock();
+        loop {
+            if state.pending.is_empty() {
+                state.running.remove(&worker);
+                // In slow mode, the next-lowest worker takes over
+                self.wakeup.notify_all();
+                return None;
+            }
+            let turn = !state.slow || state.running.iter().min() == Some(&worker);
+            if !state.paused && !state.held && turn {
+                if let Some(pos) = state.next_allowed() {
+                    let job = state.pending.remove(pos)?;
+                    *state
+                        .active
+                        .entry(job.1.parent_owner.to_lowercase())
+                        .or_insert(0) += 1;
+                    if state.pending.is_empty() {
+                        // Let parked workers notice the queue is drained
+                        self.wakeup.notify_all();
+                    }
+                    return Some(job);
+                }
+            }
+            state = self
+                .wakeup
+                .wait(state)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+    }
+
+    /// Mark a fork as finished, freeing a slot for its upstream owner.
+    fn finish_job(&self, fork: &Fork) {
+        let mut state = self.lock();
+        if let Some(count) = state.active.get_mut(&fork.parent_owner.to_lowercase()) {
+            *count = count.saturating_sub(1);
+        }
+        drop(state);
+        self.wakeup.notify_all();
+    }
+
+    /// Put a fork that hit an auth error back at the front and pause the
+    /// queue. Returns true if this call paused it (so only one popup is shown).
+    fn pause_for_auth(&self, job: (usize, Fork)) -> bool {
+        let mut state = self.lock();
+        state.pending.push_front(job);
+        !std::mem::replace(&mut state.paused, true)
+    }
+
+    /// Resume a paused queue (e.g. after re-authenticating).
+    pub fn resume(&self) {
+        self.lock().paused = false;
+        self.wakeup.notify_all();
+    }
+
+    /// Add a fork to the running batch. If a worker slot is free (its worker
+    /// exited once the queue ran dry), a worker is started for it.
+    pub fn push(
+        self: &Arc<Self>,
+        job: (usize, Fork),
+        options: &SyncOptions,
+        tx: &mpsc::Sender<SyncResult>,
+    ) {
+        let mut state = self.lock();
+        state.pending.push_back(job);
+        let idle = (0..state.jobs).find(|worker| !state.running.contains(worker));
+        if let Some(worker) = idle {
+            state.running.insert(worker);
+            drop(state);
+            spawn_worker(self, worker, options.clone(), tx.clone());
+        } else {
+            drop(state);
+            self.wakeup.notify_all();
+        }
+    }
+
+    /// Remove the fork at `idx` if it hasn't started yet. Returns whether it
+    /// was still pending.
+    pub fn remove(&self, idx: usize) -> bool {
+        let mut state = self.lock();
+        let Some(pos) = state.pending.iter().position(|(i, _)| *i == idx) else {
+            return false;
+        };
+        state.pending.remove(pos);
+        drop(state);
+        // Parked workers exit if that was the last one
+        self.wakeup.notify_all();
+        true
+    }
+
+    /// Remove and return every fork that hasn't started, unpausing the queue
+    /// so idle workers exit.
+    pub fn drain(&self) -> Vec<(usize, Fork)> {
+        let mut state = self.lock();
+        let drained = state.pending.drain(..).collect();
+        state.paused = false;
+        drop(state);
+        self.wakeup.notify_all();
+        drained
+    }
+
+    /// Sleep between forks. In slow mode this waits `SLOW_MODE_DELAY`, but
+    /// wakes early if slow mode is switched off.
+    fn pause_between_forks(&self) {
+        let state = self.lock();
+        if state.slow {
+            let _ = self
+                .wakeup
+                .wait_timeout_while(state, SLOW_MODE_DELAY, |s| s.slow);
+        } else {
+            drop(state);
+            thread::sleep(FULL_SPEED_DELAY);
+        }
+    }
+}
+
+/// Start syncing selected forks on a pool of `jobs` background workers.
+/// Returns the shared queue so the caller can control it during the run.
+pub fn start_syncing(
+    forks_to_sync: Vec<(usize, Fork)>,
+    options: &SyncOptions,
+    jobs: usize,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Arc<SyncQueue> {
+    let mut options = options.clone();
+    if !options.local_only {
+        let forks = forks_to_sync.iter().map(|(_, fork)| fork.clone()).collect();
+        options.behind = Some(Arc::new(BehindLookup::new(forks)));
+    }
+    start_workers(forks_to_sync, &options, jobs, tx, sync_single_fork)
+}
+
+/// Start cloning forks on a pool of `jobs` background workers, with the same
+/// slow mode and per-owner limits as a sync batch.
+pub fn start_cloning(
+    forks_to_clone: Vec<(usize, Fork)>,
+    options: &SyncOptions,
+    jobs: usize,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Arc<SyncQueue> {
+    start_workers(forks_to_clone, options, jobs, tx, |fork, options, tx| {
+        clone_single_fork(fork, options, tx);
+        SyncOutcome::Done
+    })
+}
+
+/// Run `job` for every fork on a pool of `jobs` workers sharing one queue.
+fn start_workers(
+    forks: Vec<(usize, Fork)>,
+    options: &SyncOptions,
+    jobs: usize,
+    tx: &mpsc::Sender<SyncResult>,
+    job: JobFn,
+) -> Arc<SyncQueue> {
+    let jobs = jobs.max(1);
+    let queue = Arc::new(SyncQueue::new(forks, &options.owner_limits, jobs, job));
+
+    for worker in 0..jobs {
+        spawn_worker(&queue, worker, options.clone(), tx.clone());
+    }
+
+    queue
+}
+
+/// Start `worker`, which takes forks from `queue` until it's drained.
+fn spawn_worker(
+    queue: &Arc<SyncQueue>,
+    worker: usize,
+    options: SyncOptions,
+    tx: mpsc::Sender<SyncResult>,
+) {
+    let queue = Arc::clone(queue);
+    thread::spawn(move || {
+        while let Some((idx, fork)) = queue.next_job(worker) {
+            let watch_tx = tx.clone();
+            let id = fork.full_name();
+            let watch = logging::on_command(move |line| {
+                let _ = watch_tx.send(SyncResult::Command(id.clone(), line.to_string()));
+            });
+            let outcome = (queue.job)(&fork, &options, &tx);
+            drop(watch);
+            queue.finish_job(&fork);
+            if outcome == SyncOutcome::AuthRequired {
+                if queue.pause_for_auth((idx, fork)) {
+                    let _ = tx.send(SyncResult::AuthRequired);
+                }
+                continue;
+            }
+            queue.pause_between_forks();
+        }
+    });
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slow_mode_job_put_back_after_worker_0_exits() {
+        let queue = SyncQueue::new(
+            vec![(0, Fork::test("me", "a")), (1, Fork::test("me", "b"))],
+            &HashMap::new(),
+            2,
+            |_, _, _| SyncOutcome::Done,
+        );
+        let a = queue.next_job(0).unwrap();
+        let b = queue.next_job(1).unwrap();
+        queue.finish_job(&a.1);
+        assert!(queue.next_job(0).is_none());
+
+        // Worker 1 hits an auth error in slow mode and puts its fork back
+        queue.set_slow(true);
+        queue.finish_job(&b.1);
+        assert!(queue.pause_for_auth(b));
+        queue.resume();
+        assert_eq!(queue.next_job(1).map(|(idx, _)| idx), Some(1));
+    }
+}
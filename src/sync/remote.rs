@@ -0,0 +1,158 @@
+use super::force::is_divergence_error;
+use super::worktree::git;
+use super::{SyncOptions, SyncOutcome};
+use crate::api_cache::gh_api;
+use crate::config::{expand_clone_url, CloneProtocol};
+use crate::github::{host, is_auth_error};
+use crate::local::remote_url;
+use crate::logging::LoggedOutput;
+use crate::token_api;
+use crate::tools;
+use crate::types::{Fork, SyncResult, SyncStatus, DIVERGED};
+use std::process::Output;
+use std::sync::mpsc;
+
+/// Make sure a fork's local clone has an `upstream` remote pointing at its
+/// parent, adding one if it is missing (see `missing_upstream_url`).
+/// Best effort: failures are ignored since syncing doesn't depend on it.
+pub(super) fn ensure_upstream_remote(fork: &Fork, clone_url_template: Option<&str>) {
+    if let Some(url) = missing_upstream_url(fork, clone_url_template) {
+        add_upstream_remote(fork, &url);
+    }
+}
+
+/// Add the `upstream` remote at `url` to a fork's clone (best effort).
+pub(super) fn add_upstream_remote(fork: &Fork, url: &str) {
+    let _ = git(
+        &fork.local_path.to_string_lossy(),
+        &["remote", "add", "upstream", url],
+    );
+}
+
+/// The `upstream` remote a fork's clone would get, if it's missing one. The
+/// URL comes from the clone URL template if one is configured, otherwise it
+/// uses the same protocol as `origin` (SSH or HTTPS). `None` for repos that
+/// aren't forks and clones that already have one (which is left untouched).
+pub(super) fn missing_upstream_url(
+    fork: &Fork,
+    clone_url_template: Option<&str>,
+) -> Option<String> {
+    let path = &fork.local_path;
+    if !fork.is_github_fork() || remote_url(path, "upstream").is_some() {
+        return None;
+    }
+
+    Some(if let Some(template) = clone_url_template {
+        expand_clone_url(template, &fork.parent_owner, &fork.parent_name)
+    } else {
+        let uses_ssh = remote_url(path, "origin")
+            .is_some_and(|url| url.starts_with("git@") || url.starts_with("ssh://"));
+        let protocol = if uses_ssh {
+            CloneProtocol::Ssh
+        } else {
+            CloneProtocol::Https
+        };
+        expand_clone_url(
+            &protocol.template(&host()),
+            &fork.parent_owner,
+            &fork.parent_name,
+        )
+    })
+}
+
+/// Get how many commits a fork is behind its upstream (what upstream's
+/// branch is ahead by, compared with the fork's). Returns None if the check
+/// fails or can't be determined. Batches use `BehindLookup` instead.
+pub(super) fn get_commits_behind(fork: &Fork) -> Option<u32> {
+    gh_api(&[
+        &format!(
+            "repos/{}/{}/compare/{}...{}:{}",
+            fork.owner, fork.name, fork.default_branch, fork.parent_owner, fork.default_branch
+        ),
+        "--jq",
+        ".ahead_by",
+    ])
+    .ok()?
+    .trim()
+    .parse()
+    .ok()
+}
+
+/// Run `gh repo sync` on a fork's default branch, optionally with `--force`
+/// (or the same through the API, without `gh`).
+pub(super) fn gh_repo_sync(fork: &Fork, force: bool) -> std::io::Result<Output> {
+    if token_api::enabled() {
+        return token_api::sync_fork(fork, force);
+    }
+    tools::gh()
+        .args(gh_repo_sync_args(fork, force))
+        .logged_output()
+}
+
+/// Sync a fork remotely without any local clone operations.
+/// Uses `gh repo sync` to update the GitHub fork from its upstream.
+pub(super) fn sync_fork_remote(
+    fork: &Fork,
+    options: &SyncOptions,
+    tx: &mpsc::Sender<SyncResult>,
+) -> SyncOutcome {
+    let send = |status: SyncStatus| {
+        let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), status));
+    };
+
+    // Check how many commits behind before syncing
+    let commits_behind = options.commits_behind(fork);
+
+    send(SyncStatus::Syncing);
+
+    match gh_repo_sync(fork, false) {
+        Ok(output) if output.status.success() => {
+            send(SyncStatus::Synced(commits_behind));
+        }
+        Ok(output) => {
+            let err = String::from_utf8_lossy(&output.stderr);
+            if is_auth_error(&err) {
+                send(SyncStatus::Pending);
+                return SyncOutcome::AuthRequired;
+            } else if is_divergence_error(&err) {
+                // Force-sync safety needs a local clone to inspect
+                send(SyncStatus::Failed(DIVERGED.to_string()));
+            } else if err.contains("already up-to-date") || !output.stdout.is_empty() {
+                send(SyncStatus::Synced(Some(0)));
+            } else {
+                send(SyncStatus::failed(&err));
+            }
+        }
+        Err(e) => {
+            send(SyncStatus::failed(&e.to_string()));
+        }
+    }
+    SyncOutcome::Done
+}
+
+/// What a `gh repo sync` run came to: its stderr (or why it couldn't run)
+/// on failure.
+pub(super) fn sync_result(output: std::io::Result<Output>) -> Result<(), String> {
+    match output {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(String::from_utf8_lossy(&output.stderr).into_owned()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Arguments of the `gh repo sync` run by `gh_repo_sync`.
+pub(super) fn gh_repo_sync_args(fork: &Fork, force: bool) -> Vec<String> {
+    let mut args = vec![
+        "repo".to_string(),
+        "sync".to_string(),
+        fork.full_name(),
+        "--source".to_string(),
+        format!("{}/{}", fork.parent_owner, fork.parent_name),
+        "--branch".to_string(),
+        fork.default_branch.clone(),
+    ];
+    if force {
+        args.push("--force".to_string());
+    }
+    args
+}
@@ -0,0 +1,114 @@
+use crate::api_cache::gh_api;
+use crate::github::truncate_error;
+use crate::logging::LoggedOutput;
+use crate::tools;
+use crate::types::{Fork, ForkRisks, SyncResult};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::thread;
+
+/// Check in the background what archiving or deleting the fork could lose:
+/// open PRs to upstream and branches with commits upstream doesn't have.
+pub fn check_risks_async(fork: Fork, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        let result = open_prs_from(&fork).and_then(|open_prs| {
+            Ok(ForkRisks {
+                open_prs,
+                ahead_branches: ahead_branches(&fork)?,
+            })
+        });
+        let _ = tx.send(SyncResult::RisksChecked(fork.full_name(), result));
+    });
+}
+
+/// `#number title` of every open upstream PR whose head is in the fork.
+fn open_prs_from(fork: &Fork) -> Result<Vec<String>, String> {
+    gh_lines(&[
+        "api",
+        "--paginate",
+        &format!(
+            "repos/{}/{}/pulls?state=open&per_page=100",
+            fork.parent_owner, fork.parent_name
+        ),
+        "--jq",
+        &format!(
+            r##".[] | select(.head.repo.full_name == "{}") | "#\(.number) \(.title)""##,
+            fork.full_name()
+        ),
+    ])
+}
+
+/// Fork branches ahead of upstream, as `branch (N ahead)`. Each branch is
+/// compared with the upstream branch of the same name, or upstream's default
+/// branch if there's none; branches whose head matches upstream are skipped
+/// without a compare call.
+fn ahead_branches(fork: &Fork) -> Result<Vec<String>, String> {
+    let upstream = branch_heads(&fork.parent_owner, &fork.parent_name)?;
+    let mut ahead = Vec::new();
+    for (branch, sha) in branch_heads(&fork.owner, &fork.name)? {
+        if upstream.get(&branch) == Some(&sha) {
+            continue;
+        }
+        let base = if upstream.contains_key(&branch) {
+            &branch
+        } else {
+            &fork.default_branch
+        };
+        let ahead_by = gh_api_lines(&[
+            &format!(
+                "repos/{}/{}/compare/{base}...{}:{branch}",
+                fork.parent_owner, fork.parent_name, fork.owner
+            ),
+            "--jq",
+            ".ahead_by",
+        ]);
+        match ahead_by.as_deref() {
+            Ok([n]) if n == "0" => {}
+            Ok([n]) => ahead.push(format!("{branch} ({n} ahead)")),
+            // No common history with upstream: everything on it is unique
+            _ => ahead.push(format!("{branch} (not in upstream)")),
+        }
+    }
+    ahead.sort();
+    Ok(ahead)
+}
+
+/// Branch name → head commit SHA for a repo.
+fn branch_heads(owner: &str, name: &str) -> Result<HashMap<String, String>, String> {
+    Ok(gh_lines(&[
+        "api",
+        "--paginate",
+        &format!("repos/{owner}/{name}/branches?per_page=100"),
+        "--jq",
+        r#".[] | "\(.name) \(.commit.sha)""#,
+    ])?
+    .into_iter()
+    .filter_map(|line| {
+        let (branch, sha) = line.rsplit_once(' ')?;
+        Some((branch.to_string(), sha.to_string()))
+    })
+    .collect())
+}
+
+/// Run a conditional `gh api` GET (see `gh_api`) and return its stdout
+/// lines, or its (truncated) stderr.
+pub(super) fn gh_api_lines(args: &[&str]) -> Result<Vec<String>, String> {
+    gh_api(args)
+        .map(|output| output.lines().map(str::to_string).collect())
+        .map_err(|e| truncate_error(&e))
+}
+
+/// Run `gh` and return its stdout lines, or its (truncated) stderr.
+pub(super) fn gh_lines(args: &[&str]) -> Result<Vec<String>, String> {
+    let output = tools::gh()
+        .args(args)
+        .logged_output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(truncate_error(&String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
@@ -0,0 +1,113 @@
+//! What a sync of one fork will do, decided once from read-only checks.
+//! `sync_single_fork` carries the decision out, and the plan overlay and
+//! `--dry-run` describe it, so the three can't drift apart.
+
+use super::fork::has_unpushed_commits;
+use super::opt_out::opt_out_reason;
+use super::remote::missing_upstream_url;
+use super::worktree::Worktree;
+use super::SyncOptions;
+use crate::config::{ForceSync, LocalUpdate};
+use crate::types::{Fork, SyncStatus, DIRTY};
+
+/// How a sync of a fork goes.
+pub(super) enum Route {
+    /// Left alone with this status: opted out, offline without a clone,
+    /// dirty with `skip_dirty`, unpushed commits, or no usable worktree.
+    Skip(SyncStatus),
+    /// A bare `--mirror` clone (`sync_mirror`), after the GitHub fork if
+    /// `on_github`.
+    Mirror { on_github: bool },
+    /// Not cloned: only the GitHub fork is synced (`gh repo sync`).
+    Remote,
+    /// Update the clone's default branch.
+    Clone(Box<CloneSync>),
+}
+
+/// The steps of a clone's update.
+#[allow(clippy::struct_excessive_bools)] // Independent steps
+pub(super) struct CloneSync {
+    pub worktree: Worktree,
+    /// Through the GitHub fork (`gh repo sync`, then `origin`); otherwise
+    /// straight from the `upstream` remote with plain git.
+    pub on_github: bool,
+    /// URL of the clone's missing `upstream` remote, added before updating.
+    pub add_upstream: Option<String>,
+    /// The repo's configured `update`; a fast-forward without one.
+    pub update: Option<LocalUpdate>,
+    /// Whether uncommitted changes may be left for `--autostash`.
+    pub autostash: bool,
+    /// Whether uncommitted changes are stashed by hand first.
+    pub stash: bool,
+    /// Whether the default branch is checked out first (and the original
+    /// branch again at the end).
+    pub switch: bool,
+    /// When `gh repo sync` reports a diverged fork, whether it's forced.
+    pub force: ForceSync,
+    /// When the fast-forward from the GitHub fork fails (no `update` set):
+    /// `Some(true)` resets to it (`reset_fallback`), `Some(false)` fails
+    /// with `NEEDS_PULL`. `None` passes the failure on as it is.
+    pub fallback_reset: Option<bool>,
+}
+
+impl CloneSync {
+    /// The ref the default branch is updated to.
+    pub fn target(&self, branch: &str) -> String {
+        if self.on_github {
+            format!("origin/{branch}")
+        } else {
+            format!("upstream/{branch}")
+        }
+    }
+}
+
+/// Decide how `fork` is synced. Only reads: the opt-out file, the clone's
+/// worktree state and whether it has unpushed commits.
+pub(super) fn route(fork: &Fork, options: &SyncOptions) -> Route {
+    if let Some(reason) = opt_out_reason(&fork.local_path) {
+        return Route::Skip(SyncStatus::Skipped(reason));
+    }
+    let on_github = fork.is_github_fork() && !options.local_only;
+    let cloned = fork.local_path.exists();
+    if options.is_mirror(fork) && cloned {
+        return Route::Mirror { on_github };
+    }
+    if !cloned {
+        return if on_github {
+            Route::Remote
+        } else {
+            Route::Skip(SyncStatus::Skipped("offline: not cloned".to_string()))
+        };
+    }
+
+    let branch = &fork.default_branch;
+    let worktree = match Worktree::for_branch(fork.local_path.to_string_lossy().to_string(), branch)
+    {
+        Ok(worktree) => worktree,
+        Err(status) => return Route::Skip(status),
+    };
+    if options.skip_dirty && worktree.is_dirty {
+        return Route::Skip(SyncStatus::Skipped(DIRTY.to_string()));
+    }
+    // A rebase keeps unpushed commits on top
+    let update = options.update_for(fork);
+    if on_github
+        && update != Some(LocalUpdate::Rebase)
+        && has_unpushed_commits(&worktree.path, fork)
+    {
+        return Route::Skip(SyncStatus::Skipped("unpushed commits".to_string()));
+    }
+
+    let autostash = options.autostash_for(fork);
+    Route::Clone(Box::new(CloneSync {
+        add_upstream: missing_upstream_url(fork, options.clone_url_template.as_deref()),
+        on_github,
+        update,
+        autostash,
+        stash: worktree.is_dirty && !worktree.will_autostash(branch, autostash),
+        switch: worktree.original_branch != *branch,
+        force: options.force_for(fork),
+        fallback_reset: (on_github && update.is_none()).then_some(options.reset_fallback),
+        worktree,
+    }))
+}
@@ -0,0 +1,77 @@
+use super::worktree::{git_ok, git_stdout};
+use crate::types::StashRecord;
+
+/// Where a recorded auto-stash is in its clone's stash list (`stash@{n}`),
+/// or `None` once it's gone (popped, dropped, or the clone moved).
+pub fn find_stash(stash: &StashRecord) -> Option<usize> {
+    git_stdout(
+        &stash.path.to_string_lossy(),
+        &["stash", "list", "--format=%H"],
+    )?
+    .lines()
+    .position(|oid| oid == stash.oid)
+}
+
+/// Put a leftover auto-stash back: check out the branch it was made on and
+/// pop it. If the pop conflicts, git keeps the stash entry.
+pub fn restore_stash(stash: &StashRecord) -> Result<(), String> {
+    let path = stash.path.to_string_lossy();
+    let index = find_stash(stash).ok_or("the stash is gone (restored or dropped)")?;
+    let branch = git_stdout(&path, &["rev-parse", "--abbrev-ref", "HEAD"]);
+    if branch.as_deref() != Some(stash.branch.as_str())
+        && !git_ok(&path, &["checkout", &stash.branch])
+    {
+        return Err(format!(
+            "couldn't check out {} (uncommitted changes?)",
+            stash.branch
+        ));
+    }
+    let entry = format!("stash@{{{index}}}");
+    if git_ok(&path, &["stash", "pop", &entry]) {
+        Ok(())
+    } else {
+        Err(format!(
+            "restoring {entry} conflicted; resolve it in {path} and run `git stash drop {entry}`"
+        ))
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::Fixture;
+    use super::super::worktree::Worktree;
+    use super::*;
+
+    #[test]
+    fn test_restore_leftover_stash() {
+        let fixture = Fixture::new();
+        fixture.switch_clone_to("feature");
+        let readme = fixture.clone.join("README.md");
+        std::fs::write(&readme, "work in progress").unwrap();
+
+        // A sync that stashed and switched branches, then died
+        let mut worktree =
+            Worktree::for_branch(fixture.clone.to_string_lossy().to_string(), "main").unwrap();
+        worktree.stash_and_checkout("main", false, &|_| {}).unwrap();
+        let stash = worktree.stash_record(&fixture.fork()).unwrap();
+        assert_eq!(find_stash(&stash), Some(0));
+
+        // Stashed later by hand, on top of it
+        std::fs::write(&readme, "unrelated").unwrap();
+        assert!(git_ok(&fixture.clone.to_string_lossy(), &["stash", "push"]));
+        assert_eq!(find_stash(&stash), Some(1));
+
+        restore_stash(&stash).unwrap();
+        assert_eq!(fixture.clone_branch(), "feature");
+        assert_eq!(
+            std::fs::read_to_string(&readme).unwrap(),
+            "work in progress"
+        );
+        assert_eq!(find_stash(&stash), None);
+        assert!(restore_stash(&stash).is_err());
+    }
+}
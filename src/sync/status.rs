@@ -0,0 +1,197 @@
+use super::opt_out::opt_out_reason;
+use super::worktree::{git, git_stdout};
+use crate::local::remote_url;
+use crate::types::{Fork, GitStatus, SyncResult};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+
+/// Read the branch, dirty state, ahead/behind, stash count and remotes of
+/// the clone at `path` in the background.
+pub fn git_status_async(id: String, path: PathBuf, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        let result = git_status(&path.to_string_lossy());
+        let _ = tx.send(SyncResult::GitStatus(id, result));
+    });
+}
+
+/// Check in the background which of the clones have uncommitted changes
+/// (the ones a sync would stash).
+pub fn check_dirty_async(forks: Vec<Fork>, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        for fork in forks {
+            let dirty = is_dirty(&fork.local_path.to_string_lossy());
+            if tx.send(SyncResult::Dirty(fork.full_name(), dirty)).is_err() {
+                return;
+            }
+        }
+    });
+}
+
+/// Check in the background which clones hold local work: uncommitted
+/// changes, or commits on a local branch that aren't on any remote.
+pub fn check_local_work_async(forks: Vec<Fork>, tx: mpsc::Sender<SyncResult>) {
+    thread::spawn(move || {
+        for fork in forks {
+            let busy = has_local_work(&fork.local_path.to_string_lossy());
+            if tx
+                .send(SyncResult::LocalWork(fork.full_name(), busy))
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+}
+
+/// Read-only look at a clone for `repo-syncer status`: how many commits its
+/// default branch is behind `upstream/<branch>` as of the last fetch (`None`
+/// without that ref), and whether it has uncommitted changes. Fetches nothing.
+pub fn clone_snapshot(fork: &Fork) -> (Option<u32>, bool) {
+    let path = fork.local_path.to_string_lossy();
+    let branch = &fork.default_branch;
+    let behind = git_stdout(
+        &path,
+        &[
+            "rev-list",
+            "--count",
+            &format!("{branch}..upstream/{branch}"),
+        ],
+    )
+    .and_then(|count| count.parse().ok());
+    (behind, is_dirty(&path))
+}
+
+fn is_dirty(path: &str) -> bool {
+    git(path, &["status", "--porcelain"]).is_ok_and(|o| o.status.success() && !o.stdout.is_empty())
+}
+
+/// What the repo at `path` holds that may exist nowhere else, e.g. shown
+/// before deleting it: "uncommitted changes" and/or "unpushed commits".
+pub fn unsaved_work(path: &Path) -> Vec<&'static str> {
+    let path = path.to_string_lossy();
+    let mut work = Vec::new();
+    if is_dirty(&path) {
+        work.push("uncommitted changes");
+    }
+    if has_unpushed_branches(&path) {
+        work.push("unpushed commits");
+    }
+    work
+}
+
+fn has_local_work(path: &str) -> bool {
+    is_dirty(path) || has_unpushed_branches(path)
+}
+
+/// Whether a local branch has commits that aren't on any remote.
+fn has_unpushed_branches(path: &str) -> bool {
+    git_stdout(
+        path,
+        &[
+            "log",
+            "--branches",
+            "--not",
+            "--remotes",
+            "-1",
+            "--format=%H",
+        ],
+    )
+    .is_some_and(|oid| !oid.is_empty())
+}
+
+fn git_status(path: &str) -> Result<GitStatus, String> {
+    let output = git(path, &["status", "--porcelain=v2", "--branch"]).map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr
+            .lines()
+            .next()
+            .unwrap_or("git status failed")
+            .to_string());
+    }
+    let mut status = parse_status(&String::from_utf8_lossy(&output.stdout));
+    status.stashes = git_stdout(path, &["stash", "list"]).map_or(0, |s| s.lines().count());
+    status.opt_out = opt_out_reason(Path::new(path));
+    status.origin = remote_url(Path::new(path), "origin");
+    status.upstream = remote_url(Path::new(path), "upstream");
+    Ok(status)
+}
+
+/// Parse `git status --porcelain=v2 --branch` (the stash count is left at 0,
+/// and the opt-out and remotes unread).
+fn parse_status(porcelain: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    for line in porcelain.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            status.branch = (head != "(detached)").then(|| head.to_string());
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            // "+<ahead> -<behind>"
+            let mut counts = ab
+                .split_whitespace()
+                .map(|n| n.trim_start_matches(['+', '-']).parse().unwrap_or(0));
+            status.ahead_behind = Some((counts.next().unwrap_or(0), counts.next().unwrap_or(0)));
+        } else if !line.starts_with('#') && !line.is_empty() {
+            status.changes += 1;
+        }
+    }
+    status
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{commit, Fixture};
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        let porcelain = "# branch.oid 1234abcd\n\
+                         # branch.head main\n\
+                         # branch.upstream origin/main\n\
+                         # branch.ab +2 -5\n\
+                         1 .M N... 100644 100644 100644 abc abc src/main.rs\n\
+                         ? notes.txt\n";
+        assert_eq!(
+            parse_status(porcelain),
+            GitStatus {
+                branch: Some("main".to_string()),
+                changes: 2,
+                ahead_behind: Some((2, 5)),
+                stashes: 0,
+                opt_out: None,
+                origin: None,
+                upstream: None,
+            }
+        );
+
+        // Detached, clean, no upstream
+        let porcelain = "# branch.oid 1234abcd\n# branch.head (detached)\n";
+        assert_eq!(parse_status(porcelain), GitStatus::default());
+    }
+
+    #[test]
+    fn test_local_work() {
+        let fixture = Fixture::new();
+        let path = fixture.clone.to_string_lossy().to_string();
+        assert!(!has_local_work(&path));
+
+        std::fs::write(fixture.clone.join("notes.txt"), "wip").unwrap();
+        assert!(has_local_work(&path));
+        std::fs::remove_file(fixture.clone.join("notes.txt")).unwrap();
+
+        // A commit on a branch that was never pushed
+        fixture.switch_clone_to("feature");
+        commit(&fixture.clone, "feature.txt", "new");
+        assert!(has_local_work(&path));
+        assert_eq!(unsaved_work(&fixture.clone), ["unpushed commits"]);
+        std::fs::write(fixture.clone.join("notes.txt"), "wip").unwrap();
+        assert_eq!(
+            unsaved_work(&fixture.clone),
+            ["uncommitted changes", "unpushed commits"]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use super::worktree::{git_ok, git_stdout};
+use super::SyncOptions;
+use crate::types::{Fork, SyncResult, SyncStatus};
+use std::collections::BTreeSet;
+use std::sync::mpsc;
+
+/// Mirror upstream's tags after a sync, if enabled: fetch them into the
+/// clone and push the ones the fork (`origin`) is missing, since `gh repo
+/// sync` only moves branch tips. Nothing is pushed offline or for repos that
+/// aren't GitHub forks.
+pub(super) fn mirror_upstream_tags(
+    fork: &Fork,
+    path: &str,
+    options: &SyncOptions,
+    tx: &mpsc::Sender<SyncResult>,
+) -> Result<(), SyncStatus> {
+    if !options.mirror_tags {
+        return Ok(());
+    }
+    let _ = tx.send(SyncResult::StatusUpdate(fork.full_name(), SyncStatus::Tags));
+    let push = fork.is_github_fork() && !options.local_only;
+    let pushed = mirror_tags(path, push)?;
+    if !pushed.is_empty() {
+        let mut log = vec![format!(
+            "Pushed {} upstream tag(s) to the fork:",
+            pushed.len()
+        )];
+        log.extend(pushed);
+        let _ = tx.send(SyncResult::Log(fork.full_name(), log));
+    }
+    Ok(())
+}
+
+/// `git fetch upstream --tags`, then (if `push`) push the upstream tags
+/// `origin` doesn't have. Returns the pushed tag names.
+fn mirror_tags(path: &str, push: bool) -> Result<Vec<String>, SyncStatus> {
+    if !git_ok(path, &["fetch", "upstream", "--tags"]) {
+        return Err(SyncStatus::Failed("fetch upstream tags failed".to_string()));
+    }
+    if !push {
+        return Ok(Vec::new());
+    }
+    let (Some(upstream), Some(origin)) =
+        (remote_tags(path, "upstream"), remote_tags(path, "origin"))
+    else {
+        return Err(SyncStatus::Failed("listing tags failed".to_string()));
+    };
+    let missing: Vec<String> = upstream.difference(&origin).cloned().collect();
+    if missing.is_empty() {
+        return Ok(missing);
+    }
+    let refs: Vec<String> = missing
+        .iter()
+        .map(|tag| format!("refs/tags/{tag}"))
+        .collect();
+    let mut args = vec!["push", "origin"];
+    args.extend(refs.iter().map(String::as_str));
+    if git_ok(path, &args) {
+        Ok(missing)
+    } else {
+        Err(SyncStatus::Failed("pushing tags failed".to_string()))
+    }
+}
+
+/// Tag names on `remote`, from `git ls-remote --tags` (peeled `^{}` entries
+/// folded into their tag).
+fn remote_tags(path: &str, remote: &str) -> Option<BTreeSet<String>> {
+    let listing = git_stdout(path, &["ls-remote", "--tags", remote])?;
+    Some(
+        listing
+            .lines()
+            .filter_map(|line| line.split_once("refs/tags/"))
+            .map(|(_, tag)| tag.trim_end_matches("^{}").to_string())
+            .collect(),
+    )
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::Fixture;
+    use super::*;
+
+    #[test]
+    fn test_mirror_tags_pushes_missing_upstream_tags() {
+        let fixture = Fixture::new();
+        fixture.upstream_tag("v1.0.0");
+        fixture.upstream_tag("v1.1.0");
+        let clone = fixture.clone.to_string_lossy();
+
+        assert_eq!(
+            mirror_tags(&clone, true),
+            Ok(vec!["v1.0.0".to_string(), "v1.1.0".to_string()])
+        );
+        assert_eq!(
+            remote_tags(&clone, "origin"),
+            remote_tags(&clone, "upstream")
+        );
+        // Nothing left to push
+        assert_eq!(mirror_tags(&clone, true), Ok(Vec::new()));
+    }
+}
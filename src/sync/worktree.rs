@@ -0,0 +1,332 @@
+use crate::logging::LoggedOutput;
+use crate::tools;
+use crate::types::{Fork, StashRecord, SyncStatus};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::sync::OnceLock;
+
+/// Run `git -C <path> <args>`.
+pub(super) fn git(path: &str, args: &[&str]) -> std::io::Result<Output> {
+    tools::git().arg("-C").arg(path).args(args).logged_output()
+}
+
+/// Run `git -C <path> <args>` and report whether it succeeded.
+pub(super) fn git_ok(path: &str, args: &[&str]) -> bool {
+    git(path, args).is_ok_and(|o| o.status.success())
+}
+
+/// Run `git -C <path> <args>` and return its trimmed stdout on success.
+pub(super) fn git_stdout(path: &str, args: &[&str]) -> Option<String> {
+    match git(path, args) {
+        Ok(output) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Whether git can carry uncommitted changes across a merge itself
+/// (`git merge --autostash`, git 2.27+).
+fn supports_autostash() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| {
+        git_stdout(".", &["--version"])
+            .and_then(|version| parse_version(&version))
+            .is_some_and(|version| version >= (2, 27))
+    })
+}
+
+/// Major and minor version from `git --version` ("git version 2.39.5").
+fn parse_version(output: &str) -> Option<(u32, u32)> {
+    let mut parts = output.strip_prefix("git version ")?.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// How a sync stashes uncommitted changes by hand.
+pub(super) const STASH_PUSH: [&str; 4] = ["stash", "push", "-m", "repo-syncer auto-stash"];
+
+/// Working-tree state captured before a local sync so it can be restored
+/// afterwards: the branch the user was on and whether we stashed changes.
+pub(super) struct Worktree {
+    pub path: String,
+    pub original_branch: String,
+    pub is_dirty: bool,
+    stash: Stash,
+    pub switched_branch: bool,
+}
+
+/// What a sync did with a clone's uncommitted changes.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Stash {
+    /// Nothing (yet)
+    None,
+    /// Stashed by hand; `restore` pops them
+    Manual,
+    /// Left for `--autostash` to carry across the update. `top` is the top
+    /// of the stash list before it, to tell whether git failed to apply them
+    /// (it keeps them in the stash list then).
+    Autostash { top: Option<String> },
+}
+
+impl Worktree {
+    /// Inspect the worktree of a clone in which `branch` should be synced.
+    ///
+    /// Git refuses to check out a branch that is already checked out in
+    /// another linked worktree, so if `branch` lives in one we sync there
+    /// instead of in the main clone.
+    pub fn for_branch(path: String, branch: &str) -> Result<Self, SyncStatus> {
+        let worktree = Self::inspect(path)?;
+        if worktree.original_branch == branch {
+            return Ok(worktree);
+        }
+        let Some(listing) = git_stdout(&worktree.path, &["worktree", "list", "--porcelain"]) else {
+            return Ok(worktree);
+        };
+        match find_branch_worktree(&listing, branch) {
+            Some(linked) if linked.path == worktree.path => Ok(worktree),
+            Some(linked) if linked.prunable || !Path::new(&linked.path).exists() => {
+                Err(SyncStatus::Skipped(format!(
+                    "{branch} checked out in missing worktree (git worktree prune)"
+                )))
+            }
+            Some(linked) => Self::inspect(linked.path),
+            None => Ok(worktree),
+        }
+    }
+
+    /// Inspect a local clone: dirty state and current branch.
+    pub fn inspect(path: String) -> Result<Self, SyncStatus> {
+        let is_dirty = match git(&path, &["status", "--porcelain"]) {
+            Ok(output) => !output.stdout.is_empty(),
+            Err(e) => return Err(SyncStatus::failed(&e.to_string())),
+        };
+
+        let Some(original_branch) = git_stdout(&path, &["rev-parse", "--abbrev-ref", "HEAD"])
+        else {
+            return Err(SyncStatus::Failed("get branch failed".to_string()));
+        };
+
+        Ok(Self {
+            path,
+            original_branch,
+            is_dirty,
+            stash: Stash::None,
+            switched_branch: false,
+        })
+    }
+
+    /// Stash uncommitted changes (if any) and check out `branch`.
+    /// On failure the stash is restored before returning the error status.
+    ///
+    /// If `branch` is already checked out and the caller's update can pass
+    /// `--autostash` (`autostash`), the changes are left in place for git to
+    /// carry across the merge or rebase, with no stash/pop cycle of our own.
+    /// Old gits without `merge --autostash` get the manual stash.
+    pub fn stash_and_checkout(
+        &mut self,
+        branch: &str,
+        autostash: bool,
+        send: &impl Fn(SyncStatus),
+    ) -> Result<(), SyncStatus> {
+        if self.will_autostash(branch, autostash) {
+            self.stash = Stash::Autostash {
+                top: self.stash_top(),
+            };
+            return Ok(());
+        }
+        if self.is_dirty {
+            self.stash(send)?;
+        }
+
+        if self.original_branch != branch {
+            if !git_ok(&self.path, &["checkout", branch]) {
+                self.abort();
+                return Err(SyncStatus::Failed("checkout failed".to_string()));
+            }
+            self.switched_branch = true;
+        }
+
+        Ok(())
+    }
+
+    /// Whether `stash_and_checkout` leaves the changes for `--autostash`.
+    pub fn will_autostash(&self, branch: &str, autostash: bool) -> bool {
+        self.is_dirty && autostash && self.original_branch == branch && supports_autostash()
+    }
+
+    /// Stash the changes by hand after all, for an update that can't carry
+    /// them (a reset). No-op unless they were left for `--autostash`.
+    pub fn stash_instead_of_autostash(
+        &mut self,
+        send: &impl Fn(SyncStatus),
+    ) -> Result<(), SyncStatus> {
+        if !self.autostash() {
+            return Ok(());
+        }
+        self.stash(send)
+    }
+
+    /// Whether the update should pass `--autostash`.
+    pub fn autostash(&self) -> bool {
+        matches!(self.stash, Stash::Autostash { .. })
+    }
+
+    fn stash_top(&self) -> Option<String> {
+        git_stdout(&self.path, &["rev-parse", "-q", "--verify", "stash@{0}"])
+    }
+
+    fn stash(&mut self, send: &impl Fn(SyncStatus)) -> Result<(), SyncStatus> {
+        send(SyncStatus::Stashing);
+        if !git_ok(&self.path, &STASH_PUSH) {
+            return Err(SyncStatus::Failed("stash failed".to_string()));
+        }
+        self.stash = Stash::Manual;
+        Ok(())
+    }
+
+    /// The stash made by `stash_and_checkout`, if any, for the cache to keep
+    /// until the sync finishes.
+    pub fn stash_record(&self, fork: &Fork) -> Option<StashRecord> {
+        if self.stash != Stash::Manual {
+            return None;
+        }
+        Some(StashRecord {
+            id: fork.full_name(),
+            path: PathBuf::from(&self.path),
+            oid: git_stdout(&self.path, &["rev-parse", "stash@{0}"])?,
+            branch: self.original_branch.clone(),
+            stashed_at: Utc::now(),
+        })
+    }
+
+    /// Restore the original branch and pop the stash, reporting progress.
+    ///
+    /// If the pop conflicts, git leaves conflict markers in the working tree
+    /// and keeps the stash entry; that is reported as `NeedsAttention` with
+    /// recovery instructions instead of silently claiming success.
+    pub fn restore(&self, send: &impl Fn(SyncStatus)) -> Result<(), SyncStatus> {
+        if self.switched_branch || self.stash == Stash::Manual {
+            send(SyncStatus::Restoring);
+        }
+        if self.switched_branch {
+            let _ = git(&self.path, &["checkout", &self.original_branch]);
+        }
+        if self.stash == Stash::Manual && !git_ok(&self.path, &["stash", "pop"]) {
+            return Err(SyncStatus::NeedsAttention(format!(
+                "Synced, but restoring your uncommitted changes on {} conflicted. \
+                 They are still saved as stash@{{0}}. In {}: resolve the conflicts \
+                 and run `git stash drop`, or `git checkout -- . && git stash pop` \
+                 to start over.",
+                self.original_branch, self.path
+            )));
+        }
+        if let Stash::Autostash { top } = &self.stash {
+            if self.stash_top() != *top {
+                return Err(SyncStatus::NeedsAttention(format!(
+                    "Synced, but git couldn't put your uncommitted changes back on {} \
+                     (they conflict with the new commits). They are saved as stash@{{0}}. \
+                     In {}: run `git stash pop` and resolve the conflicts.",
+                    self.original_branch, self.path
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Bring submodules in line with the checked-out commit
+    /// (`git submodule update --init --recursive`). No-op without `.gitmodules`.
+    pub fn update_submodules(&self, send: &impl Fn(SyncStatus)) -> Result<(), SyncStatus> {
+        if !Path::new(&self.path).join(".gitmodules").exists() {
+            return Ok(());
+        }
+        send(SyncStatus::Submodules);
+        if git_ok(
+            &self.path,
+            &["submodule", "update", "--init", "--recursive"],
+        ) {
+            Ok(())
+        } else {
+            Err(SyncStatus::Failed("submodule update failed".to_string()))
+        }
+    }
+
+    /// Restore the original branch and pop the stash without reporting.
+    /// Used on error paths where the final status is already decided.
+    pub fn abort(&self) {
+        if self.switched_branch {
+            let _ = git(&self.path, &["checkout", &self.original_branch]);
+        }
+        if self.stash == Stash::Manual {
+            let _ = git(&self.path, &["stash", "pop"]);
+        }
+    }
+}
+
+/// A linked worktree entry from `git worktree list --porcelain`.
+#[derive(Debug, PartialEq, Eq)]
+struct LinkedWorktree {
+    path: String,
+    prunable: bool,
+}
+
+/// Find the worktree that has `refs/heads/<branch>` checked out.
+fn find_branch_worktree(porcelain: &str, branch: &str) -> Option<LinkedWorktree> {
+    let wanted = format!("refs/heads/{branch}");
+    porcelain.split("\n\n").find_map(|entry| {
+        let mut path = None;
+        let mut matches = false;
+        let mut prunable = false;
+        for line in entry.lines() {
+            if let Some(p) = line.strip_prefix("worktree ") {
+                path = Some(p.to_string());
+            } else if line.strip_prefix("branch ") == Some(wanted.as_str()) {
+                matches = true;
+            } else if line.starts_with("prunable") {
+                prunable = true;
+            }
+        }
+        path.filter(|_| matches)
+            .map(|path| LinkedWorktree { path, prunable })
+    })
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("git version 2.39.5"), Some((2, 39)));
+        assert_eq!(
+            parse_version("git version 2.27.0.windows.1\n"),
+            Some((2, 27))
+        );
+        assert_eq!(parse_version("not git"), None);
+    }
+
+    #[test]
+    fn test_find_branch_worktree() {
+        let listing = "worktree /src/repo\nHEAD abc\nbranch refs/heads/feature\n\n\
+                       worktree /src/repo-main\nHEAD def\nbranch refs/heads/main\n\n\
+                       worktree /tmp/gone\nHEAD 123\nbranch refs/heads/old\nprunable gitdir file points to non-existent location";
+
+        assert_eq!(
+            find_branch_worktree(listing, "main"),
+            Some(LinkedWorktree {
+                path: "/src/repo-main".to_string(),
+                prunable: false
+            })
+        );
+        assert!(find_branch_worktree(listing, "old").is_some_and(|w| w.prunable));
+        // Prefix of another branch name must not match
+        assert_eq!(find_branch_worktree(listing, "mai"), None);
+        assert_eq!(find_branch_worktree(listing, "develop"), None);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::github::host;
+use crate::logging::LoggedOutput;
+use crate::tools;
+use crate::types::Fork;
+use serde_json::{json, Value};
+use std::io;
+use std::process::{Command, Output};
+use std::sync::OnceLock;
+
+/// The token for direct API calls: `GH_TOKEN`, then `GITHUB_TOKEN` (the
+/// order `gh` reads them in).
+fn token() -> Option<String> {
+    ["GH_TOKEN", "GITHUB_TOKEN"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|token| !token.is_empty())
+}
+
+/// Whether to talk to GitHub with `curl` and a token instead of `gh`: `gh`
+/// isn't installed but `GH_TOKEN` or `GITHUB_TOKEN` is set. Only the fork
+/// list, fork sync and the behind-by lookups have a direct fallback; cloning
+/// uses plain `git clone` (see `Config::clone_url_template`).
+pub fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED
+        .get_or_init(|| token().is_some() && tools::gh().arg("--version").logged_output().is_err())
+}
+
+/// The API root for `host()`: `api.github.com`, or `/api/v3` (REST) and
+/// `/api/graphql` on GitHub Enterprise Server.
+fn api_url(path: &str) -> String {
+    let host = host();
+    if host == "github.com" {
+        format!("https://api.github.com/{path}")
+    } else if path == "graphql" {
+        format!("https://{host}/api/graphql")
+    } else {
+        format!("https://{host}/api/v3/{path}")
+    }
+}
+
+/// `curl` a JSON request to the API. The token goes in on stdin (`-H @-`)
+/// so it never shows up in the process list or the audit log. On an HTTP
+/// error, stderr is replaced with `HTTP <code>: <message from GitHub>`, the
+/// way `gh` reports it.
+fn request(method: &str, path: &str, body: Option<&Value>) -> io::Result<Output> {
+    let token = token().unwrap_or_default();
+    let headers = format!(
+        "Authorization: Bearer {token}\nAccept: application/vnd.github+json\nContent-Type: application/json\n"
+    );
+    let mut command = Command::new("curl");
+    command.args(["-sS", "--fail-with-body", "-X", method, "-H", "@-"]);
+    if let Some(body) = body {
+        command.args(["--data-binary", &body.to_string()]);
+    }
+    command.args(["-w", "\n%{http_code}", &api_url(path)]);
+    let mut output = command.logged_output_with_stdin(headers.as_bytes())?;
+
+    // The status code is on the last line, after the body
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let (body, code) = stdout.rsplit_once('\n').unwrap_or(("", stdout.as_str()));
+    if !output.status.success() {
+        let message = serde_json::from_str::<Value>(body)
+            .ok()
+            .and_then(|v| v["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| String::from_utf8_lossy(&output.stderr).trim().to_string());
+        output.stderr = format!("HTTP {code}: {message}").into_bytes();
+    }
+    output.stdout = body.as_bytes().to_vec();
+    Ok(output)
+}
+
+/// Run a GraphQL query; stdout is the response, as from `gh api graphql`.
+pub fn graphql(query: &str, variables: &Value) -> io::Result<Output> {
+    request(
+        "POST",
+        "graphql",
+        Some(&json!({"query": query, "variables": variables})),
+    )
+}
+
+/// Sync the fork's default branch with upstream, like `gh repo sync`. A
+/// merge conflict (409) is reported as diverged; `force` instead points the
+/// branch at upstream's head, dropping the fork's own commits.
+pub fn sync_fork(fork: &Fork, force: bool) -> io::Result<Output> {
+    let branch = &fork.default_branch;
+    if !force {
+        let mut output = request(
+            "POST",
+            &format!("repos/{}/merge-upstream", fork.full_name()),
+            Some(&json!({ "branch": branch })),
+        )?;
+        if output.stderr.starts_with(b"HTTP 409") {
+            output.stderr = format!(
+                "can't sync because there are diverging changes ({})",
+                String::from_utf8_lossy(&output.stderr)
+            )
+            .into_bytes();
+        }
+        return Ok(output);
+    }
+
+    let head = request(
+        "GET",
+        &format!(
+            "repos/{}/{}/branches/{branch}",
+            fork.parent_owner, fork.parent_name
+        ),
+        None,
+    )?;
+    let sha = serde_json::from_slice::<Value>(&head.stdout)
+        .ok()
+        .and_then(|v| v["commit"]["sha"].as_str().map(str::to_string));
+    let Some(sha) = sha.filter(|_| head.status.success()) else {
+        return Ok(head);
+    };
+    request(
+        "PATCH",
+        &format!("repos/{}/git/refs/heads/{branch}", fork.full_name()),
+        Some(&json!({ "sha": sha, "force": true })),
+    )
+}
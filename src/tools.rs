@@ -0,0 +1,80 @@
+use crate::paths::expand_home;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::RwLock;
+
+/// The `git` and `gh` executables to run (`[tools]`), e.g. a wrapper script
+/// or one of several installed versions, and the arguments every git call
+/// starts with. gh has no global flags to put before its subcommand; its
+/// host comes from `GH_HOST` (a profile's `host`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tools {
+    /// Path to `git` (`~` is expanded); found on `PATH` if unset.
+    pub git: Option<PathBuf>,
+    /// Arguments before every git subcommand, e.g. `["-c", "protocol.version=2"]`.
+    pub git_args: Vec<String>,
+    /// Path to `gh` (`~` is expanded); found on `PATH` if unset.
+    pub gh: Option<PathBuf>,
+}
+
+/// The tools of the running session, set by `configure`.
+static TOOLS: RwLock<Option<Tools>> = RwLock::new(None);
+
+/// Use `tools` for every command built from now on.
+pub fn configure(tools: &Tools) {
+    if let Ok(mut current) = TOOLS.write() {
+        *current = Some(tools.clone());
+    }
+}
+
+/// A `git` command with the configured path and default arguments.
+pub fn git() -> Command {
+    let tools = current();
+    command("git", tools.git.as_deref(), &tools.git_args)
+}
+
+/// A `gh` command with the configured path.
+pub fn gh() -> Command {
+    command("gh", current().gh.as_deref(), &[])
+}
+
+fn current() -> Tools {
+    TOOLS
+        .read()
+        .ok()
+        .and_then(|t| t.clone())
+        .unwrap_or_default()
+}
+
+fn command(program: &str, path: Option<&Path>, args: &[String]) -> Command {
+    let mut command = match path {
+        Some(path) => Command::new(expand_home(path)),
+        None => Command::new(program),
+    };
+    command.args(args);
+    command
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_prepends_default_args() {
+        let args = vec!["-c".to_string(), "protocol.version=2".to_string()];
+        let mut cmd = command("git", Some(Path::new("/opt/git/bin/git")), &args);
+        cmd.args(["fetch", "origin"]);
+        assert_eq!(cmd.get_program(), "/opt/git/bin/git");
+        assert_eq!(
+            cmd.get_args().collect::<Vec<_>>(),
+            ["-c", "protocol.version=2", "fetch", "origin"]
+        );
+        assert_eq!(command("gh", None, &[]).get_program(), "gh");
+    }
+}
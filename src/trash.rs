@@ -0,0 +1,118 @@
+use crate::paths::ToolHomes;
+use anyhow::{bail, Context, Result};
+use chrono::Local;
+use std::path::{Path, PathBuf};
+
+/// Directory under tool home that deleted clones are moved into.
+/// It's hidden, so local repo discovery skips it.
+pub const TRASH_DIR: &str = ".trash";
+
+/// Timestamp suffix of trashed clones, e.g. `cargo-20250101-120000`.
+const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Move a clone to `tool_home/.trash/<owner>/<name>-<timestamp>`.
+pub fn move_to_trash(tool_home: &Path, owner: &str, name: &str, path: &Path) -> Result<PathBuf> {
+    let dir = tool_home.join(TRASH_DIR).join(owner);
+    std::fs::create_dir_all(&dir).context("Failed to create trash directory")?;
+    let dest = dir.join(format!("{name}-{}", Local::now().format(STAMP_FORMAT)));
+    std::fs::rename(path, &dest)
+        .with_context(|| format!("Failed to move {} to trash", path.display()))?;
+    Ok(dest)
+}
+
+/// Delete the clone of `owner/name` at `path`: moved to the trash of the tool
+/// home it's in when `trash_homes` is set, else removed for good. Every
+/// clone deletion goes through here.
+pub fn delete_clone(
+    trash_homes: Option<&ToolHomes>,
+    owner: &str,
+    name: &str,
+    path: &Path,
+) -> Result<()> {
+    match trash_homes {
+        Some(homes) => {
+            let home = homes.root_of(path).unwrap_or(homes.primary());
+            move_to_trash(home, owner, name, path).map(|_| ())
+        }
+        None => std::fs::remove_dir_all(path)
+            .with_context(|| format!("Failed to remove {}", path.display())),
+    }
+}
+
+/// Trashed copies of `owner/name`, oldest first.
+pub fn trashed_copies(tool_home: &Path, owner: &str, name: &str) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(tool_home.join(TRASH_DIR).join(owner)) else {
+        return Vec::new();
+    };
+    let prefix = format!("{name}-");
+    let mut copies: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .filter(|e| {
+            e.file_name()
+                .to_str()
+                .and_then(|f| f.strip_prefix(&prefix))
+                .is_some_and(is_stamp)
+        })
+        .map(|e| e.path())
+        .collect();
+    // The timestamp format sorts chronologically
+    copies.sort();
+    copies
+}
+
+fn is_stamp(s: &str) -> bool {
+    chrono::NaiveDateTime::parse_from_str(s, STAMP_FORMAT).is_ok()
+}
+
+/// Move the most recent trashed copy of `id` (`owner/name`), from any tool
+/// home, back to `<that tool home>/<owner>/<name>`.
+pub fn restore(tool_homes: &ToolHomes, id: &str) -> Result<PathBuf> {
+    let Some((owner, name)) = id.split_once('/') else {
+        bail!("Expected OWNER/NAME, got {id:?}");
+    };
+    let Some((tool_home, latest)) = tool_homes
+        .roots()
+        .iter()
+        .filter_map(|root| Some((root, trashed_copies(root, owner, name).pop()?)))
+        .max_by(|(_, a), (_, b)| a.file_name().cmp(&b.file_name()))
+    else {
+        bail!("No trashed clone of {id} in any tool home's {TRASH_DIR}");
+    };
+    let dest = tool_home.join(owner).join(name);
+    if dest.exists() {
+        bail!("{} already exists", dest.display());
+    }
+    std::fs::create_dir_all(tool_home.join(owner)).context("Failed to create owner directory")?;
+    std::fs::rename(&latest, &dest)
+        .with_context(|| format!("Failed to restore {}", latest.display()))?;
+    Ok(dest)
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_and_restore() {
+        let home = std::env::temp_dir().join(format!("repo-syncer-trash-{}", std::process::id()));
+        let clone = home.join("me/cargo");
+        std::fs::create_dir_all(&clone).unwrap();
+        std::fs::write(clone.join("README"), "hi").unwrap();
+
+        let trashed = move_to_trash(&home, "me", "cargo", &clone).unwrap();
+        assert!(!clone.exists());
+        assert_eq!(trashed_copies(&home, "me", "cargo"), vec![trashed]);
+        assert!(trashed_copies(&home, "me", "car").is_empty());
+
+        let homes = ToolHomes::new(vec![home.join("elsewhere"), home.clone()]);
+        assert_eq!(restore(&homes, "me/cargo").unwrap(), clone);
+        assert!(clone.join("README").exists());
+        assert!(restore(&homes, "me/cargo").is_err());
+
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+}
@@ -0,0 +1,445 @@
+mod records;
+mod ui;
+
+pub use records::*;
+pub use ui::*;
+
+use crate::github::truncate_error;
+use crate::paths::ToolHomes;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+// ============================================================
+// STORAGE TRAIT
+// ============================================================
+
+/// Trait for fork metadata storage backends.
+/// Implementations can use `SQLite`, a JSON file, or any other datastore
+/// (picked with `store` in the config, see `cache::open_store`).
+pub trait ForkStore: Send {
+    /// Load all forks from storage.
+    fn load_forks(&self, tool_homes: &ToolHomes) -> Result<Vec<Fork>>;
+
+    /// Save multiple forks to storage.
+    fn save_forks(&self, forks: &[Fork]) -> Result<()>;
+
+    /// Remove a fork (`owner/name`) from storage.
+    fn remove_fork(&self, id: &str) -> Result<()>;
+
+    /// Remove every fork missing from `keep` (the result of a full refresh),
+    /// so deleted and archived forks don't linger. Returns the removed IDs.
+    fn prune_forks(&self, keep: &[Fork]) -> Result<Vec<String>>;
+
+    /// Check if the store is empty.
+    fn is_empty(&self) -> Result<bool>;
+
+    /// Get the timestamp of the last full sync.
+    fn last_full_sync(&self) -> Result<Option<DateTime<Utc>>>;
+
+    /// Set the timestamp of the last full sync.
+    fn set_last_full_sync(&self, when: DateTime<Utc>) -> Result<()>;
+
+    /// Load the set of ignored fork IDs (`owner/name`).
+    fn ignored_forks(&self) -> Result<HashSet<String>>;
+
+    /// Mark a fork as ignored (hidden from the list) or un-ignore it.
+    fn set_ignored(&self, id: &str, ignored: bool) -> Result<()>;
+
+    /// Add a finished session's usage counters to the local totals.
+    fn record_usage(&self, session: &UsageSession) -> Result<()>;
+
+    /// Load accumulated usage statistics for a calendar year.
+    fn usage_stats(&self, year: i32) -> Result<UsageStats>;
+
+    /// Load all fork reminders, keyed by fork ID (`owner/name`).
+    fn reminders(&self) -> Result<HashMap<String, Reminder>>;
+
+    /// Set or clear (`None`) the reminder for a fork.
+    fn set_reminder(&self, id: &str, reminder: Option<&Reminder>) -> Result<()>;
+
+    /// Load per-fork clone locations that replace `<tool home>/<owner>/<name>`,
+    /// keyed by fork ID (`owner/name`).
+    fn path_overrides(&self) -> Result<HashMap<String, PathBuf>>;
+
+    /// Set or clear (`None`) where a fork's clone lives.
+    fn set_path_override(&self, id: &str, path: Option<&Path>) -> Result<()>;
+
+    /// Persist the fork IDs of a starting sync batch, replacing any previous queue.
+    fn save_sync_queue(&self, ids: &[String]) -> Result<()>;
+
+    /// Drop a fork from the persisted queue once it has finished.
+    fn remove_from_sync_queue(&self, id: &str) -> Result<()>;
+
+    /// Load fork IDs left over from an interrupted batch, in queue order.
+    fn sync_queue(&self) -> Result<Vec<String>>;
+
+    /// Record an auto-stash made while syncing a fork, replacing the fork's
+    /// previous one.
+    fn record_stash(&self, stash: &StashRecord) -> Result<()>;
+
+    /// Forget a fork's auto-stash (its sync finished, or it was restored).
+    fn remove_stash(&self, id: &str) -> Result<()>;
+
+    /// Auto-stashes recorded by runs that didn't finish, oldest first.
+    fn stashes(&self) -> Result<Vec<StashRecord>>;
+
+    /// Record the backup ref made before a fork's clone was reset or
+    /// rebased, replacing the fork's previous one.
+    fn record_backup(&self, backup: &BackupRecord) -> Result<()>;
+
+    /// Forget a fork's backup ref (its sync was undone).
+    fn remove_backup(&self, id: &str) -> Result<()>;
+
+    /// The backup ref of a fork's last destructive sync, if any.
+    fn backup(&self, id: &str) -> Result<Option<BackupRecord>>;
+
+    /// Append commands to the audit log, dropping the oldest beyond
+    /// `AUDIT_LIMIT`.
+    fn record_commands(&self, entries: &[AuditEntry]) -> Result<()>;
+
+    /// The last `limit` commands of the audit log, newest first.
+    fn recent_commands(&self, limit: usize) -> Result<Vec<AuditEntry>>;
+
+    /// Save API responses for conditional requests (replacing ones for the
+    /// same request), dropping the oldest beyond `API_CACHE_LIMIT`.
+    fn save_api_responses(&self, responses: &[CachedResponse]) -> Result<()>;
+
+    /// Every saved API response.
+    fn api_responses(&self) -> Result<Vec<CachedResponse>>;
+
+    /// Record the fork list of a full refresh, keeping the previous one.
+    fn record_snapshot(&self, forks: &[Fork]) -> Result<()>;
+
+    /// Compare the last two refresh snapshots (`None` until there are two).
+    fn snapshot_diff(&self) -> Result<Option<SnapshotDiff>>;
+
+    /// Record how a fork's sync ended (a final `SyncStatus`), for the trend.
+    fn record_sync(&self, id: &str, status: &SyncStatus) -> Result<()>;
+
+    /// Syncs and failures per week over the last `weeks` weeks, oldest first.
+    fn sync_trend(&self, weeks: u32) -> Result<Vec<WeekTrend>>;
+
+    /// When each fork (`owner/name`) last synced successfully.
+    fn last_synced(&self) -> Result<HashMap<String, DateTime<Utc>>>;
+
+    /// The latest `per_fork` failures of each fork that has failed, newest
+    /// first.
+    fn recent_failures(&self, per_fork: usize) -> Result<HashMap<String, Vec<FailureRecord>>>;
+}
+
+// ============================================================
+// APPLICATION TYPES
+// ============================================================
+
+/// Upstreams without a push for this long are shown as dormant.
+pub const DORMANT_UPSTREAM_DAYS: i64 = 365;
+
+#[derive(Debug, Clone)]
+pub struct Fork {
+    pub name: String,
+    pub owner: String,
+    pub parent_owner: String,
+    pub parent_name: String,
+    pub default_branch: String,
+    pub local_path: PathBuf,
+    pub is_cloned: bool,
+    pub description: Option<String>,
+    pub primary_language: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Tip of the upstream default branch, as of the fetch (GraphQL only).
+    pub upstream_head: Option<String>,
+    /// When the upstream repo was last pushed to (GraphQL only).
+    pub upstream_pushed_at: Option<DateTime<Utc>>,
+    /// `public`, `private` or `internal` (GraphQL only).
+    pub visibility: Option<String>,
+    pub kind: RepoKind,
+}
+
+/// Where a repository in the list comes from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepoKind {
+    /// A GitHub fork of the authenticated user.
+    #[default]
+    Fork,
+    /// A local clone (not a GitHub fork) with an `upstream` remote, synced
+    /// with plain `git fetch` + `git merge --ff-only`.
+    UpstreamRemote,
+}
+
+impl Fork {
+    /// The visibility `V` switches to: private for public forks, else public.
+    /// `None` while the visibility is unknown (e.g. cached before it was fetched).
+    pub fn toggled_visibility(&self) -> Option<&'static str> {
+        match self.visibility.as_deref()? {
+            "public" => Some("private"),
+            _ => Some("public"),
+        }
+    }
+
+    /// Point the fork at a clone location, re-checking whether it's cloned.
+    pub fn relocate(&mut self, path: PathBuf) {
+        self.is_cloned = path.exists();
+        self.local_path = path;
+    }
+
+    /// Whether this is a GitHub fork (as opposed to a local upstream-remote repo).
+    pub fn is_github_fork(&self) -> bool {
+        self.kind == RepoKind::Fork
+    }
+
+    /// Whether the upstream hasn't been pushed to in `DORMANT_UPSTREAM_DAYS`.
+    pub fn is_upstream_dormant(&self, now: DateTime<Utc>) -> bool {
+        self.upstream_pushed_at
+            .is_some_and(|at| (now - at).num_days() >= DORMANT_UPSTREAM_DAYS)
+    }
+
+    /// Unique identifier for the fork (`owner/name`).
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.name)
+    }
+
+    /// A fork of `them/<name>` for tests, uncloned and with nothing else
+    /// known; set other fields with `..Fork::test(owner, name)`.
+    #[cfg(test)]
+    pub fn test(owner: &str, name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            owner: owner.to_string(),
+            parent_owner: "them".to_string(),
+            parent_name: name.to_string(),
+            default_branch: "main".to_string(),
+            local_path: PathBuf::from("/tmp/test"),
+            is_cloned: false,
+            description: None,
+            primary_language: None,
+            created_at: None,
+            updated_at: None,
+            upstream_head: None,
+            upstream_pushed_at: None,
+            visibility: None,
+            kind: RepoKind::Fork,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CacheStatus {
+    Fresh,
+    Stale { refreshing: bool },
+    Offline,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SyncStatus {
+    Pending,
+    Checking,
+    Cloning,
+    Stashing,
+    Fetching,
+    Syncing,
+    Restoring,
+    /// Rebasing local branches onto the updated default branch (opt-in)
+    Rebasing,
+    /// Updating submodules after a sync (opt-in)
+    Submodules,
+    /// Fetching upstream tags and pushing them to the fork (opt-in)
+    Tags,
+    /// Running a configured sync hook in the clone
+    RunningHook,
+    /// Opening a pull request to upstream
+    OpeningPr,
+    /// Flipping the fork between public and private
+    ChangingVisibility,
+    Archiving,
+    Deleting,
+    /// Sync completed. Option<u32> is the number of commits fast-forwarded.
+    Synced(Option<u32>),
+    Skipped(String),
+    Failed(String),
+    /// Synced, but the clone was left needing manual work (e.g. the stash
+    /// pop conflicted). Holds recovery instructions for the details pane.
+    NeedsAttention(String),
+}
+
+/// Steps of a sync shown in the progress: checking → stashing → syncing →
+/// pulling → restoring.
+pub const SYNC_STEPS: u8 = 5;
+
+/// Failure reason of a fork whose branch diverged from upstream, which only
+/// a force-sync (`F`) can bring back in line.
+pub const DIVERGED: &str = "diverged from upstream";
+
+/// Failure reason of a clone that can't be fast-forwarded, when hard resets
+/// aren't allowed (`reset_fallback`).
+pub const NEEDS_PULL: &str = "needs manual pull";
+
+/// Skip reason of a clone with uncommitted changes under `skip_dirty`.
+pub const DIRTY: &str = "dirty";
+
+impl SyncStatus {
+    pub fn is_diverged(&self) -> bool {
+        matches!(self, Self::Failed(reason) if reason == DIVERGED)
+    }
+
+    /// A failure holding the whole error (e.g. `gh` stderr); the list shows
+    /// its first line truncated, `E` shows all of it.
+    pub fn failed(err: &str) -> Self {
+        Self::Failed(err.trim().to_string())
+    }
+
+    pub fn display(&self) -> String {
+        match self {
+            Self::Pending => "Pending".to_string(),
+            Self::Checking => "Checking".to_string(),
+            Self::Cloning => "Cloning".to_string(),
+            Self::Stashing => "Stashing".to_string(),
+            Self::Fetching => "Fetching".to_string(),
+            Self::Syncing => "Syncing".to_string(),
+            Self::Restoring => "Restoring".to_string(),
+            Self::Rebasing => "Rebasing branches".to_string(),
+            Self::Submodules => "Submodules".to_string(),
+            Self::Tags => "Mirroring tags".to_string(),
+            Self::RunningHook => "Running hook".to_string(),
+            Self::OpeningPr => "Opening PR".to_string(),
+            Self::ChangingVisibility => "Changing visibility".to_string(),
+            Self::Archiving => "Archiving".to_string(),
+            Self::Deleting => "Deleting".to_string(),
+            Self::Synced(None) => "Synced".to_string(),
+            Self::Synced(Some(0)) => "Up-to-date".to_string(),
+            Self::Synced(Some(n)) => format!("+{n} commits"),
+            Self::Skipped(reason) => reason.clone(),
+            Self::Failed(err) => truncate_error(err),
+            Self::NeedsAttention(_) => "Needs attention".to_string(),
+        }
+    }
+
+    /// Which step of a sync (1 to `SYNC_STEPS`) this status is, for the
+    /// per-fork progress. `None` outside the sync pipeline.
+    pub fn sync_step(&self) -> Option<u8> {
+        match self {
+            Self::Checking => Some(1),
+            Self::Stashing => Some(2),
+            Self::Syncing => Some(3),
+            Self::Fetching | Self::Rebasing => Some(4),
+            Self::Restoring | Self::Submodules | Self::Tags => Some(5),
+            _ => None,
+        }
+    }
+
+    /// How a finished sync is recorded in the history (`None` while running).
+    pub fn outcome(&self) -> Option<&'static str> {
+        match self {
+            Self::Synced(_) => Some("synced"),
+            Self::Skipped(_) => Some("skipped"),
+            Self::Failed(_) => Some("failed"),
+            Self::NeedsAttention(_) => Some("attention"),
+            _ => None,
+        }
+    }
+
+    /// The message of a failed sync (or one needing attention), kept in the
+    /// fork's failure history.
+    pub fn failure(&self) -> Option<&str> {
+        match self {
+            Self::Failed(message) | Self::NeedsAttention(message) => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a final status for a sync batch.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            Self::Synced(_) | Self::Skipped(_) | Self::Failed(_) | Self::NeedsAttention(_)
+        )
+    }
+}
+
+#[derive(PartialEq, Clone)]
+pub enum Mode {
+    Selecting,
+    Search,
+    StatsOverlay,
+    UsageOverlay,
+    ReconcileOverlay,
+    RemindersOverlay,
+    StashesOverlay,
+    ProfilesOverlay,
+    ChangesOverlay,
+    LogOverlay,
+    PreviewOverlay,
+    PlanOverlay,
+    AuditOverlay,
+    Input,
+    ConfirmModal,
+    ErrorPopup,
+    Syncing,
+}
+
+// ============================================================
+// CHANNEL MESSAGES
+// ============================================================
+
+#[derive(Debug)]
+pub enum SyncResult {
+    // Results about one fork name it by `owner/name`: indices shift when a
+    // refresh or removal lands while the fork's operation is running
+    StatusUpdate(String, SyncStatus),
+    ForkCloned(String),
+    ForkArchived(String),
+    ForkDeleted(String),
+    /// A fork created from inside the TUI (`n`), with the clone error if
+    /// cloning it failed
+    ForkCreated(Fork, Option<String>),
+    ForksRefreshed(FetchedForks),
+    /// Result of an incremental refresh (`R`)
+    ForksUpdated(ForkDelta),
+    RefreshFailed(String),
+    /// An error occurred that may have an actionable fix
+    ActionableError(ErrorDetails),
+    /// Local clone scan finished
+    LocalScanned(Vec<LocalFinding>),
+    /// A `gh` call failed because the CLI isn't logged in; the queue is paused
+    AuthRequired,
+    /// Output lines for a fork's log (e.g. from a sync hook)
+    Log(String, Vec<String>),
+    /// On-disk size in bytes of a fork's clone (by `owner/name`)
+    DiskUsage(String, u64),
+    /// How many commits each fork (by `owner/name`) is behind upstream, for
+    /// the Behind column
+    Behind(HashMap<String, u32>),
+    /// A diverged fork was force-synced; its clone (if any) was reset,
+    /// discarding this many local commits
+    ForceSynced(String, usize),
+    /// A pull request to upstream was opened (or already existed) at this URL
+    PullRequestOpened(String, String),
+    /// A fork's visibility was changed (to `public` or `private`)
+    VisibilityChanged(String, String),
+    /// Open PRs and ahead branches of a fork (by `owner/name`), checked
+    /// before archiving or deleting it
+    RisksChecked(String, Result<ForkRisks, String>),
+    /// Upstream commits a fork (by `owner/name`) doesn't have yet, for the
+    /// details pane
+    UpstreamCommits(String, Result<UpstreamCommits, String>),
+    /// Local git status of a clone (by `owner/name`), for the details pane
+    GitStatus(String, Result<GitStatus, String>),
+    /// Incoming changes of a fork (by `owner/name`), for the preview overlay
+    Preview(String, Result<IncomingChanges, String>),
+    /// The commands syncing the confirm modal's forks would run
+    Plan(Vec<SyncPlan>),
+    /// Whether a clone (by `owner/name`) has uncommitted changes, for the
+    /// Sync confirm modal
+    Dirty(String, bool),
+    /// Uncommitted changes of a clone were stashed before syncing it
+    Stashed(StashRecord),
+    /// A backup ref was written before a clone's branch was reset or rebased
+    BackedUp(BackupRecord),
+    /// Whether a clone has uncommitted changes or unpushed commits (for
+    /// `select_all_skips_dirty`)
+    LocalWork(String, bool),
+    /// The command a fork of the batch is running now
+    Command(String, String),
+}
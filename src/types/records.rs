@@ -0,0 +1,362 @@
+use super::Fork;
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// ============================================================
+// REMINDERS
+// ============================================================
+
+/// A dated maintenance note on a fork ("revisit after upstream 2.0 ships").
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reminder {
+    pub due: NaiveDate,
+    pub note: String,
+}
+
+impl Reminder {
+    pub fn is_due(&self, today: NaiveDate) -> bool {
+        self.due <= today
+    }
+
+    /// Parse prompt input of the form `<when> [note]`, where `when` is a
+    /// date (`2025-06-01`) or an offset from today (`10d`, `3w`, `2m`).
+    pub fn parse(input: &str, today: NaiveDate) -> Option<Self> {
+        let input = input.trim();
+        let (when, note) = input.split_once(' ').unwrap_or((input, ""));
+
+        let due = if let Ok(date) = NaiveDate::parse_from_str(when, "%Y-%m-%d") {
+            date
+        } else {
+            let unit = when.chars().last()?;
+            let n: u32 = when[..when.len() - unit.len_utf8()].parse().ok()?;
+            match unit {
+                'd' => today.checked_add_days(Days::new(n.into()))?,
+                'w' => today.checked_add_days(Days::new(u64::from(n) * 7))?,
+                'm' => today.checked_add_months(Months::new(n))?,
+                _ => return None,
+            }
+        };
+
+        Some(Self {
+            due,
+            note: note.trim().to_string(),
+        })
+    }
+}
+
+// ============================================================
+// REFRESH SNAPSHOTS
+// ============================================================
+
+/// The forks from a full refresh.
+#[derive(Debug, Default)]
+pub struct FetchedForks {
+    pub forks: Vec<Fork>,
+    /// The `gh repo list` fallback stopped at its fork limit, so some forks
+    /// may be missing.
+    pub truncated: bool,
+}
+
+/// Forks changed since the last full refresh, from an incremental refresh.
+#[derive(Debug, Default)]
+pub struct ForkDelta {
+    /// New or updated forks, most recently updated first.
+    pub updated: Vec<Fork>,
+    /// IDs (`owner/name`) of forks archived since.
+    pub archived: Vec<String>,
+}
+
+/// What changed between the previous and the latest full refresh.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotDiff {
+    /// When the previous snapshot was taken.
+    pub since: Option<DateTime<Utc>>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Forks whose upstream default branch moved.
+    pub upstream_moved: Vec<String>,
+}
+
+impl SnapshotDiff {
+    /// Diff two snapshots of fork ID → upstream head.
+    pub fn between(
+        previous: &HashMap<String, Option<String>>,
+        latest: &HashMap<String, Option<String>>,
+    ) -> Self {
+        let mut diff = Self::default();
+        for (id, head) in latest {
+            match previous.get(id) {
+                None => diff.added.push(id.clone()),
+                // Unknown heads (e.g. REST fallback) don't count as movement
+                Some(old) if old.is_some() && head.is_some() && old != head => {
+                    diff.upstream_moved.push(id.clone());
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = previous
+            .keys()
+            .filter(|id| !latest.contains_key(*id))
+            .cloned()
+            .collect();
+        diff.added.sort();
+        diff.removed.sort();
+        diff.upstream_moved.sort();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.upstream_moved.is_empty()
+    }
+}
+
+// ============================================================
+// SYNC HISTORY
+// ============================================================
+
+/// Sync outcomes of one week (starting Monday), for the success-rate trend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeekTrend {
+    pub week: NaiveDate,
+    pub syncs: u64,
+    pub failures: u64,
+}
+
+impl WeekTrend {
+    /// The Monday starting the week of `date`.
+    pub fn week_of(date: NaiveDate) -> NaiveDate {
+        date - Days::new(u64::from(date.weekday().num_days_from_monday()))
+    }
+
+    /// One entry per week for the `weeks` weeks up to `today`, oldest first,
+    /// from (syncs, failures) by week start; weeks without syncs are zero.
+    pub fn fill(
+        counts: &HashMap<NaiveDate, (u64, u64)>,
+        today: NaiveDate,
+        weeks: u32,
+    ) -> Vec<Self> {
+        let current = Self::week_of(today);
+        (0..u64::from(weeks))
+            .rev()
+            .map(|ago| {
+                let week = current - Days::new(ago * 7);
+                let (syncs, failures) = counts.get(&week).copied().unwrap_or_default();
+                Self {
+                    week,
+                    syncs,
+                    failures,
+                }
+            })
+            .collect()
+    }
+
+    /// Share of the week's syncs that failed, in percent.
+    pub fn failure_percent(&self) -> u64 {
+        (self.failures * 100).checked_div(self.syncs).unwrap_or(0)
+    }
+}
+
+/// Failures kept per fork for the details pane.
+pub const FAILURES_SHOWN: usize = 3;
+
+/// A sync of a fork that failed or needed attention, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureRecord {
+    pub at: DateTime<Utc>,
+    pub message: String,
+}
+
+// ============================================================
+// AUTO-STASHES
+// ============================================================
+
+/// Uncommitted changes stashed while syncing a clone, recorded until the
+/// fork's sync finishes so stashes left by an interrupted run can be found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StashRecord {
+    /// Fork ID (`owner/name`)
+    pub id: String,
+    /// Worktree the stash was made in (the clone, or a linked worktree)
+    pub path: PathBuf,
+    /// Commit of the stash entry, which stays the same as others are pushed
+    pub oid: String,
+    /// Branch that was checked out, to go back to before popping
+    pub branch: String,
+    pub stashed_at: DateTime<Utc>,
+}
+
+// ============================================================
+// BACKUP REFS
+// ============================================================
+
+/// Where a clone's branch was before a sync reset or rebased it, kept as a
+/// `refs/repo-syncer/backup/<branch>/<timestamp>` ref so the sync can be undone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupRecord {
+    /// Fork ID (`owner/name`)
+    pub id: String,
+    /// Worktree the branch was checked out in (the clone, or a linked worktree)
+    pub path: PathBuf,
+    pub branch: String,
+    /// The backup ref, e.g. `refs/repo-syncer/backup/main/20250601T120000.123Z`
+    pub ref_name: String,
+    /// Commit the branch pointed at
+    pub oid: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================================
+// COMMAND AUDIT LOG
+// ============================================================
+
+/// Commands kept in the audit log; older ones are dropped.
+pub const AUDIT_LIMIT: usize = 5000;
+
+/// An external command (git, gh, a hook) repo-syncer ran, kept so what it
+/// actually did to a clone can be reconstructed later.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub started_at: DateTime<Utc>,
+    /// Program and arguments, space-separated
+    pub command: String,
+    /// Working directory (`git -C <path>` is part of `command`)
+    pub cwd: String,
+    /// `None` if it was killed by a signal or couldn't be started
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// stdout, then stderr (or why it couldn't start), truncated
+    pub output: String,
+}
+
+// ============================================================
+// API RESPONSE CACHE
+// ============================================================
+
+/// API responses kept for conditional requests; the least recently fetched
+/// ones are dropped.
+pub const API_CACHE_LIMIT: usize = 2000;
+
+/// A `gh api` response kept so the next identical request can send its
+/// validators (`If-None-Match`/`If-Modified-Since`) and, when GitHub answers
+/// 304 Not Modified, reuse `output` instead of downloading it again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    /// The `gh api` arguments, space-separated
+    pub request: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// The response body, or what `--jq` made of it
+    pub output: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
+// ============================================================
+// LOCAL CLONE RECONCILIATION
+// ============================================================
+
+/// A problem found when comparing `tool_home` on disk with the fork list.
+#[derive(Debug, Clone)]
+pub struct LocalFinding {
+    pub path: PathBuf,
+    pub origin: Option<String>,
+    pub kind: FindingKind,
+    /// Where the repo belongs according to its origin URL (if that path is free).
+    pub suggested_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FindingKind {
+    /// A git repo that doesn't belong to any listed fork. `adopt_as` is the
+    /// uncloned fork (`owner/name`) its origin points at, if any.
+    Orphan { adopt_as: Option<String> },
+    /// A fork's local path holds a repo whose origin points somewhere else.
+    OriginMismatch { fork: String },
+}
+
+// ============================================================
+// USAGE STATISTICS (local only, never sent anywhere)
+// ============================================================
+
+/// Usage counters for the current session.
+#[derive(Debug, Clone)]
+pub struct UsageSession {
+    pub started_at: DateTime<Utc>,
+    pub keys: HashMap<String, u64>,
+    pub actions: HashMap<String, u64>,
+}
+
+impl UsageSession {
+    pub fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            keys: HashMap::new(),
+            actions: HashMap::new(),
+        }
+    }
+
+    pub fn record_key(&mut self, key: &str) {
+        *self.keys.entry(key.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn record_action(&mut self, action: &str) {
+        *self.actions.entry(action.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn action_count(&self) -> u64 {
+        self.actions.values().sum()
+    }
+}
+
+/// Accumulated usage statistics, sorted by count (descending).
+#[derive(Debug, Clone, Default)]
+pub struct UsageStats {
+    pub sessions: u64,
+    pub keys: Vec<(String, u64)>,
+    pub actions: Vec<(String, u64)>,
+}
+
+impl UsageStats {
+    pub fn action_count(&self) -> u64 {
+        self.actions.iter().map(|(_, n)| n).sum()
+    }
+
+    /// Average number of actions per session.
+    pub fn actions_per_session(&self) -> f64 {
+        if self.sessions == 0 {
+            0.0
+        } else {
+            self.action_count() as f64 / self.sessions as f64
+        }
+    }
+}
+
+// ============================================================
+// TESTS
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reminder_parse() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        let due = |input| Reminder::parse(input, today).map(|r| r.due.to_string());
+
+        assert_eq!(
+            Reminder::parse("2025-06-01 revisit after 2.0 ships", today),
+            Some(Reminder {
+                due: NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+                note: "revisit after 2.0 ships".to_string(),
+            })
+        );
+        assert_eq!(due("10d").as_deref(), Some("2025-02-10"));
+        assert_eq!(due("3w").as_deref(), Some("2025-02-21"));
+        // Clamped to the end of a shorter month
+        assert_eq!(due("1m").as_deref(), Some("2025-02-28"));
+        assert_eq!(Reminder::parse("2y later", today), None);
+        assert_eq!(Reminder::parse("", today), None);
+        assert_eq!(Reminder::parse("   ", today), None);
+    }
+}
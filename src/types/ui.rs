@@ -0,0 +1,330 @@
+use super::WeekTrend;
+use std::collections::{HashMap, HashSet};
+
+// ============================================================
+// TOAST & ERROR HANDLING
+// ============================================================
+
+#[derive(Clone, Debug)]
+pub struct Toast {
+    pub message: String,
+    pub level: ToastLevel,
+    pub created_at: std::time::Instant,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[allow(dead_code)] // Reserved for future toast notifications
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Toast {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level: ToastLevel::Info,
+            created_at: std::time::Instant::now(),
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level: ToastLevel::Warning,
+            created_at: std::time::Instant::now(),
+        }
+    }
+
+    #[allow(dead_code)] // Reserved for future toast notifications
+    pub fn success(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level: ToastLevel::Success,
+            created_at: std::time::Instant::now(),
+        }
+    }
+
+    #[allow(dead_code)] // Reserved for future toast notifications
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level: ToastLevel::Error,
+            created_at: std::time::Instant::now(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ErrorDetails {
+    pub title: String,
+    pub message: String,
+    pub action: Option<ErrorAction>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ErrorAction {
+    pub label: String,
+    /// Arguments of the `gh` command that fixes the error
+    pub gh_args: Vec<String>,
+    /// Run in the foreground with the TUI suspended (e.g. `gh auth login`)
+    pub interactive: bool,
+    /// What to run again once an interactive command succeeds
+    pub retry: Option<Retry>,
+}
+
+/// An operation that failed for a reason its error popup's command fixes.
+#[derive(Clone, Debug)]
+pub enum Retry {
+    /// Delete these forks (full names: indices may shift in the meantime)
+    Delete(Vec<String>),
+}
+
+#[derive(PartialEq, Clone)]
+pub enum ModalAction {
+    Sync,
+    /// Resume a batch interrupted by quitting or a crash
+    Resume,
+    Clone,
+    /// Clone every uncloned fork in the current (filtered) list
+    CloneAll,
+    Archive,
+    Delete,
+    /// Open a pull request from the fork's default branch to upstream
+    PullRequest,
+    /// Switch the fork between public and private
+    Visibility,
+    /// Turn the fork into a standalone repo (leave the fork network)
+    Detach,
+    /// `gh repo sync --force` a diverged fork and reset its clone
+    ForceSync,
+    /// Put a clone's branch back to the backup ref of its last sync
+    Undo,
+}
+
+/// What archiving or deleting a fork could lose, checked before confirming.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskCheck {
+    Checking,
+    Found(ForkRisks),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForkRisks {
+    /// `#number title` of each open PR from the fork to upstream
+    pub open_prs: Vec<String>,
+    /// Branches with commits upstream doesn't have, as `branch (N ahead)`
+    pub ahead_branches: Vec<String>,
+}
+
+impl ForkRisks {
+    pub fn is_empty(&self) -> bool {
+        self.open_prs.is_empty() && self.ahead_branches.is_empty()
+    }
+}
+
+/// Commits on the upstream default branch that the fork doesn't have.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpstreamCommits {
+    /// How many commits the fork is behind
+    pub total: u32,
+    /// The newest of them, as `sha subject`
+    pub recent: Vec<String>,
+}
+
+/// Loading state of a fork's upstream commits in the details pane.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpstreamCommitsCheck {
+    Loading,
+    Loaded(UpstreamCommits),
+    Failed(String),
+}
+
+/// What syncing a clone would bring in from its upstream.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IncomingChanges {
+    /// `sha subject`, newest first
+    pub commits: Vec<String>,
+    /// `git diff --name-status` lines
+    pub files: Vec<String>,
+}
+
+/// The incoming-change preview overlay (`f`) of one fork.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preview {
+    /// `owner/name` of the previewed fork
+    pub id: String,
+    /// `None` while fetching
+    pub result: Option<Result<IncomingChanges, String>>,
+    pub scroll: u16,
+}
+
+/// What a sync of one fork would do: the commands it would run, in order,
+/// or why it would be skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncPlan {
+    /// `owner/name` of the fork
+    pub id: String,
+    /// Shell-quoted command lines; `#` lines are notes
+    pub commands: Vec<String>,
+    pub skip: Option<String>,
+}
+
+/// The plan overlay (`p` in the Sync confirm modal).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlanView {
+    /// `None` while the plans are worked out
+    pub plans: Option<Vec<SyncPlan>>,
+    pub scroll: u16,
+}
+
+/// Working-tree state of a local clone, shown in the details pane.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitStatus {
+    /// Checked-out branch (`None` when HEAD is detached)
+    pub branch: Option<String>,
+    /// Changed and untracked files
+    pub changes: usize,
+    /// Commits ahead of and behind the branch's upstream (`None` without one)
+    pub ahead_behind: Option<(u32, u32)>,
+    pub stashes: usize,
+    /// Why syncs skip the clone, if it opted out (`.repo-syncer.toml`)
+    pub opt_out: Option<String>,
+    /// URLs of the `origin` and `upstream` remotes (`None` without one)
+    pub origin: Option<String>,
+    pub upstream: Option<String>,
+}
+
+/// Loading state of the local git status of the fork under the cursor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GitStatusCheck {
+    Loading,
+    Loaded(GitStatus),
+    Failed(String),
+}
+
+/// The fork list in the Sync confirm modal.
+#[derive(Debug, Clone, Default)]
+pub struct ConfirmList {
+    pub scroll: usize,
+    /// Whether the clones' working trees are being (or have been) checked
+    pub checked: bool,
+    /// Which clones have uncommitted changes (a sync stashes them), by
+    /// `owner/name`
+    pub dirty: HashMap<String, bool>,
+}
+
+impl ConfirmList {
+    /// Forks shown at once; the rest are scrolled to with j/k.
+    pub const ROWS: usize = 10;
+}
+
+/// Clones holding local work, which select-all leaves out with
+/// `select_all_skips_dirty`.
+#[derive(Debug, Clone, Default)]
+pub struct LocalWork {
+    /// Whether the clones are being (or have been) scanned
+    pub scanned: bool,
+    /// Clones with uncommitted changes or unpushed commits, by `owner/name`
+    pub busy: HashSet<String>,
+}
+
+/// State of the details pane next to the fork list.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DetailsPane {
+    /// Shown or hidden with `I`; `None` shows it on terminals 100+ wide
+    pub forced: Option<bool>,
+    /// Whether it was shown in the last frame
+    pub shown: bool,
+    /// Whether j/k scroll the pane instead of moving the cursor
+    pub focused: bool,
+    pub scroll: u16,
+}
+
+/// Column the fork list is sorted by (number keys or a header click).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Repository,
+    Status,
+    Language,
+    Behind,
+    LastSync,
+    Path,
+}
+
+impl SortColumn {
+    /// In the order of the number keys `1`-`6`.
+    pub const ALL: [Self; 6] = [
+        Self::Repository,
+        Self::Status,
+        Self::Language,
+        Self::Behind,
+        Self::LastSync,
+        Self::Path,
+    ];
+}
+
+#[allow(dead_code)] // Fields reserved for future stats display
+pub struct ForkStats {
+    pub by_language: Vec<(String, u64)>,
+    pub total: usize,
+    pub cloned: usize,
+    pub uncloned: usize,
+    pub synced: usize,
+    pub pending: usize,
+    pub failed: usize,
+    /// Total size of all measured clones, in bytes
+    pub disk_usage: u64,
+    /// Forks synced this session by how far behind upstream they were
+    /// (bucket label, count)
+    pub behind_by: Vec<(&'static str, u64)>,
+    /// Commits fast-forwarded across all syncs this session
+    pub commits_pulled: u64,
+    /// Why forks were skipped this session, most common first
+    pub skipped_reasons: Vec<(String, u64)>,
+    /// Forks created per month over the last year, oldest first
+    pub created_by_month: Vec<(String, u64)>,
+    /// Recorded syncs per week, oldest first (empty without a store)
+    pub trend: Vec<WeekTrend>,
+}
+
+/// What the text typed into an input prompt will be used for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputPurpose {
+    /// Set the reminder of the fork with this ID (`owner/name`).
+    Reminder(String),
+    /// Set (or, when empty, clear) where the fork with this ID is cloned.
+    LocalPath(String),
+    /// Fork and clone the upstream typed in (`owner/repo` or a URL).
+    NewFork,
+    /// Move the cursor to the next repo whose name starts with what's typed.
+    GoTo,
+}
+
+/// A single-line text prompt shown in the help bar.
+#[derive(Debug, Clone)]
+pub struct InputPrompt {
+    pub purpose: InputPurpose,
+    pub label: String,
+    pub buffer: String,
+}
+
+/// A profile picked in the `S` overlay, which restarts the session with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileSwitch {
+    /// Run without a profile.
+    Default,
+    Named(String),
+}
+
+impl ProfileSwitch {
+    pub fn into_name(self) -> Option<String> {
+        match self {
+            Self::Default => None,
+            Self::Named(name) => Some(name),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::app::App;
+use chrono::Local;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+/// Lines of the selected command's directory and output under the list.
+const OUTPUT_HEIGHT: u16 = 8;
+
+pub fn render_audit_overlay(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let modal_width = 110.min(area.width.saturating_sub(4));
+    let modal_height = 30.min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Command Audit Log ({}) ", app.audit.len()));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    if app.audit.is_empty() {
+        let widget = Paragraph::new("No commands recorded yet.")
+            .style(Style::default().fg(Color::Gray))
+            .centered();
+        f.render_widget(widget, inner);
+        return;
+    }
+
+    let [list_area, output_area] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(OUTPUT_HEIGHT)]).areas(inner);
+
+    let items: Vec<ListItem> = app
+        .audit
+        .iter()
+        .map(|entry| {
+            let (exit, color) = match entry.exit_code {
+                Some(0) => ("ok".to_string(), Color::Green),
+                Some(code) => (format!("exit {code}"), Color::Red),
+                None => ("killed".to_string(), Color::Red),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    entry
+                        .started_at
+                        .with_timezone(&Local)
+                        .format("%m-%d %H:%M:%S ")
+                        .to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(format!("{exit:>7} "), Style::default().fg(color)),
+                Span::styled(
+                    format!("{:>6}ms ", entry.duration_ms),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(entry.command.clone()),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(Some(app.audit_selected));
+    f.render_stateful_widget(list, list_area, &mut state);
+
+    let Some(entry) = app.audit.get(app.audit_selected) else {
+        return;
+    };
+    let mut lines = vec![Line::styled(
+        format!("in {}", entry.cwd),
+        Style::default().fg(Color::Cyan),
+    )];
+    lines.extend(entry.output.lines().map(|line| Line::raw(line.to_string())));
+    let output = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(output, output_area);
+}
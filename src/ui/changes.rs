@@ -0,0 +1,82 @@
+use crate::app::App;
+use chrono::Local;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+pub fn render_changes_overlay(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let modal_width = 70.min(area.width.saturating_sub(4));
+    let modal_height = 22.min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Changes Since Last Refresh ");
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let Some(diff) = &app.changes else {
+        let widget = Paragraph::new("Nothing to compare yet. Refresh (R) at least twice.")
+            .style(Style::default().fg(Color::Gray))
+            .centered();
+        f.render_widget(widget, inner);
+        return;
+    };
+
+    let mut lines = Vec::new();
+    if let Some(since) = diff.since {
+        lines.push(Line::styled(
+            format!(
+                "Compared with the refresh of {}",
+                since.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+        lines.push(Line::raw(""));
+    }
+
+    if diff.is_empty() {
+        lines.push(Line::styled(
+            "No new or removed forks, no upstream activity.",
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    let sections = [
+        (
+            "Upstream gained commits",
+            &diff.upstream_moved,
+            Color::Yellow,
+        ),
+        ("New forks", &diff.added, Color::Green),
+        ("Removed forks", &diff.removed, Color::Red),
+    ];
+    for (title, ids, color) in sections {
+        if ids.is_empty() {
+            continue;
+        }
+        lines.push(Line::styled(
+            format!("{title} ({})", ids.len()),
+            Style::default().fg(color).bold(),
+        ));
+        for id in ids {
+            lines.push(Line::from(vec![Span::raw("  "), Span::raw(id.clone())]));
+        }
+        lines.push(Line::raw(""));
+    }
+
+    let widget = Paragraph::new(lines).wrap(Wrap { trim: false });
+    f.render_widget(widget, inner);
+}
@@ -0,0 +1,54 @@
+use crate::app::App;
+use crate::types::{CacheStatus, ConfirmList};
+use ratatui::prelude::*;
+
+/// The forks a Sync/Resume would go through, flagging the ones that will be
+/// cloned first and clones whose changes will be stashed.
+pub fn sync_target_lines(app: &App) -> Vec<Line<'static>> {
+    let targets = app.confirm_targets();
+    let offline = app.cache_status == CacheStatus::Offline;
+    let scroll = app.confirm_list.scroll;
+
+    let mut lines = vec![Line::from("")];
+    lines.extend(
+        targets
+            .iter()
+            .skip(scroll)
+            .take(ConfirmList::ROWS)
+            .map(|&i| {
+                let fork = &app.forks[i];
+                // Named like in the list (upstream owner)
+                let name = format!("  {}/{}", fork.parent_owner, fork.name);
+                let mut spans = vec![Span::raw(name)];
+                if !fork.is_cloned {
+                    spans.push(if offline {
+                        Span::styled("  skipped (offline)", Style::default().fg(Color::DarkGray))
+                    } else {
+                        Span::styled("  will clone", Style::default().fg(Color::Cyan))
+                    });
+                }
+                if app.confirm_list.dirty.get(&fork.full_name()) == Some(&true) {
+                    spans.push(Span::styled(
+                        if app.config.skip_dirty {
+                            "  dirty: will skip"
+                        } else {
+                            "  dirty: will stash"
+                        },
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                Line::from(spans)
+            }),
+    );
+    if targets.len() > ConfirmList::ROWS {
+        let last = (scroll + ConfirmList::ROWS).min(targets.len());
+        lines.push(
+            Line::styled(
+                format!("j/k: scroll ({}-{last} of {})", scroll + 1, targets.len()),
+                Style::default().fg(Color::DarkGray),
+            )
+            .centered(),
+        );
+    }
+    lines
+}
@@ -1,29 +1,36 @@
+use super::format_size;
+use super::help::running_command;
 use crate::app::App;
-use chrono::{DateTime, Utc};
+use crate::local::parse_repo_slug;
+use crate::types::{Fork, GitStatus, GitStatusCheck, SyncStatus, UpstreamCommitsCheck, DIVERGED};
+use chrono::{DateTime, Local, Utc};
 use ratatui::{
     prelude::*,
     widgets::{Block, BorderType, Borders, Paragraph, Wrap},
 };
 
-pub fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
+pub fn render_details_pane(f: &mut Frame, app: &mut App, area: Rect) {
     let fork = app.current_fork();
 
     let content = if let Some(fork) = fork {
-        let local_path_display = fork.local_path.strip_prefix(&app.tool_home).map_or_else(
-            |_| fork.local_path.display().to_string(),
-            |p| format!("~/{}", p.display()),
-        );
+        let local_path_display = if app.path_overrides.contains_key(&fork.full_name()) {
+            format!("{} (set with p)", fork.local_path.display())
+        } else if app.tool_homes.roots().len() == 1 {
+            format!("~/{}", app.tool_homes.display(&fork.local_path))
+        } else {
+            fork.local_path.display().to_string()
+        };
 
         let description = fork
             .description
             .as_deref()
             .unwrap_or("No description")
-            .chars()
-            .take(200)
-            .collect::<String>();
+            .to_string();
 
         let language = fork.primary_language.as_deref().unwrap_or("Unknown");
-        let clone_status = if fork.is_cloned {
+        let clone_status = if !fork.is_github_fork() {
+            "Local repo with upstream remote"
+        } else if fork.is_cloned {
             "Cloned"
         } else {
             "Not cloned"
@@ -33,7 +40,7 @@ pub fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
             .created_at
             .map_or_else(|| "Unknown".to_string(), format_relative_date);
 
-        vec![
+        let mut lines = vec![
             Line::from(vec![
                 Span::styled("Name: ", Style::default().fg(Color::DarkGray)),
                 Span::styled(
@@ -87,7 +94,208 @@ pub fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
                 Span::styled("Path: ", Style::default().fg(Color::DarkGray)),
                 Span::styled(local_path_display, Style::default().fg(Color::Blue)),
             ]),
-        ]
+        ];
+
+        if let Some((_, check)) = app
+            .git_status
+            .as_ref()
+            .filter(|(id, _)| *id == fork.full_name())
+        {
+            let mut spans = vec![Span::styled("Git: ", Style::default().fg(Color::DarkGray))];
+            match check {
+                GitStatusCheck::Loading => spans.push(Span::styled(
+                    "reading...",
+                    Style::default().fg(Color::DarkGray),
+                )),
+                GitStatusCheck::Loaded(status) => spans.extend(git_status_spans(status)),
+                GitStatusCheck::Failed(err) => {
+                    spans.push(Span::styled(err.clone(), Style::default().fg(Color::Red)));
+                }
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(spans));
+            if let GitStatusCheck::Loaded(GitStatus {
+                opt_out: Some(reason),
+                ..
+            }) = check
+            {
+                lines.push(Line::from(vec![
+                    Span::styled("Never synced: ", Style::default().fg(Color::Yellow).bold()),
+                    Span::raw(reason.clone()),
+                ]));
+            }
+            if let GitStatusCheck::Loaded(status) = check {
+                lines.extend(remote_lines(fork, status));
+            }
+        }
+
+        if let Some(visibility) = &fork.visibility {
+            let color = if visibility == "public" {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("Visibility: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(visibility.clone(), Style::default().fg(color)),
+            ]));
+        }
+
+        if let Some(pushed_at) = fork.upstream_pushed_at {
+            let style = if fork.is_upstream_dormant(Utc::now()) {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::Cyan)
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("Upstream pushed: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(format_relative_date(pushed_at), style),
+            ]));
+        }
+
+        if let Some(size) = app.disk_usage.get(&fork.full_name()) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("Disk: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(format_size(*size), Style::default().fg(Color::Magenta)),
+            ]));
+        }
+
+        match app.current_fork_index().map(|i| &app.statuses[i]) {
+            Some(SyncStatus::NeedsAttention(help)) => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    "Needs attention:",
+                    Style::default().fg(Color::Magenta).bold(),
+                )));
+                lines.push(Line::from(Span::raw(help.clone())));
+            }
+            Some(status) if !status.is_finished() && running_command(app).is_some() => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{}: ", status.display()),
+                        Style::default().fg(Color::Cyan).bold(),
+                    ),
+                    Span::raw(running_command(app).unwrap_or_default().to_string()),
+                ]));
+            }
+            Some(SyncStatus::Failed(err)) => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Failed: ", Style::default().fg(Color::Red).bold()),
+                    Span::raw(err.lines().next().unwrap_or_default().to_string()),
+                ]));
+                let hint = if err == DIVERGED {
+                    "(E: full error, F: force-sync)"
+                } else {
+                    "(E: full error)"
+                };
+                lines.push(Line::from(Span::styled(
+                    hint,
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            _ => {}
+        }
+
+        // Earlier syncs too, so a fork that keeps failing stands out
+        if let Some(failures) = app.failures.get(&fork.full_name()) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Recent failures:",
+                Style::default().fg(Color::DarkGray),
+            )));
+            lines.extend(failures.iter().map(|failure| {
+                Line::from(vec![
+                    Span::styled(
+                        failure
+                            .at
+                            .with_timezone(&Local)
+                            .format("%b %d %H:%M ")
+                            .to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        failure
+                            .message
+                            .lines()
+                            .next()
+                            .unwrap_or_default()
+                            .to_string(),
+                        Style::default().fg(Color::Red),
+                    ),
+                ])
+            }));
+        }
+
+        if let Some(reminder) = app.reminders.get(&fork.full_name()) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("Reminder: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(reminder.due.to_string(), Style::default().fg(Color::Yellow)),
+                Span::raw(format!(" {}", reminder.note)),
+            ]));
+        }
+
+        match app.upstream_commits.get(&fork.full_name()) {
+            Some(UpstreamCommitsCheck::Loading) => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    "Upstream commits: loading...",
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            Some(UpstreamCommitsCheck::Loaded(commits)) if commits.total == 0 => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Upstream commits: ", Style::default().fg(Color::DarkGray)),
+                    Span::styled("up to date", Style::default().fg(Color::Green)),
+                ]));
+            }
+            Some(UpstreamCommitsCheck::Loaded(commits)) => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!("Upstream commits ({} new):", commits.total),
+                    Style::default().fg(Color::DarkGray),
+                )));
+                lines.extend(commits.recent.iter().map(|commit| {
+                    let (sha, subject) = commit.split_once(' ').unwrap_or((commit, ""));
+                    Line::from(vec![
+                        Span::styled(sha.to_string(), Style::default().fg(Color::Yellow)),
+                        Span::raw(format!(" {subject}")),
+                    ])
+                }));
+                let more = (commits.total as usize).saturating_sub(commits.recent.len());
+                if more > 0 {
+                    lines.push(Line::from(Span::styled(
+                        format!("...and {more} more"),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+            Some(UpstreamCommitsCheck::Failed(err)) => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Upstream commits: ", Style::default().fg(Color::DarkGray)),
+                    Span::styled(err.clone(), Style::default().fg(Color::Red)),
+                ]));
+            }
+            None => {}
+        }
+
+        // This session's log; focus the pane (l) to scroll through it
+        if let Some(log) = app.logs.get(&fork.full_name()) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Log:",
+                Style::default().fg(Color::DarkGray),
+            )));
+            lines.extend(log.iter().map(|line| Line::from(line.clone())));
+        }
+        lines
     } else {
         vec![Line::from(Span::styled(
             "No fork selected",
@@ -95,16 +303,98 @@ pub fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
         ))]
     };
 
+    // Don't scroll past the last line
+    let scroll = app.details.scroll.min(
+        u16::try_from(content.len())
+            .unwrap_or(u16::MAX)
+            .saturating_sub(1),
+    );
+    let (title, border) = if app.details.focused {
+        (" Details (j/k: scroll, h: back) ", Color::Cyan)
+    } else {
+        (" Details ", Color::Reset)
+    };
     let details = Paragraph::new(content)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .title(" Details "),
+                .border_style(Style::default().fg(border))
+                .title(title),
         )
-        .wrap(Wrap { trim: true });
+        .wrap(Wrap { trim: true })
+        .scroll((scroll, 0));
 
     f.render_widget(details, area);
+    app.details.scroll = scroll;
+}
+
+/// Branch, dirty state, ahead/behind and stashes of a clone, e.g.
+/// "main, 3 changed, 1 ahead, 2 behind, 1 stash". Anything other than a clean
+/// checkout of the default branch means a sync will stash or switch branches.
+fn git_status_spans(status: &GitStatus) -> Vec<Span<'static>> {
+    let mut spans = vec![match &status.branch {
+        Some(branch) => Span::styled(branch.clone(), Style::default().fg(Color::Green)),
+        None => Span::styled("detached HEAD", Style::default().fg(Color::Yellow)),
+    }];
+    let mut push = |text: String, color: Color| {
+        spans.push(Span::raw(", "));
+        spans.push(Span::styled(text, Style::default().fg(color)));
+    };
+    if status.changes == 0 {
+        push("clean".to_string(), Color::Green);
+    } else {
+        push(format!("{} changed", status.changes), Color::Yellow);
+    }
+    match status.ahead_behind {
+        Some((ahead, behind)) => {
+            if ahead > 0 {
+                push(format!("{ahead} ahead"), Color::Cyan);
+            }
+            if behind > 0 {
+                push(format!("{behind} behind"), Color::Magenta);
+            }
+        }
+        None => push("no upstream branch".to_string(), Color::DarkGray),
+    }
+    if status.stashes > 0 {
+        let plural = if status.stashes == 1 { "" } else { "es" };
+        push(format!("{} stash{plural}", status.stashes), Color::Yellow);
+    }
+    spans
+}
+
+/// The clone's `origin` and `upstream` URLs, with a warning when origin
+/// isn't the fork (syncs push to and pull from origin).
+fn remote_lines(fork: &Fork, status: &GitStatus) -> Vec<Line<'static>> {
+    let url = |url: &Option<String>| match url {
+        Some(url) => Span::styled(url.clone(), Style::default().fg(Color::Blue)),
+        None => Span::styled("not set", Style::default().fg(Color::DarkGray)),
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("origin:   ", Style::default().fg(Color::DarkGray)),
+            url(&status.origin),
+        ]),
+        Line::from(vec![
+            Span::styled("upstream: ", Style::default().fg(Color::DarkGray)),
+            url(&status.upstream),
+        ]),
+    ];
+    let points_at_fork = status
+        .origin
+        .as_deref()
+        .and_then(parse_repo_slug)
+        .is_some_and(|(owner, name)| {
+            owner.eq_ignore_ascii_case(&fork.owner) && name.eq_ignore_ascii_case(&fork.name)
+        });
+    if !points_at_fork {
+        lines.push(Line::from(Span::styled(
+            format!("⚠ origin isn't {}", fork.full_name()),
+            Style::default().fg(Color::Yellow).bold(),
+        )));
+    }
+    lines
 }
 
 /// Format a date as relative time (e.g., "3 months ago") with actual date
@@ -4,29 +4,98 @@ use ratatui::{
     prelude::*,
     widgets::{Block, BorderType, Borders, Paragraph},
 };
+use std::fmt::Write;
 
 pub fn render_help_bar(f: &mut Frame, app: &App, area: Rect) {
+    // Configured launchers go with the built-in keys
+    let mut launchers = String::new();
+    for (key, launcher) in &app.config.launchers {
+        let _ = write!(launchers, " | {key}: {}", launcher.name);
+    }
+    let selecting_help = format!(
+        "j/k: Nav | Space: Select | a: All | Enter: Sync | c: Clone | x: Archive | D: Delete | o: Open | i: Ignore | H: Hidden | r: Remind{launchers} | /: Search | q: Quit"
+    );
     let help_text = match app.mode {
         Mode::Selecting => {
             if let Some((msg, _)) = &app.status_message {
                 msg.as_str()
+            } else if app.details.focused {
+                "j/k: Scroll details | h or Esc: Back to list | I: Hide details"
             } else {
-                "j/k: Nav | Space: Select | a: All | Enter: Sync | c: Clone | x: Archive | D: Delete | o: Open | /: Search | q: Quit"
+                &selecting_help
             }
         }
         Mode::Search => "Type to filter | Enter: Confirm | Esc: Cancel",
         Mode::StatsOverlay => "d or Esc: Close stats",
+        Mode::UsageOverlay => "U or Esc: Close usage stats",
+        Mode::ChangesOverlay => "W or Esc: Close changes",
+        Mode::LogOverlay => "v or Esc: Close log",
+        Mode::PreviewOverlay => "j/k: Scroll | Enter: Sync | f or Esc: Close",
+        Mode::AuditOverlay => "j/k: Nav | T or Esc: Close",
+        Mode::PlanOverlay => "j/k: Scroll | Enter: Sync | p or Esc: Back",
+        Mode::ReconcileOverlay => {
+            if let Some((msg, _)) = &app.status_message {
+                msg.as_str()
+            } else {
+                "j/k: Nav | a: Adopt | m: Relocate | X: Remove | r: Rescan | Esc: Close"
+            }
+        }
+        Mode::RemindersOverlay => {
+            if let Some((msg, _)) = &app.status_message {
+                msg.as_str()
+            } else {
+                "j/k: Nav | Enter: Go to fork | x: Clear reminder | Esc: Close"
+            }
+        }
+        Mode::StashesOverlay => {
+            if let Some((msg, _)) = &app.status_message {
+                msg.as_str()
+            } else {
+                "j/k: Nav | Enter: Restore changes | x: Forget (keep stash) | Esc: Later"
+            }
+        }
+        Mode::ProfilesOverlay => "j/k: Nav | Enter: Switch profile | Esc: Close",
+        Mode::Input => "Enter: Save | Esc: Cancel",
         Mode::ConfirmModal => "h/l or Tab: Switch | Enter: Select | Esc: Cancel",
-        Mode::ErrorPopup => "Enter: Run action | Esc: Dismiss",
-        Mode::Syncing => "j/k: Scroll | q: Quit",
+        Mode::ErrorPopup => "j/k: Scroll | Enter: Run action | Esc: Dismiss",
+        Mode::Syncing => {
+            if let Some((msg, _)) = &app.status_message {
+                msg.as_str()
+            } else {
+                "j/k: Scroll | Space: Add fork | x: Skip queued fork | p: Pause | s: Slow mode | q: Quit"
+            }
+        }
     };
 
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    // What the highlighted fork is running (a fork's status alone can't
+    // tell a long `gh repo sync` from a slow pull)
+    if let Some(line) = running_command(app) {
+        let max = usize::from(area.width.saturating_sub(6));
+        let line = if line.chars().count() > max {
+            let cut: String = line.chars().take(max.saturating_sub(1)).collect();
+            format!("{cut}…")
+        } else {
+            line.to_string()
+        };
+        block = block.title(Span::styled(
+            format!(" {line} "),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
     let help = Paragraph::new(help_text)
         .style(Style::default().fg(Color::Gray))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-        );
+        .block(block);
     f.render_widget(help, area);
 }
+
+/// The command the highlighted fork of a running batch is running.
+pub(super) fn running_command(app: &App) -> Option<&str> {
+    if app.mode != Mode::Syncing {
+        return None;
+    }
+    let idx = app.current_fork_index()?;
+    app.commands.get(&idx).map(String::as_str)
+}
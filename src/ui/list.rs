@@ -1,23 +1,150 @@
+use super::format_duration;
 use crate::app::App;
-use crate::types::SyncStatus;
+use crate::types::{SortColumn, SyncStatus, SYNC_STEPS};
+use chrono::{DateTime, Utc};
 use ratatui::{
+    layout::Flex,
     prelude::*,
-    widgets::{Block, BorderType, Borders, Cell, Row, Table},
+    widgets::{Block, BorderType, Borders, Cell, Row, Table, TableState},
 };
+use std::collections::HashSet;
+
+const HIGHLIGHT_SYMBOL: &str = "▶ ";
+
+/// Columns added when the list is wide enough, rather than letting the
+/// repository column take all the space.
+#[derive(Clone, Copy)]
+enum WideColumn {
+    Language,
+    Behind,
+    LastSync,
+    Path,
+}
+
+impl WideColumn {
+    const ALL: [Self; 4] = [Self::Language, Self::Behind, Self::LastSync, Self::Path];
+
+    /// List width (including borders) from which the column is shown.
+    fn min_list_width(self) -> u16 {
+        match self {
+            Self::Language | Self::Behind => 84,
+            Self::LastSync => 96,
+            Self::Path => 130,
+        }
+    }
+
+    fn header(self) -> &'static str {
+        match self {
+            Self::Language => "Language",
+            Self::Behind => "Behind",
+            Self::LastSync => "Synced",
+            Self::Path => "Path",
+        }
+    }
+
+    fn sort_column(self) -> SortColumn {
+        match self {
+            Self::Language => SortColumn::Language,
+            Self::Behind => SortColumn::Behind,
+            Self::LastSync => SortColumn::LastSync,
+            Self::Path => SortColumn::Path,
+        }
+    }
+
+    fn constraint(self) -> Constraint {
+        match self {
+            Self::Language => Constraint::Length(12),
+            Self::Behind => Constraint::Length(7),
+            Self::LastSync => Constraint::Length(9),
+            Self::Path => Constraint::Length(28),
+        }
+    }
+
+    fn cell(self, app: &App, idx: usize, now: DateTime<Utc>) -> Cell<'static> {
+        let fork = &app.forks[idx];
+        let id = fork.full_name();
+        let text = match self {
+            Self::Language => fork.primary_language.clone().unwrap_or_default(),
+            // How far behind the fork is, or was when it synced this session
+            Self::Behind => app
+                .behind_by(idx)
+                .map(|n| n.to_string())
+                .unwrap_or_default(),
+            Self::LastSync => app
+                .last_synced
+                .get(&id)
+                .map(|&at| short_age(now, at))
+                .unwrap_or_default(),
+            Self::Path if fork.is_cloned => app.tool_homes.display(&fork.local_path),
+            Self::Path => String::new(),
+        };
+        Cell::from(text).style(Style::default().fg(Color::DarkGray))
+    }
+}
 
 pub fn render_fork_list(f: &mut Frame, app: &mut App, area: Rect) {
-    let header_cells = ["St", "Repository", "Status"]
-        .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).bold()));
+    let wide_columns: Vec<WideColumn> = WideColumn::ALL
+        .into_iter()
+        .filter(|c| area.width >= c.min_list_width())
+        .collect();
+    // How long each fork of the running (or last) batch took
+    let show_time = app.batch_started.is_some() || !app.fork_durations.is_empty();
+    let columns: Vec<(&str, Option<SortColumn>, Constraint)> = [
+        ("St", None, Constraint::Length(2)),
+        (
+            "Repository",
+            Some(SortColumn::Repository),
+            Constraint::Min(30),
+        ),
+        ("Status", Some(SortColumn::Status), Constraint::Length(20)),
+    ]
+    .into_iter()
+    .chain(show_time.then_some(("Time", None, Constraint::Length(7))))
+    .chain(
+        wide_columns
+            .iter()
+            .map(|c| (c.header(), Some(c.sort_column()), c.constraint())),
+    )
+    .collect();
+    let header_cells = columns.iter().map(|(title, column, _)| {
+        let arrow = match app.sort {
+            Some((sorted, descending)) if Some(sorted) == *column => {
+                if descending {
+                    " ▼"
+                } else {
+                    " ▲"
+                }
+            }
+            _ => "",
+        };
+        Cell::from(format!("{title}{arrow}")).style(Style::default().fg(Color::Yellow).bold())
+    });
     let header = Row::new(header_cells).height(1).bottom_margin(1);
+    let widths: Vec<Constraint> = columns.iter().map(|&(_, _, width)| width).collect();
+    app.set_list_header(header_rects(app, area, &columns, &widths));
 
+    // Only build the rows that fit (below the borders and header), keeping
+    // the cursor centered: lists can hold thousands of forks
+    let page_rows = usize::from(area.height.saturating_sub(4));
+    app.list_rows = page_rows;
+    let start = app.list_window_start(page_rows);
+    let selected = app.state.selected().map(|s| s - start);
+
+    let now = Utc::now();
     let visible = app.visible_forks();
-    let rows = visible.iter().map(|&i| {
+    let window = &visible[start..(start + page_rows).min(visible.len())];
+    let rows = window.iter().map(|&i| {
         let fork = &app.forks[i];
+        let ignored = app.is_ignored(i);
         let status_icon = match &app.statuses[i] {
             SyncStatus::Pending => {
-                if app.selected[i] {
+                if ignored {
+                    Cell::from("⊘").style(Style::default().fg(Color::DarkGray))
+                } else if app.selected[i] {
                     Cell::from("*").style(Style::default().fg(Color::Green))
+                } else if app.has_local_work(i) {
+                    // Uncommitted changes or unpushed commits
+                    Cell::from("✎").style(Style::default().fg(Color::Yellow))
                 } else if fork.is_cloned {
                     Cell::from(" ")
                 } else {
@@ -30,6 +157,12 @@ pub fn render_fork_list(f: &mut Frame, app: &mut App, area: Rect) {
             | SyncStatus::Fetching
             | SyncStatus::Syncing
             | SyncStatus::Restoring
+            | SyncStatus::Rebasing
+            | SyncStatus::Submodules
+            | SyncStatus::Tags
+            | SyncStatus::RunningHook
+            | SyncStatus::OpeningPr
+            | SyncStatus::ChangingVisibility
             | SyncStatus::Archiving
             | SyncStatus::Deleting => {
                 Cell::from(app.spinner()).style(Style::default().fg(Color::Cyan))
@@ -37,15 +170,49 @@ pub fn render_fork_list(f: &mut Frame, app: &mut App, area: Rect) {
             SyncStatus::Synced(_) => Cell::from("✓").style(Style::default().fg(Color::Green)),
             SyncStatus::Skipped(_) => Cell::from("-").style(Style::default().fg(Color::Yellow)),
             SyncStatus::Failed(_) => Cell::from("✗").style(Style::default().fg(Color::Red)),
+            SyncStatus::NeedsAttention(_) => {
+                Cell::from("!").style(Style::default().fg(Color::Magenta).bold())
+            }
         };
 
-        let repo_name = format!("{}/{}", fork.parent_owner, fork.name);
+        let mut name_spans = highlighted_name(
+            &format!("{}/{}", fork.parent_owner, fork.name),
+            &app.name_match_indices(i),
+        );
+        if !fork.is_github_fork() {
+            // Local repo with an upstream remote (not a GitHub fork)
+            name_spans.push(Span::styled(
+                " [upstream]",
+                Style::default().fg(Color::Magenta).dim(),
+            ));
+        }
+        if fork.is_upstream_dormant(now) {
+            // Upstream hasn't seen a push in a long time
+            name_spans.push(Span::styled(" 💤", Style::default().fg(Color::DarkGray)));
+        }
+        if app.is_reminder_due(i) {
+            name_spans.push(Span::styled(
+                " ⏰ due",
+                Style::default().fg(Color::Yellow).bold(),
+            ));
+        } else if app.reminder_for(i).is_some() {
+            name_spans.push(Span::styled(" ⏰", Style::default().fg(Color::DarkGray)));
+        }
+        let repo_name = Cell::from(Line::from(name_spans));
 
         // Determine display status (show "Not cloned" for uncloned forks)
-        let display_status = if !fork.is_cloned
+        let display_status = if ignored && app.statuses[i] == SyncStatus::Pending {
+            "Ignored".to_string()
+        } else if !fork.is_cloned
             && matches!(app.statuses[i], SyncStatus::Pending | SyncStatus::Checking)
         {
             "Not cloned".to_string()
+        } else if let Some(step) = app.statuses[i].sync_step() {
+            format!(
+                "{} {} ({step}/{SYNC_STEPS})",
+                step_bar(step),
+                app.statuses[i].display()
+            )
         } else {
             app.statuses[i].display()
         };
@@ -54,26 +221,35 @@ pub fn render_fork_list(f: &mut Frame, app: &mut App, area: Rect) {
             SyncStatus::Synced(_) => Style::default().fg(Color::Green),
             SyncStatus::Skipped(_) => Style::default().fg(Color::Yellow),
             SyncStatus::Failed(_) => Style::default().fg(Color::Red),
+            SyncStatus::NeedsAttention(_) => Style::default().fg(Color::Magenta),
             SyncStatus::Checking
             | SyncStatus::Cloning
             | SyncStatus::Stashing
             | SyncStatus::Fetching
             | SyncStatus::Syncing
             | SyncStatus::Restoring
+            | SyncStatus::Rebasing
+            | SyncStatus::Submodules
+            | SyncStatus::Tags
+            | SyncStatus::RunningHook
+            | SyncStatus::OpeningPr
+            | SyncStatus::ChangingVisibility
             | SyncStatus::Archiving
             | SyncStatus::Deleting => Style::default().fg(Color::Cyan),
             SyncStatus::Pending if app.selected[i] => Style::default().fg(Color::White).bold(),
+            SyncStatus::Pending if ignored => Style::default().fg(Color::DarkGray).italic(),
             SyncStatus::Pending if !fork.is_cloned => Style::default().fg(Color::DarkGray).dim(),
             SyncStatus::Pending => Style::default().fg(Color::Reset),
         };
 
-        Row::new(vec![
-            status_icon,
-            Cell::from(repo_name),
-            Cell::from(display_status),
-        ])
-        .style(style)
-        .height(1)
+        let mut cells = vec![status_icon, repo_name, Cell::from(display_status)];
+        if show_time {
+            cells.push(Cell::from(
+                app.fork_elapsed(i).map(format_duration).unwrap_or_default(),
+            ));
+        }
+        cells.extend(wide_columns.iter().map(|c| c.cell(app, i, now)));
+        Row::new(cells).style(style).height(1)
     });
 
     let title = if app.search_query.is_empty() {
@@ -82,23 +258,103 @@ pub fn render_fork_list(f: &mut Frame, app: &mut App, area: Rect) {
         format!(" Forks ({} matches) ", visible.len())
     };
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(2),
-            Constraint::Min(30),
-            Constraint::Length(20),
-        ],
-    )
-    .header(header)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .title(title),
-    )
-    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
-    .highlight_symbol("▶ ");
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(title),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol(HIGHLIGHT_SYMBOL);
+
+    f.render_stateful_widget(
+        table,
+        area,
+        &mut TableState::default().with_selected(selected),
+    );
+}
+
+/// The repo name with the characters matched by the search highlighted.
+fn highlighted_name(name: &str, matched: &HashSet<usize>) -> Vec<Span<'static>> {
+    if matched.is_empty() {
+        return vec![Span::raw(name.to_string())];
+    }
+    let highlight = Style::default()
+        .fg(Color::Yellow)
+        .bold()
+        .add_modifier(Modifier::UNDERLINED);
+    let mut spans: Vec<Span> = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, c) in name.chars().enumerate() {
+        let is_match = matched.contains(&i);
+        if is_match != run_matched && !run.is_empty() {
+            let text = std::mem::take(&mut run);
+            spans.push(if run_matched {
+                Span::styled(text, highlight)
+            } else {
+                Span::raw(text)
+            });
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    spans.push(if run_matched {
+        Span::styled(run, highlight)
+    } else {
+        Span::raw(run)
+    });
+    spans
+}
+
+/// Screen areas of the sortable header cells, laid out the way `Table`
+/// does: after the highlight symbol, one space between columns.
+fn header_rects(
+    app: &App,
+    area: Rect,
+    columns: &[(&str, Option<SortColumn>, Constraint)],
+    widths: &[Constraint],
+) -> Vec<(Rect, SortColumn)> {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let selection_width = if app.state.selected().is_some() {
+        Line::from(HIGHLIGHT_SYMBOL).width() as u16
+    } else {
+        0
+    };
+    let header_row = Rect {
+        x: inner.x + selection_width,
+        width: inner.width.saturating_sub(selection_width),
+        height: 1,
+        ..inner
+    };
+    let cells = Layout::horizontal(widths.iter().copied())
+        .flex(Flex::Start)
+        .spacing(1)
+        .split(header_row);
+    columns
+        .iter()
+        .zip(cells.iter())
+        .filter_map(|((_, column, _), rect)| column.map(|c| (*rect, c)))
+        .collect()
+}
+
+/// Compact age for the list ("today", "3d", "5w", "2mo", "1y").
+pub fn short_age(now: DateTime<Utc>, at: DateTime<Utc>) -> String {
+    let days = (now - at).num_days();
+    match days {
+        ..1 => "today".to_string(),
+        1..14 => format!("{days}d"),
+        14..60 => format!("{}w", days / 7),
+        60..365 => format!("{}mo", days / 30),
+        _ => format!("{}y", days / 365),
+    }
+}
 
-    f.render_stateful_widget(table, area, &mut app.state);
+/// A small bar of the steps a fork has reached, e.g. `■■■□□`.
+fn step_bar(step: u8) -> String {
+    (1..=SYNC_STEPS)
+        .map(|n| if n <= step { '■' } else { '□' })
+        .collect()
 }
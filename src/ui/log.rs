@@ -0,0 +1,53 @@
+use crate::app::App;
+use crate::types::Fork;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+pub fn render_log_overlay(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let modal_width = 100.min(area.width.saturating_sub(4));
+    let modal_height = 24.min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let name = app.current_fork().map(Fork::full_name).unwrap_or_default();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Blue))
+        .title(format!(" Log: {name} "));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let log = app.current_log();
+    if log.is_empty() {
+        let widget = Paragraph::new("Nothing logged for this fork in this session.")
+            .style(Style::default().fg(Color::Gray))
+            .centered();
+        f.render_widget(widget, inner);
+        return;
+    }
+
+    // Show the tail that fits
+    let skip = log.len().saturating_sub(inner.height as usize);
+    let lines: Vec<Line> = log[skip..]
+        .iter()
+        .map(|line| {
+            if line.starts_with("$ ") {
+                Line::styled(line.clone(), Style::default().fg(Color::Cyan).bold())
+            } else {
+                Line::raw(line.clone())
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
@@ -1,19 +1,63 @@
+mod audit;
+mod changes;
+mod confirm;
 mod details;
 mod help;
 mod list;
+mod log;
 mod overlays;
+mod plan;
+mod preview;
+mod profiles;
+mod reconcile;
+mod reminders;
+mod risks;
 mod search;
+mod stashes;
+mod stats;
 mod title;
+mod usage;
+
+pub use list::short_age;
 
 use crate::app::App;
 use crate::types::Mode;
 use ratatui::prelude::*;
+use std::time::Duration;
+
+/// Short form of a duration ("45s", "3m 20s", "1h 05m").
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Human-readable byte count (`512 B`, `1.4 MB`, `2.0 GB`).
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
 
 pub fn render(f: &mut Frame, app: &mut App) {
     let area = f.area();
 
-    // Determine if we show details pane (need at least 100 chars width)
-    let show_details = area.width >= 100;
+    // Show the details pane when toggled on with `I`, or by default when
+    // there's room (at least 100 chars)
+    let show_details = app.details.forced.unwrap_or(area.width >= 100);
+    app.details.shown = show_details;
+    app.details.focused &= show_details;
 
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -50,6 +94,8 @@ pub fn render(f: &mut Frame, app: &mut App) {
     // Help bar or search input
     if app.mode == Mode::Search {
         search::render_search_input(f, app, main_chunks[2]);
+    } else if app.mode == Mode::Input {
+        search::render_prompt_input(f, app, main_chunks[2]);
     } else {
         help::render_help_bar(f, app, main_chunks[2]);
     }
@@ -60,7 +106,48 @@ pub fn render(f: &mut Frame, app: &mut App) {
     }
 
     if app.mode == Mode::StatsOverlay {
-        overlays::render_stats_overlay(f, app);
+        stats::render_stats_overlay(f, app);
+    }
+
+    if app.mode == Mode::UsageOverlay {
+        usage::render_usage_overlay(f, app);
+    }
+
+    if app.mode == Mode::ChangesOverlay {
+        changes::render_changes_overlay(f, app);
+    }
+
+    if app.mode == Mode::LogOverlay {
+        log::render_log_overlay(f, app);
+    }
+
+    if app.mode == Mode::PreviewOverlay {
+        preview::render_preview_overlay(f, app);
+    }
+
+    if app.mode == Mode::AuditOverlay {
+        audit::render_audit_overlay(f, app);
+    }
+
+    if app.mode == Mode::PlanOverlay {
+        overlays::render_modal(f, app);
+        plan::render_plan_overlay(f, app);
+    }
+
+    if app.mode == Mode::ReconcileOverlay {
+        reconcile::render_reconcile_overlay(f, app);
+    }
+
+    if app.mode == Mode::RemindersOverlay {
+        reminders::render_reminders_overlay(f, app);
+    }
+
+    if app.mode == Mode::StashesOverlay {
+        stashes::render_stashes_overlay(f, app);
+    }
+
+    if app.mode == Mode::ProfilesOverlay {
+        profiles::render_profiles_overlay(f, app);
     }
 
     if app.mode == Mode::ErrorPopup {
@@ -1,15 +1,25 @@
+use super::confirm::sync_target_lines;
+use super::risks::{risk_lines, risk_target_names};
 use crate::app::App;
-use crate::types::{ModalAction, ToastLevel};
+use crate::types::{CacheStatus, Fork, ModalAction, ToastLevel};
+use chrono::Local;
 use ratatui::{
     prelude::*,
-    widgets::{Bar, BarChart, BarGroup, Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
 };
 
 pub fn render_modal(f: &mut Frame, app: &App) {
     let area = f.area();
 
-    let modal_width = 50;
-    let modal_height = 9;
+    let risk_lines = match app.modal_action {
+        ModalAction::Detach => detach_lines(),
+        ModalAction::ForceSync => force_sync_lines(app),
+        ModalAction::Clone => clone_root_lines(app),
+        ModalAction::Sync | ModalAction::Resume => sync_target_lines(app),
+        _ => risk_lines(app),
+    };
+    let modal_width = if risk_lines.is_empty() { 50 } else { 64 };
+    let modal_height = 10 + risk_lines.len() as u16;
     let modal_area = Rect {
         x: area.width.saturating_sub(modal_width) / 2,
         y: area.height.saturating_sub(modal_height) / 2,
@@ -28,7 +38,9 @@ pub fn render_modal(f: &mut Frame, app: &App) {
                 .enumerate()
                 .filter(|(i, f)| app.selected[*i] && !f.is_cloned)
                 .count();
-            let clone_info = if not_cloned > 0 {
+            let clone_info = if app.cache_status == CacheStatus::Offline {
+                " (offline: local clones only)".to_string()
+            } else if not_cloned > 0 {
                 format!(" ({not_cloned} will be cloned)")
             } else {
                 String::new()
@@ -42,6 +54,17 @@ pub fn render_modal(f: &mut Frame, app: &App) {
                 ),
             )
         }
+        ModalAction::Resume => {
+            let count = app.resume.len();
+            (
+                " Resume Sync ",
+                format!(
+                    "Resume interrupted sync of {} fork{}?",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ),
+            )
+        }
         ModalAction::Clone => {
             let name = app
                 .current_fork()
@@ -49,35 +72,101 @@ pub fn render_modal(f: &mut Frame, app: &App) {
                 .unwrap_or_default();
             (" Confirm Clone ", format!("Clone {name}?"))
         }
-        ModalAction::Archive => {
-            let name = app
+        ModalAction::CloneAll => {
+            let count = app.uncloned_visible().len();
+            (
+                " Clone All ",
+                format!(
+                    "Clone {count} uncloned fork{}?",
+                    if count == 1 { "" } else { "s" }
+                ),
+            )
+        }
+        ModalAction::Archive => (
+            " ⚠ Archive Fork ",
+            format!("Archive {}? This cannot be undone.", risk_target_names(app)),
+        ),
+        ModalAction::PullRequest => {
+            let (head, base) = app
                 .current_fork()
-                .map(|f| format!("{}/{}", f.owner, f.name))
+                .map(|f| {
+                    (
+                        format!("{}:{}", f.owner, f.default_branch),
+                        format!("{}/{}", f.parent_owner, f.parent_name),
+                    )
+                })
                 .unwrap_or_default();
             (
-                " ⚠ Archive Fork ",
-                format!("Archive {name}? This cannot be undone."),
+                " Open Pull Request ",
+                format!("Open a PR from {head} to {base}?"),
             )
         }
-        ModalAction::Delete => {
-            let name = app
+        ModalAction::Visibility => {
+            let (name, visibility) = app
                 .current_fork()
-                .map(|f| format!("{}/{}", f.owner, f.name))
+                .map(|f| (f.full_name(), f.toggled_visibility().unwrap_or_default()))
                 .unwrap_or_default();
-            let cloned = app.current_fork().is_some_and(|f| f.is_cloned);
-            let extra = if cloned {
-                " Local clone will also be removed."
+            (" Change Visibility ", format!("Make {name} {visibility}?"))
+        }
+        ModalAction::ForceSync => (
+            " ⚠ Force Sync ",
+            format!(
+                "Force-sync {} with upstream?",
+                app.current_fork().map(Fork::full_name).unwrap_or_default()
+            ),
+        ),
+        ModalAction::Undo => (
+            " ⚠ Undo Last Sync ",
+            app.undo
+                .as_ref()
+                .map(|b| {
+                    format!(
+                        "Put {} of {} back to {} ({})?",
+                        b.branch,
+                        b.id,
+                        &b.oid[..b.oid.len().min(7)],
+                        b.created_at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                    )
+                })
+                .unwrap_or_default(),
+        ),
+        ModalAction::Detach => (
+            " ⚠ DETACH Fork ",
+            format!(
+                "Detach {} from its upstream?",
+                app.current_fork().map(Fork::full_name).unwrap_or_default()
+            ),
+        ),
+        ModalAction::Delete => {
+            let cloned = app
+                .risk_targets
+                .iter()
+                .any(|&i| app.forks.get(i).is_some_and(|f| f.is_cloned));
+            let subject = if app.risk_targets.len() == 1 {
+                "Local clone"
             } else {
-                ""
+                "Local clones"
+            };
+            let extra = match (cloned, app.config.trash_deleted_clones) {
+                (true, true) => format!(" {subject} will be moved to .trash."),
+                (true, false) => format!(" {subject} will also be removed."),
+                (false, _) => String::new(),
             };
             (
                 " ⚠ DELETE Fork ",
-                format!("Permanently delete {name}?{extra}"),
+                format!("Permanently delete {}?{extra}", risk_target_names(app)),
             )
         }
     };
 
-    let is_destructive = matches!(app.modal_action, ModalAction::Archive | ModalAction::Delete);
+    let is_destructive = matches!(
+        app.modal_action,
+        ModalAction::Archive
+            | ModalAction::Delete
+            | ModalAction::Detach
+            | ModalAction::ForceSync
+            | ModalAction::Undo
+    );
 
     let (cancel_style, proceed_style) = if app.modal_button == 0 {
         (
@@ -104,11 +193,14 @@ pub fn render_modal(f: &mut Frame, app: &App) {
         Span::styled(" [ PROCEED ] ", proceed_style),
     ]);
 
-    let text = vec![
+    let mut text = vec![
         Line::from(""),
         Line::from(message)
             .style(Style::default().bold())
             .centered(),
+    ];
+    text.extend(risk_lines);
+    text.extend([
         Line::from(""),
         Line::from(if app.dry_run {
             "(Dry run - no changes will be made)"
@@ -120,10 +212,16 @@ pub fn render_modal(f: &mut Frame, app: &App) {
         Line::from(""),
         buttons.centered(),
         Line::from(""),
-        Line::from("h/l: Switch | Enter: Select | Esc: Cancel")
-            .style(Style::default().fg(Color::DarkGray))
-            .centered(),
-    ];
+        Line::from(
+            if matches!(app.modal_action, ModalAction::Sync | ModalAction::Resume) {
+                "h/l: Switch | Enter: Select | p: Plan | Esc: Cancel"
+            } else {
+                "h/l: Switch | Enter: Select | Esc: Cancel"
+            },
+        )
+        .style(Style::default().fg(Color::DarkGray))
+        .centered(),
+    ]);
 
     let modal = Paragraph::new(text).block(
         Block::default()
@@ -140,77 +238,67 @@ pub fn render_modal(f: &mut Frame, app: &App) {
     f.render_widget(modal, modal_area);
 }
 
-pub fn render_stats_overlay(f: &mut Frame, app: &App) {
-    let area = f.area();
-
-    let modal_width = 60.min(area.width.saturating_sub(4));
-    let modal_height = 18.min(area.height.saturating_sub(4));
-    let modal_area = Rect {
-        x: area.width.saturating_sub(modal_width) / 2,
-        y: area.height.saturating_sub(modal_height) / 2,
-        width: modal_width,
-        height: modal_height,
-    };
-
-    f.render_widget(Clear, modal_area);
-
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Cyan))
-        .title(" Fork Statistics ");
-
-    let inner = block.inner(modal_area);
-    f.render_widget(block, modal_area);
-
-    if let Some(stats) = &app.stats_cache {
-        // Split inner area
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Summary
-                Constraint::Min(5),    // Charts
-            ])
-            .split(inner);
-
-        // Summary line
-        let summary = format!(
-            "Total: {} | Cloned: {} | Uncloned: {}",
-            stats.total, stats.cloned, stats.uncloned
+/// What a force-sync throws away.
+fn force_sync_lines(app: &App) -> Vec<Line<'static>> {
+    let warn = Style::default().fg(Color::Red).bold();
+    let mut lines = vec![
+        Line::styled("The fork's commits that aren't upstream", warn).centered(),
+        Line::styled("are discarded (gh repo sync --force).", warn).centered(),
+    ];
+    if let Some(fork) = app.current_fork().filter(|f| f.is_cloned) {
+        lines.push(
+            Line::styled(
+                format!("The clone's {} is reset to match;", fork.default_branch),
+                Style::default().fg(Color::Gray),
+            )
+            .centered(),
         );
-        let summary_widget = Paragraph::new(summary)
-            .style(Style::default().fg(Color::White).bold())
-            .centered();
-        f.render_widget(summary_widget, chunks[0]);
-
-        // Language bar chart
-        if !stats.by_language.is_empty() {
-            let bars: Vec<Bar> = stats
-                .by_language
-                .iter()
-                .map(|(lang, count)| {
-                    let label = if lang.len() > 8 {
-                        format!("{}…", &lang[..7])
-                    } else {
-                        lang.clone()
-                    };
-                    Bar::default()
-                        .value(*count)
-                        .label(Line::from(label))
-                        .style(Style::default().fg(Color::Cyan))
-                })
-                .collect();
+        lines.push(
+            Line::styled(
+                "uncommitted changes are kept.",
+                Style::default().fg(Color::Gray),
+            )
+            .centered(),
+        );
+    }
+    lines
+}
 
-            let chart = BarChart::default()
-                .block(Block::default().title(" Languages ").borders(Borders::TOP))
-                .data(BarGroup::default().bars(&bars))
-                .bar_width(8)
-                .bar_gap(1)
-                .value_style(Style::default().fg(Color::White).bold());
+/// What detaching means, and why PROCEED opens the browser.
+fn detach_lines() -> Vec<Line<'static>> {
+    let warn = Style::default().fg(Color::Red).bold();
+    vec![
+        Line::styled("It becomes a standalone repo: it can't sync", warn).centered(),
+        Line::styled("from or open PRs to upstream. No undo.", warn).centered(),
+        Line::styled(
+            "GitHub has no API for this: PROCEED opens the fork's",
+            Style::default().fg(Color::Gray),
+        )
+        .centered(),
+        Line::styled(
+            "settings (Danger Zone → Leave fork network).",
+            Style::default().fg(Color::Gray),
+        )
+        .centered(),
+    ]
+}
 
-            f.render_widget(chart, chunks[1]);
-        }
+/// With several tool homes, the roots a clone can go into (1-9 picks one).
+fn clone_root_lines(app: &App) -> Vec<Line<'static>> {
+    let roots = app.tool_homes.roots();
+    if roots.len() < 2 {
+        return Vec::new();
     }
+    let mut lines = vec![Line::from("")];
+    lines.extend(roots.iter().take(9).enumerate().map(|(i, root)| {
+        let (marker, style) = if i == app.clone_root {
+            ("▶", Style::default().fg(Color::Cyan).bold())
+        } else {
+            (" ", Style::default().fg(Color::Gray))
+        };
+        Line::styled(format!("{marker} {}  {}", i + 1, root.display()), style)
+    }));
+    lines
 }
 
 pub fn render_toasts(f: &mut Frame, app: &App) {
@@ -226,7 +314,9 @@ pub fn render_toasts(f: &mut Frame, app: &App) {
         let toast_height = 3;
 
         let x = area.width.saturating_sub(toast_width + 2);
-        let y = area.height.saturating_sub((i as u16 + 1) * (toast_height + 1) + 1);
+        let y = area
+            .height
+            .saturating_sub((i as u16 + 1) * (toast_height + 1) + 1);
 
         let toast_area = Rect {
             x,
@@ -268,8 +358,11 @@ pub fn render_error_popup(f: &mut Frame, app: &App) {
     // Calculate width based on message length, but clamp to reasonable bounds
     let content_width = details.message.lines().map(str::len).max().unwrap_or(40) as u16;
     let modal_width = (content_width + 6).clamp(40, area.width.saturating_sub(4));
-    let modal_height = if details.action.is_some() { 14 } else { 10 };
-    let modal_height = modal_height.min(area.height.saturating_sub(4));
+    let message_lines = details.message.lines().count() as u16;
+    let footer_height = if details.action.is_some() { 7 } else { 4 };
+    let modal_height = (message_lines + footer_height + 4)
+        .max(10)
+        .min(area.height.saturating_sub(4));
 
     let modal_area = Rect {
         x: area.width.saturating_sub(modal_width) / 2,
@@ -280,15 +373,32 @@ pub fn render_error_popup(f: &mut Frame, app: &App) {
 
     f.render_widget(Clear, modal_area);
 
-    let mut text = vec![
-        Line::from(""),
-        Line::from(Span::styled(
-            &details.message,
-            Style::default().fg(Color::White),
-        )),
-        Line::from(""),
-    ];
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Red))
+        .title(format!(" ⚠ {} ", details.title));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let [message_area, footer_area] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(footer_height)]).areas(inner);
+
+    // Long messages (full `gh`/`git` errors) scroll with j/k
+    let message: Vec<Line> = std::iter::once(Line::from(""))
+        .chain(
+            details
+                .message
+                .lines()
+                .map(|line| Line::from(Span::styled(line, Style::default().fg(Color::White)))),
+        )
+        .collect();
+    let message = Paragraph::new(message)
+        .wrap(Wrap { trim: true })
+        .scroll((app.error_scroll, 0));
+    f.render_widget(message, message_area);
 
+    let mut text = Vec::new();
     if let Some(action) = &details.action {
         // Determine button styles based on selection
         let (action_style, dismiss_style) = if app.modal_button == 0 {
@@ -343,15 +453,5 @@ pub fn render_error_popup(f: &mut Frame, app: &App) {
         );
     }
 
-    let modal = Paragraph::new(text)
-        .wrap(Wrap { trim: true })
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Red))
-                .title(format!(" ⚠ {} ", details.title)),
-        );
-
-    f.render_widget(modal, modal_area);
+    f.render_widget(Paragraph::new(text), footer_area);
 }
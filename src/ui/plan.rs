@@ -0,0 +1,77 @@
+use crate::app::App;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+pub fn render_plan_overlay(f: &mut Frame, app: &mut App) {
+    let Some(view) = &app.plan else {
+        return;
+    };
+    let area = f.area();
+
+    let modal_width = 100.min(area.width.saturating_sub(4));
+    let modal_height = 24.min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Sync Plan ");
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let Some(plans) = &view.plans else {
+        let widget = Paragraph::new("Checking clones...")
+            .style(Style::default().fg(Color::Gray))
+            .centered();
+        f.render_widget(widget, inner);
+        return;
+    };
+
+    let mut lines = Vec::new();
+    for plan in plans {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(
+            plan.id.clone(),
+            Style::default().fg(Color::Cyan).bold(),
+        ));
+        if let Some(reason) = &plan.skip {
+            lines.push(Line::styled(
+                format!("  skip: {reason}"),
+                Style::default().fg(Color::Yellow),
+            ));
+            continue;
+        }
+        lines.extend(plan.commands.iter().map(|command| {
+            if command.starts_with('#') {
+                Line::styled(format!("  {command}"), Style::default().fg(Color::DarkGray))
+            } else {
+                Line::from(vec![
+                    Span::styled("  $ ", Style::default().fg(Color::DarkGray)),
+                    Span::raw(command.clone()),
+                ])
+            }
+        }));
+    }
+
+    // Don't scroll past the last screenful
+    let max_scroll = lines.len().saturating_sub(inner.height as usize);
+    let scroll = u16::try_from(max_scroll)
+        .unwrap_or(u16::MAX)
+        .min(view.scroll);
+    f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner);
+    if let Some(view) = &mut app.plan {
+        view.scroll = scroll;
+    }
+}
@@ -0,0 +1,98 @@
+use crate::app::App;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+pub fn render_preview_overlay(f: &mut Frame, app: &mut App) {
+    let Some(preview) = &app.preview else {
+        return;
+    };
+    let area = f.area();
+
+    let modal_width = 100.min(area.width.saturating_sub(4));
+    let modal_height = 24.min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Incoming: {} ", preview.id));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let incoming = match &preview.result {
+        None => {
+            let widget = Paragraph::new("Fetching upstream...")
+                .style(Style::default().fg(Color::Gray))
+                .centered();
+            f.render_widget(widget, inner);
+            return;
+        }
+        Some(Err(err)) => {
+            let widget = Paragraph::new(format!("Preview failed: {err}"))
+                .style(Style::default().fg(Color::Red))
+                .centered();
+            f.render_widget(widget, inner);
+            return;
+        }
+        Some(Ok(incoming)) if incoming.commits.is_empty() => {
+            let widget = Paragraph::new("Already up to date with upstream.")
+                .style(Style::default().fg(Color::Green))
+                .centered();
+            f.render_widget(widget, inner);
+            return;
+        }
+        Some(Ok(incoming)) => incoming,
+    };
+
+    let mut lines = vec![Line::styled(
+        format!("{} incoming commit(s):", incoming.commits.len()),
+        Style::default().fg(Color::Cyan).bold(),
+    )];
+    lines.extend(incoming.commits.iter().map(|commit| {
+        let (sha, subject) = commit.split_once(' ').unwrap_or((commit, ""));
+        Line::from(vec![
+            Span::styled(sha.to_string(), Style::default().fg(Color::Yellow)),
+            Span::raw(format!(" {subject}")),
+        ])
+    }));
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        format!("{} changed file(s):", incoming.files.len()),
+        Style::default().fg(Color::Cyan).bold(),
+    ));
+    lines.extend(incoming.files.iter().map(|file| {
+        // "M\tpath", or "R100\told\tnew" for renames
+        let (change, path) = file.split_once('\t').unwrap_or(("", file));
+        let kind = change.get(..1).unwrap_or_default();
+        let color = match kind {
+            "A" => Color::Green,
+            "D" => Color::Red,
+            "R" | "C" => Color::Cyan,
+            _ => Color::Yellow,
+        };
+        Line::from(vec![
+            Span::styled(format!("{kind} "), Style::default().fg(color)),
+            Span::raw(path.replace('\t', " -> ")),
+        ])
+    }));
+
+    // Don't scroll past the last screenful
+    let max_scroll = lines.len().saturating_sub(inner.height as usize);
+    let scroll = u16::try_from(max_scroll)
+        .unwrap_or(u16::MAX)
+        .min(preview.scroll);
+    f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner);
+    if let Some(preview) = &mut app.preview {
+        preview.scroll = scroll;
+    }
+}
@@ -0,0 +1,64 @@
+use crate::app::App;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState},
+};
+
+pub fn render_profiles_overlay(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let choices = app.profile_choices();
+
+    let modal_width = 60.min(area.width.saturating_sub(4));
+    let modal_height = (choices.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let items: Vec<ListItem> = choices
+        .iter()
+        .map(|&choice| {
+            let name = choice.unwrap_or("(no profile)");
+            let mut spans = vec![Span::styled(
+                name.to_string(),
+                Style::default().fg(Color::Cyan),
+            )];
+            if let Some(profile) = choice.and_then(|c| app.config.profiles.get(c)) {
+                let details: Vec<&str> = [profile.account.as_deref(), profile.host.as_deref()]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                if !details.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  {}", details.join(" @ ")),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+            }
+            if choice == app.profile.as_deref() {
+                spans.push(Span::styled(
+                    "  (active)",
+                    Style::default().fg(Color::Green),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(" Profiles "),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(Some(app.profiles_selected));
+    f.render_stateful_widget(list, modal_area, &mut state);
+}
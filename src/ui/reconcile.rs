@@ -0,0 +1,121 @@
+use crate::app::App;
+use crate::types::{FindingKind, LocalFinding};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+pub fn render_reconcile_overlay(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let modal_width = 90.min(area.width.saturating_sub(4));
+    let modal_height = 22.min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" Local Clones ({} issues) ", app.reconcile.len()));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    if app.reconcile_scanning || app.reconcile.is_empty() {
+        let msg = if app.reconcile_scanning {
+            let roots = app
+                .tool_homes
+                .roots()
+                .iter()
+                .map(|r| r.display().to_string());
+            format!(
+                "{} Scanning {}...",
+                app.spinner(),
+                roots.collect::<Vec<_>>().join(", ")
+            )
+        } else {
+            "✓ Every clone under tool home matches a fork".to_string()
+        };
+        let widget = Paragraph::new(msg)
+            .style(Style::default().fg(Color::Gray))
+            .centered();
+        f.render_widget(widget, inner);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(5)])
+        .split(inner);
+
+    let items: Vec<ListItem> = app
+        .reconcile
+        .iter()
+        .map(|finding| {
+            let (tag, color) = match finding.kind {
+                FindingKind::Orphan { .. } => ("orphan  ", Color::Yellow),
+                FindingKind::OriginMismatch { .. } => ("mismatch", Color::Red),
+            };
+            let path = app.tool_homes.display(&finding.path);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{tag} "), Style::default().fg(color)),
+                Span::raw(path),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(Some(app.reconcile_selected));
+    f.render_stateful_widget(list, chunks[0], &mut state);
+
+    if let Some(finding) = app.current_finding() {
+        let details = Paragraph::new(finding_details(finding))
+            .block(Block::default().borders(Borders::TOP))
+            .wrap(Wrap { trim: true });
+        f.render_widget(details, chunks[1]);
+    }
+}
+
+fn finding_details(finding: &LocalFinding) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let origin = finding
+        .origin
+        .clone()
+        .unwrap_or_else(|| "(no origin remote)".to_string());
+
+    let explanation = match &finding.kind {
+        FindingKind::Orphan {
+            adopt_as: Some(fork),
+        } => format!("Not tracked by any fork, but its origin is your uncloned fork {fork}. Press a to adopt."),
+        FindingKind::Orphan { adopt_as: None } => {
+            "Not tracked by any listed fork.".to_string()
+        }
+        FindingKind::OriginMismatch { fork } => {
+            format!("This is where {fork} should live, but origin points elsewhere.")
+        }
+    };
+    let target = finding.suggested_path.as_ref().map_or_else(
+        || "(none available)".to_string(),
+        |p| p.display().to_string(),
+    );
+
+    vec![
+        Line::from(vec![
+            Span::styled("Origin: ", label),
+            Span::styled(origin, Style::default().fg(Color::Cyan)),
+        ]),
+        Line::from(explanation),
+        Line::from(vec![
+            Span::styled("Relocate to: ", label),
+            Span::styled(target, Style::default().fg(Color::Blue)),
+        ]),
+    ]
+}
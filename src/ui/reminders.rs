@@ -0,0 +1,64 @@
+use crate::app::App;
+use chrono::Local;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+pub fn render_reminders_overlay(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let modal_width = 80.min(area.width.saturating_sub(4));
+    let modal_height = 18.min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" Reminders ({}) ", app.reminders.len()));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    if app.reminders.is_empty() {
+        let widget = Paragraph::new("No reminders yet. Press r on a fork to add one.")
+            .style(Style::default().fg(Color::Gray))
+            .centered();
+        f.render_widget(widget, inner);
+        return;
+    }
+
+    let today = Local::now().date_naive();
+    let items: Vec<ListItem> = app
+        .sorted_reminders()
+        .into_iter()
+        .map(|(id, reminder)| {
+            let date_style = if reminder.is_due(today) {
+                Style::default().fg(Color::Red).bold()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let mut spans = vec![
+                Span::styled(format!("{} ", reminder.due), date_style),
+                Span::styled(id.clone(), Style::default().fg(Color::Cyan)),
+            ];
+            if !reminder.note.is_empty() {
+                spans.push(Span::raw(format!("  {}", reminder.note)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(Some(app.reminders_selected));
+    f.render_stateful_widget(list, inner, &mut state);
+}
@@ -0,0 +1,155 @@
+use crate::app::App;
+use crate::types::{Fork, ForkRisks, ModalAction, RiskCheck};
+use ratatui::prelude::*;
+
+/// Items listed per section before "...and N more".
+const MAX_LISTED: usize = 5;
+
+/// `owner/name` of the single Archive/Delete target, or `N forks`.
+pub fn risk_target_names(app: &App) -> String {
+    match app.risk_targets.as_slice() {
+        [idx] => app.forks.get(*idx).map(Fork::full_name).unwrap_or_default(),
+        targets => format!("{} forks", targets.len()),
+    }
+}
+
+/// Warning lines for the Archive/Delete modal: open PRs and branches ahead
+/// of upstream, in detail for one fork or as a line per fork for several.
+pub fn risk_lines(app: &App) -> Vec<Line<'static>> {
+    if !matches!(app.modal_action, ModalAction::Archive | ModalAction::Delete) {
+        return Vec::new();
+    }
+    let mut lines = if app.risk_targets.len() > 1 {
+        batch_lines(app)
+    } else {
+        single_lines(app)
+    };
+    if app.risks_acknowledged {
+        let verb = if app.modal_action == ModalAction::Delete {
+            "delete"
+        } else {
+            "archive"
+        };
+        lines.push(
+            Line::styled(
+                format!("Press PROCEED again to {verb} anyway"),
+                Style::default().fg(Color::Red).bold(),
+            )
+            .centered(),
+        );
+    }
+    lines
+}
+
+fn single_lines(app: &App) -> Vec<Line<'static>> {
+    let check = app
+        .risk_targets
+        .first()
+        .and_then(|&i| app.forks.get(i))
+        .and_then(|f| app.risks.get(&f.full_name()));
+    match check {
+        Some(RiskCheck::Checking) => vec![Line::styled(
+            "Checking for open PRs and unmerged branches...",
+            Style::default().fg(Color::DarkGray),
+        )
+        .centered()],
+        Some(RiskCheck::Found(risks)) => {
+            let mut lines = listed(
+                "branch",
+                "ahead of upstream",
+                &risks.ahead_branches,
+                Style::default().fg(Color::Red).bold(),
+            );
+            lines.extend(listed(
+                "open PR",
+                "to upstream",
+                &risks.open_prs,
+                Style::default().fg(Color::Yellow).bold(),
+            ));
+            lines
+        }
+        Some(RiskCheck::Failed(e)) => vec![
+            Line::styled(
+                "Could not check for open PRs and unmerged branches:",
+                Style::default().fg(Color::Yellow).bold(),
+            )
+            .centered(),
+            Line::raw(format!("  {e}")),
+        ],
+        None => Vec::new(),
+    }
+}
+
+/// One line per target: `owner/name  2 branches ahead, 1 open PR`.
+fn batch_lines(app: &App) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = app
+        .risk_targets
+        .iter()
+        .filter_map(|&i| app.forks.get(i))
+        .take(MAX_LISTED * 2)
+        .map(|fork| {
+            let id = fork.full_name();
+            let (summary, color) = match app.risks.get(&id) {
+                Some(RiskCheck::Checking) | None => ("checking...".to_string(), Color::DarkGray),
+                Some(RiskCheck::Found(risks)) if risks.is_empty() => {
+                    ("nothing at risk".to_string(), Color::Green)
+                }
+                Some(RiskCheck::Found(risks)) => (summarize(risks), Color::Red),
+                Some(RiskCheck::Failed(_)) => ("check failed".to_string(), Color::Yellow),
+            };
+            Line::from(vec![
+                Span::raw(format!("  {id}  ")),
+                Span::styled(summary, Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    if app.risk_targets.len() > MAX_LISTED * 2 {
+        lines.push(Line::raw(format!(
+            "  ...and {} more",
+            app.risk_targets.len() - MAX_LISTED * 2
+        )));
+    }
+    lines
+}
+
+fn summarize(risks: &ForkRisks) -> String {
+    let mut parts = Vec::new();
+    match risks.ahead_branches.len() {
+        0 => {}
+        1 => parts.push("1 branch ahead".to_string()),
+        n => parts.push(format!("{n} branches ahead")),
+    }
+    match risks.open_prs.len() {
+        0 => {}
+        1 => parts.push("1 open PR".to_string()),
+        n => parts.push(format!("{n} open PRs")),
+    }
+    parts.join(", ")
+}
+
+/// A `N things what:` heading followed by up to five of `items`.
+fn listed(thing: &str, what: &str, items: &[String], style: Style) -> Vec<Line<'static>> {
+    if items.is_empty() {
+        return Vec::new();
+    }
+    let plural = match (items.len(), thing.ends_with('h')) {
+        (1, _) => "",
+        (_, true) => "es",
+        _ => "s",
+    };
+    let mut lines =
+        vec![Line::styled(format!("{} {thing}{plural} {what}:", items.len()), style).centered()];
+    lines.extend(
+        items
+            .iter()
+            .take(MAX_LISTED)
+            .map(|item| Line::raw(format!("  {item}"))),
+    );
+    if items.len() > MAX_LISTED {
+        lines.push(Line::raw(format!(
+            "  ...and {} more",
+            items.len() - MAX_LISTED
+        )));
+    }
+    lines
+}
@@ -15,3 +15,19 @@ pub fn render_search_input(f: &mut Frame, app: &App, area: Rect) {
         );
     f.render_widget(input, area);
 }
+
+/// Render the active text prompt (`Mode::Input`) in place of the help bar.
+pub fn render_prompt_input(f: &mut Frame, app: &App, area: Rect) {
+    let Some(prompt) = &app.input else {
+        return;
+    };
+    let input = Paragraph::new(format!("{}_", prompt.buffer))
+        .style(Style::default().fg(Color::Yellow))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(format!(" {} ", prompt.label)),
+        );
+    f.render_widget(input, area);
+}
@@ -0,0 +1,69 @@
+use crate::app::App;
+use chrono::Local;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+pub fn render_stashes_overlay(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let modal_width = 90.min(area.width.saturating_sub(4));
+    let modal_height = (app.stashes.len() as u16 + 5).min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" Leftover Stashes ({}) ", app.stashes.len()));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let [intro, list_area] =
+        Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).areas(inner);
+    let text = Paragraph::new(
+        "An interrupted run stashed uncommitted changes in these clones and never put them back.",
+    )
+    .style(Style::default().fg(Color::Gray))
+    .wrap(Wrap { trim: true });
+    f.render_widget(text, intro);
+
+    let items: Vec<ListItem> = app
+        .stashes
+        .iter()
+        .map(|stash| {
+            ListItem::new(Line::from(vec![
+                Span::styled(stash.id.clone(), Style::default().fg(Color::Cyan)),
+                Span::raw(format!("  on {}", stash.branch)),
+                Span::styled(
+                    format!(
+                        "  {}  ",
+                        stash
+                            .stashed_at
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M")
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    stash.path.display().to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(Some(app.stashes_selected));
+    f.render_stateful_widget(list, list_area, &mut state);
+}
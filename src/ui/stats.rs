@@ -0,0 +1,201 @@
+use super::format_size;
+use crate::app::App;
+use crate::types::{ForkStats, WeekTrend};
+use ratatui::{
+    prelude::*,
+    widgets::{Bar, BarChart, BarGroup, Block, BorderType, Borders, Clear, Paragraph, Sparkline},
+};
+
+pub fn render_stats_overlay(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let modal_width = 110.min(area.width.saturating_sub(4));
+    let modal_height = 26.min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Fork Statistics ");
+
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    if let Some(stats) = &app.stats_cache {
+        let outcome_lines = outcome_lines(stats);
+        let [summary_area, columns] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(10)]).areas(inner);
+        // Fork list on the left, sync activity on the right
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .spacing(2)
+                .areas(columns);
+        let [languages_area, created_area] =
+            Layout::vertical([Constraint::Min(6), Constraint::Length(8)]).areas(left);
+        let [behind_area, trend_area, outcomes_area] = Layout::vertical([
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Min(outcome_lines.len() as u16 + 1),
+        ])
+        .areas(right);
+
+        // Summary line
+        let summary = format!(
+            "Total: {} | Cloned: {} | Uncloned: {} | Disk: {}",
+            stats.total,
+            stats.cloned,
+            stats.uncloned,
+            format_size(stats.disk_usage)
+        );
+        let summary_widget = Paragraph::new(summary)
+            .style(Style::default().fg(Color::White).bold())
+            .centered();
+        f.render_widget(summary_widget, summary_area);
+
+        // Language bar chart
+        if !stats.by_language.is_empty() {
+            let bars: Vec<Bar> = stats
+                .by_language
+                .iter()
+                .map(|(lang, count)| {
+                    let label = if lang.len() > 8 {
+                        format!("{}…", &lang[..7])
+                    } else {
+                        lang.clone()
+                    };
+                    Bar::default()
+                        .value(*count)
+                        .label(Line::from(label))
+                        .style(Style::default().fg(Color::Cyan))
+                })
+                .collect();
+
+            let chart = BarChart::default()
+                .block(Block::default().title(" Languages ").borders(Borders::TOP))
+                .data(BarGroup::default().bars(&bars))
+                .bar_width(8)
+                .bar_gap(1)
+                .value_style(Style::default().fg(Color::White).bold());
+
+            f.render_widget(chart, languages_area);
+        }
+
+        // When forks were created, per month
+        let bars: Vec<Bar> = stats
+            .created_by_month
+            .iter()
+            .map(|(month, count)| {
+                Bar::default()
+                    .value(*count)
+                    .label(Line::from(month.as_str()))
+                    .style(Style::default().fg(Color::Magenta))
+            })
+            .collect();
+        let chart = BarChart::default()
+            .block(
+                Block::default()
+                    .title(" Forks created per month ")
+                    .borders(Borders::TOP),
+            )
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(3)
+            .bar_gap(1)
+            .value_style(Style::default().fg(Color::White).bold());
+        f.render_widget(chart, created_area);
+
+        // How far behind the forks synced this session were
+        let bars: Vec<Bar> = stats
+            .behind_by
+            .iter()
+            .map(|(bucket, count)| {
+                Bar::default()
+                    .value(*count)
+                    .label(Line::from(*bucket))
+                    .style(Style::default().fg(Color::Green))
+            })
+            .collect();
+        let chart = BarChart::default()
+            .block(
+                Block::default()
+                    .title(" Commits behind (synced this session) ")
+                    .borders(Borders::TOP),
+            )
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(8)
+            .bar_gap(1)
+            .value_style(Style::default().fg(Color::White).bold());
+        f.render_widget(chart, behind_area);
+
+        render_trend(f, &stats.trend, trend_area);
+
+        let outcomes = Paragraph::new(outcome_lines).block(
+            Block::default()
+                .title(" This session ")
+                .borders(Borders::TOP),
+        );
+        f.render_widget(outcomes, outcomes_area);
+    }
+}
+
+/// Syncs per week and the share that failed, from the recorded history.
+fn render_trend(f: &mut Frame, trend: &[WeekTrend], area: Rect) {
+    let block = Block::default()
+        .title(if trend.is_empty() {
+            " Sync history (not recorded) ".to_string()
+        } else {
+            format!(" Sync history (last {} weeks) ", trend.len())
+        })
+        .borders(Borders::TOP);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let syncs: Vec<u64> = trend.iter().map(|w| w.syncs).collect();
+    let failures: Vec<u64> = trend.iter().map(WeekTrend::failure_percent).collect();
+    let rows = Layout::vertical([Constraint::Length(2), Constraint::Length(2)]).split(inner);
+    let series = [
+        ("Syncs/week", syncs, None, Color::Green),
+        ("Failed %", failures, Some(100), Color::Red),
+    ];
+    for ((label, data, max, color), row) in series.into_iter().zip(rows.iter()) {
+        let [label_area, spark_area] =
+            Layout::horizontal([Constraint::Length(12), Constraint::Min(1)]).areas(*row);
+        let latest = data.last().copied().unwrap_or_default();
+        f.render_widget(
+            Paragraph::new(vec![
+                Line::from(label),
+                Line::from(format!("now {latest}")).dark_gray(),
+            ]),
+            label_area,
+        );
+        let mut sparkline = Sparkline::default()
+            .data(&data)
+            .style(Style::default().fg(color));
+        if let Some(max) = max {
+            sparkline = sparkline.max(max);
+        }
+        f.render_widget(sparkline, spark_area);
+    }
+}
+
+/// Commits pulled and skip reasons of this session's syncs.
+fn outcome_lines(stats: &ForkStats) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(format!(
+        "Commits pulled: {}",
+        stats.commits_pulled
+    ))];
+    if stats.skipped_reasons.is_empty() {
+        lines.push(Line::from("Skipped: none").dark_gray());
+    }
+    for (reason, count) in stats.skipped_reasons.iter().take(4) {
+        lines.push(Line::from(format!("Skipped ({count}): {reason}")).yellow());
+    }
+    lines
+}
@@ -1,8 +1,9 @@
+use super::format_duration;
 use crate::app::App;
-use crate::types::{CacheStatus, Mode};
+use crate::types::{CacheStatus, ModalAction, Mode};
 use ratatui::{
     prelude::*,
-    widgets::{Block, BorderType, Borders, Paragraph},
+    widgets::{Block, BorderType, Borders, LineGauge, Paragraph},
 };
 
 pub fn render_title(f: &mut Frame, app: &App, area: Rect) {
@@ -18,11 +19,38 @@ pub fn render_title(f: &mut Frame, app: &App, area: Rect) {
         | Mode::ConfirmModal
         | Mode::Search
         | Mode::StatsOverlay
+        | Mode::UsageOverlay
+        | Mode::ChangesOverlay
+        | Mode::LogOverlay
+        | Mode::PreviewOverlay
+        | Mode::PlanOverlay
+        | Mode::AuditOverlay
+        | Mode::ReconcileOverlay
+        | Mode::RemindersOverlay
+        | Mode::StashesOverlay
+        | Mode::ProfilesOverlay
+        | Mode::Input
         | Mode::ErrorPopup => {
             let cloned = app.forks.iter().filter(|f| f.is_cloned).count();
             let uncloned = app.forks.len() - cloned;
+            let ignored = app.ignored_count();
+            let hidden_indicator = match (ignored, app.show_hidden) {
+                (0, _) => String::new(),
+                (n, true) => format!(", {n} ignored shown"),
+                (n, false) => format!(", {n} hidden"),
+            };
+            let truncated = if app.forks_truncated {
+                ", list truncated"
+            } else {
+                ""
+            };
+            let profile = app
+                .profile
+                .as_deref()
+                .map(|p| format!("[{p}] "))
+                .unwrap_or_default();
             format!(
-                " Repo Syncer {} | {} forks ({} cloned, {} uncloned) | {} selected{cache_indicator} ",
+                " Repo Syncer {profile}{} | {} forks ({} cloned, {} uncloned{hidden_indicator}{truncated}) | {} selected{cache_indicator} ",
                 if app.dry_run { "[DRY RUN]" } else { "" },
                 app.forks.len(),
                 cloned,
@@ -31,12 +59,30 @@ pub fn render_title(f: &mut Frame, app: &App, area: Rect) {
             )
         }
         Mode::Syncing => {
-            let (synced, skipped, failed) = app.summary();
-            let done = synced + skipped + failed;
+            let (synced, skipped, failed, attention) = app.summary();
+            let done = synced + skipped + failed + attention;
             let total = app.selected_count();
+            // Batch archive/delete reuses the syncing progress view
+            let verb = match app.modal_action {
+                ModalAction::CloneAll => "Cloning",
+                ModalAction::Archive => "Archiving",
+                ModalAction::Delete => "Deleting",
+                _ => "Syncing",
+            };
+            let eta = app
+                .eta()
+                .filter(|_| done < total && !app.is_held())
+                .map(|eta| format!(" ~{} left", format_duration(eta)))
+                .unwrap_or_default();
+            let elapsed = app
+                .batch_elapsed()
+                .map(|elapsed| format!(" {}", format_duration(elapsed)))
+                .unwrap_or_default();
             format!(
-                " Syncing {} ({}/{}) ",
+                " {verb} {}{}{} ({}/{}){elapsed}{eta} ",
                 if app.dry_run { "[DRY RUN]" } else { "" },
+                if app.is_slow_mode() { "[SLOW]" } else { "" },
+                if app.is_held() { "[PAUSED]" } else { "" },
                 done,
                 total
             )
@@ -51,4 +97,24 @@ pub fn render_title(f: &mut Frame, app: &App, area: Rect) {
                 .border_type(BorderType::Rounded),
         );
     f.render_widget(title_block, area);
+
+    if app.mode == Mode::Syncing {
+        render_batch_gauge(f, app, area);
+    }
+}
+
+/// Overall completion of the batch, right-aligned in the title bar.
+fn render_batch_gauge(f: &mut Frame, app: &App, area: Rect) {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let width = (inner.width / 3).clamp(20, 50).min(inner.width);
+    let gauge_area = Rect {
+        x: inner.right().saturating_sub(width + 1),
+        width,
+        ..inner
+    };
+    let gauge = LineGauge::default()
+        .ratio(app.batch_progress())
+        .filled_style(Style::default().fg(Color::Green))
+        .unfilled_style(Style::default().fg(Color::DarkGray));
+    f.render_widget(gauge, gauge_area);
 }
@@ -0,0 +1,104 @@
+use crate::app::App;
+use chrono::{Datelike, Utc};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+/// Width of the longest bar in the top-N lists.
+const BAR_WIDTH: u64 = 20;
+
+pub fn render_usage_overlay(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let modal_width = 64.min(area.width.saturating_sub(4));
+    let modal_height = 24.min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: area.width.saturating_sub(modal_width) / 2,
+        y: area.height.saturating_sub(modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    f.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Magenta))
+        .title(format!(" Your {} in Forks ", app.usage.started_at.year()));
+
+    let Some(stats) = &app.usage_stats else {
+        f.render_widget(block, modal_area);
+        return;
+    };
+
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White).bold();
+    let session_minutes = (Utc::now() - app.usage.started_at).num_minutes();
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Sessions: ", label),
+            Span::styled(stats.sessions.to_string(), value),
+            Span::styled("   Actions: ", label),
+            Span::styled(stats.action_count().to_string(), value),
+            Span::styled("   Per session: ", label),
+            Span::styled(format!("{:.1}", stats.actions_per_session()), value),
+        ]),
+        Line::from(vec![
+            Span::styled("This session: ", label),
+            Span::styled(
+                format!(
+                    "{} actions in {session_minutes} min",
+                    app.usage.action_count()
+                ),
+                value,
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Favourite features",
+            Style::default().fg(Color::Yellow).bold(),
+        )),
+    ];
+    lines.extend(bar_lines(&stats.actions, 5, Color::Cyan));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Most-used keys",
+        Style::default().fg(Color::Yellow).bold(),
+    )));
+    lines.extend(bar_lines(&stats.keys, 5, Color::Green));
+    lines.push(Line::from(""));
+    lines.push(
+        Line::from("Stored locally only. Disable with --no-usage-stats.")
+            .style(Style::default().fg(Color::DarkGray))
+            .centered(),
+    );
+
+    f.render_widget(Paragraph::new(lines).block(block), modal_area);
+}
+
+/// Render the top `limit` entries as `name ████ count` lines.
+fn bar_lines(counts: &[(String, u64)], limit: usize, color: Color) -> Vec<Line<'static>> {
+    if counts.is_empty() {
+        return vec![Line::from(Span::styled(
+            "  (nothing yet)",
+            Style::default().fg(Color::DarkGray),
+        ))];
+    }
+
+    let max = counts.iter().map(|(_, n)| *n).max().unwrap_or(1).max(1);
+    counts
+        .iter()
+        .take(limit)
+        .map(|(name, count)| {
+            let width = (count * BAR_WIDTH).div_ceil(max) as usize;
+            Line::from(vec![
+                Span::raw(format!("  {name:<16} ")),
+                Span::styled("█".repeat(width), Style::default().fg(color)),
+                Span::styled(format!(" {count}"), Style::default().fg(Color::White)),
+            ])
+        })
+        .collect()
+}